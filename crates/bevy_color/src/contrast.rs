@@ -0,0 +1,27 @@
+use crate::LinearRgba;
+
+/// Contrast ratio between two colors as defined by WCAG 2.1, from 1.0 (identical luminance)
+/// to 21.0 (black and white). The order of the colors doesn't matter. WCAG AA requires a ratio
+/// of at least 4.5 for body text, and 3.0 for large text.
+pub fn contrast_ratio(a: impl Into<LinearRgba>, b: impl Into<LinearRgba>) -> f32 {
+    let la = a.into().relative_luminance();
+    let lb = b.into().relative_luminance();
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::assert_approx_eq, SRgba};
+
+    #[test]
+    fn test_contrast_ratio() {
+        assert_approx_eq!(contrast_ratio(SRgba::WHITE, SRgba::BLACK), 21., 0.001);
+        assert_approx_eq!(contrast_ratio(SRgba::BLACK, SRgba::WHITE), 21., 0.001);
+        let gray = SRgba::rgb_u8(136, 136, 136);
+        assert_approx_eq!(contrast_ratio(gray, gray), 1., 0.001);
+        // #777 on white is just short of AA; #767676 is the lightest gray that passes.
+        assert!(contrast_ratio(SRgba::rgb_u8(0x77, 0x77, 0x77), SRgba::WHITE) < 4.5);
+        assert!(contrast_ratio(SRgba::rgb_u8(0x76, 0x76, 0x76), SRgba::WHITE) >= 4.5);
+    }
+}
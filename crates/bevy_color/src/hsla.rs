@@ -31,7 +31,7 @@ impl Hsla {
         }
     }
 
-    /// Convert the Oklaba color to a tuple of components (h, s, l, a). This is useful
+    /// Convert the Hsla color to a tuple of components (h, s, l, a). This is useful
     /// when you need to transmute the data type of a color to a different type without converting
     /// the values.
     #[inline]
@@ -39,10 +39,48 @@ impl Hsla {
         (self.hue, self.saturation, self.lightness, self.alpha)
     }
 
-    /// Construct a new [`Oklaba`] color from a tuple of components (h, s, l, a).
+    /// Construct a new [`Hsla`] color from a tuple of components (h, s, l, a).
     #[inline]
-    pub const fn from_components((l, a, b, alpha): (f32, f32, f32, f32)) -> Self {
-        Self::new(l, a, b, alpha)
+    pub const fn from_components((h, s, l, a): (f32, f32, f32, f32)) -> Self {
+        Self::new(h, s, l, a)
+    }
+
+    /// Return a copy of this color with `amount` added to the lightness, clamped to
+    /// [0.0, 1.0].
+    pub fn lighten(&self, amount: f32) -> Self {
+        Self {
+            lightness: (self.lightness + amount).clamp(0., 1.),
+            ..*self
+        }
+    }
+
+    /// Return a copy of this color with `amount` subtracted from the lightness, clamped to
+    /// [0.0, 1.0].
+    pub fn darken(&self, amount: f32) -> Self {
+        self.lighten(-amount)
+    }
+
+    /// Return a copy of this color with `amount` added to the saturation, clamped to
+    /// [0.0, 1.0].
+    pub fn saturate(&self, amount: f32) -> Self {
+        Self {
+            saturation: (self.saturation + amount).clamp(0., 1.),
+            ..*self
+        }
+    }
+
+    /// Return a copy of this color with `amount` subtracted from the saturation, clamped to
+    /// [0.0, 1.0].
+    pub fn desaturate(&self, amount: f32) -> Self {
+        self.saturate(-amount)
+    }
+
+    /// Return a copy of this color with the given alpha, clamped to [0.0, 1.0].
+    pub fn with_alpha(&self, alpha: f32) -> Self {
+        Self {
+            alpha: alpha.clamp(0., 1.),
+            ..*self
+        }
     }
 }
 
@@ -143,6 +181,29 @@ mod tests {
         assert_eq!(Hsla::from(SRgba::NONE).to_css_string(), "hsl(0deg 0% 0% 0)");
     }
 
+    #[test]
+    fn test_adjust() {
+        let base = Hsla::new(200., 0.5, 0.16, 0.8);
+        let lighter = base.lighten(0.09);
+        assert_approx_eq!(lighter.lightness, 0.25, 0.001);
+        assert_eq!(lighter.hue, base.hue);
+        assert_eq!(lighter.saturation, base.saturation);
+        assert_eq!(lighter.alpha, base.alpha);
+        assert_eq!(base.darken(0.06).alpha, base.alpha);
+        assert_approx_eq!(base.saturate(0.2).saturation, 0.7, 0.001);
+        assert_approx_eq!(base.desaturate(0.2).saturation, 0.3, 0.001);
+        assert_eq!(base.with_alpha(0.5).alpha, 0.5);
+        assert_eq!(base.with_alpha(0.5).lightness, base.lightness);
+
+        // Components are clamped at the extremes.
+        assert_eq!(base.lighten(2.).lightness, 1.);
+        assert_eq!(base.darken(2.).lightness, 0.);
+        assert_eq!(base.saturate(2.).saturation, 1.);
+        assert_eq!(base.desaturate(2.).saturation, 0.);
+        assert_eq!(base.with_alpha(1.5).alpha, 1.);
+        assert_eq!(base.with_alpha(-0.5).alpha, 0.);
+    }
+
     #[test]
     fn test_mix_wrap() {
         let hsla0 = Hsla::new(10., 0.5, 0.5, 1.0);
@@ -0,0 +1,185 @@
+use crate::{to_css_string::*, Hsla, LinearRgba, Mix, SRgba};
+use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
+use serde::{Deserialize, Serialize};
+
+/// Color in Hue-Saturation-Value color space with alpha
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub struct Hsva {
+    pub hue: f32,
+    pub saturation: f32,
+    pub value: f32,
+    pub alpha: f32,
+}
+
+impl Hsva {
+    /// Construct a new [`Hsva`] color from components.
+    ///
+    /// # Arguments
+    ///
+    /// * `hue` - Hue channel. [0.0, 360.0]
+    /// * `saturation` - Saturation channel. [0.0, 1.0]
+    /// * `value` - Value channel. [0.0, 1.0]
+    /// * `alpha` - Alpha channel. [0.0, 1.0]
+    pub const fn new(hue: f32, saturation: f32, value: f32, alpha: f32) -> Self {
+        Self {
+            hue,
+            saturation,
+            value,
+            alpha,
+        }
+    }
+
+    /// Convert the Hsva color to a tuple of components (h, s, v, a). This is useful
+    /// when you need to transmute the data type of a color to a different type without converting
+    /// the values.
+    #[inline]
+    pub const fn to_components(&self) -> (f32, f32, f32, f32) {
+        (self.hue, self.saturation, self.value, self.alpha)
+    }
+
+    /// Construct a new [`Hsva`] color from a tuple of components (h, s, v, a).
+    #[inline]
+    pub const fn from_components((h, s, v, a): (f32, f32, f32, f32)) -> Self {
+        Self::new(h, s, v, a)
+    }
+}
+
+impl Default for Hsva {
+    fn default() -> Self {
+        Self::new(0., 0., 0., 1.)
+    }
+}
+
+impl ToCssString for Hsva {
+    /// CSS has no HSV notation, so this uses the equivalent `hwb()` form.
+    fn to_css_string(&self) -> String {
+        format!(
+            "hwb({}deg {}% {}% {})",
+            self.hue.round_to_decimal_places(6),
+            ((1. - self.saturation) * self.value * 100.).round_to_decimal_places(3),
+            ((1. - self.value) * 100.).round_to_decimal_places(3),
+            self.alpha
+        )
+    }
+}
+
+impl Mix for Hsva {
+    #[inline]
+    fn mix(&self, other: &Self, factor: f32) -> Self {
+        let n_factor = 1.0 - factor;
+        // TODO: Refactor this into EuclideanModulo::lerp_modulo
+        let shortest_angle = ((((other.hue - self.hue) % 360.) + 540.) % 360.) - 180.;
+        let mut hue = self.hue + shortest_angle * factor;
+        if hue < 0. {
+            hue += 360.;
+        } else if hue >= 360. {
+            hue -= 360.;
+        }
+        Self {
+            hue,
+            saturation: self.saturation * n_factor + other.saturation * factor,
+            value: self.value * n_factor + other.value * factor,
+            alpha: self.alpha * n_factor + other.alpha * factor,
+        }
+    }
+}
+
+impl From<Hsla> for Hsva {
+    fn from(value: Hsla) -> Self {
+        let Hsla {
+            hue,
+            saturation,
+            lightness,
+            alpha,
+        } = value;
+        let v = lightness + saturation * lightness.min(1. - lightness);
+        let s = if v == 0. {
+            0.
+        } else {
+            2. * (1. - lightness / v)
+        };
+        Self::new(hue, s, v, alpha)
+    }
+}
+
+impl From<Hsva> for Hsla {
+    fn from(value: Hsva) -> Self {
+        let Hsva {
+            hue,
+            saturation,
+            value,
+            alpha,
+        } = value;
+        let l = value * (1. - saturation * 0.5);
+        let s = if l == 0. || l == 1. {
+            0.
+        } else {
+            (value - l) / l.min(1. - l)
+        };
+        Self::new(hue, s, l, alpha)
+    }
+}
+
+impl From<SRgba> for Hsva {
+    fn from(value: SRgba) -> Self {
+        Hsva::from(Hsla::from(value))
+    }
+}
+
+impl From<LinearRgba> for Hsva {
+    fn from(value: LinearRgba) -> Self {
+        Hsva::from(SRgba::from(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::assert_approx_eq;
+
+    #[test]
+    fn test_to_from_srgba() {
+        let hsva = Hsva::new(0.5, 0.5, 0.5, 1.0);
+        let srgba: SRgba = hsva.into();
+        let hsva2: Hsva = srgba.into();
+        assert_approx_eq!(hsva.hue, hsva2.hue, 0.001);
+        assert_approx_eq!(hsva.saturation, hsva2.saturation, 0.001);
+        assert_approx_eq!(hsva.value, hsva2.value, 0.001);
+        assert_approx_eq!(hsva.alpha, hsva2.alpha, 0.001);
+    }
+
+    #[test]
+    fn test_to_from_linear() {
+        let hsva = Hsva::new(0.5, 0.5, 0.5, 1.0);
+        let linear: LinearRgba = hsva.into();
+        let hsva2: Hsva = linear.into();
+        assert_approx_eq!(hsva.hue, hsva2.hue, 0.001);
+        assert_approx_eq!(hsva.saturation, hsva2.saturation, 0.001);
+        assert_approx_eq!(hsva.value, hsva2.value, 0.001);
+        assert_approx_eq!(hsva.alpha, hsva2.alpha, 0.001);
+    }
+
+    #[test]
+    fn test_from_srgba() {
+        let red: Hsva = SRgba::RED.into();
+        assert_eq!(red, Hsva::new(0., 1., 1., 1.));
+        let white: Hsva = SRgba::WHITE.into();
+        assert_approx_eq!(white.saturation, 0., 0.001);
+        assert_approx_eq!(white.value, 1., 0.001);
+        let black: Hsva = SRgba::BLACK.into();
+        assert_approx_eq!(black.value, 0., 0.001);
+    }
+
+    #[test]
+    fn to_css_string() {
+        assert_eq!(
+            Hsva::from(SRgba::WHITE).to_css_string(),
+            "hwb(0deg 100% 0% 1)"
+        );
+        assert_eq!(
+            Hsva::from(SRgba::BLUE).to_css_string(),
+            "hwb(240deg 0% 0% 1)"
+        );
+    }
+}
@@ -5,7 +5,8 @@
 //! - [`SRgba`] (standard RGBA, with gamma correction)
 //! - [`LinearRgba`] (linear RGBA, without gamma correction)
 //! - [`Hsla`] (hue, saturation, lightness, alpha)
-//! - [`Oklaba`] (hue, chroma, lightness, alpha)
+//! - [`Hsva`] (hue, saturation, value, alpha)
+//! - [`Oklaba`] (lightness, green-red, blue-yellow, alpha)
 //!
 //! Each of these color spaces is represented as distinct Rust types. Colors can be converted
 //! from one color space to another using the [`From`] trait.
@@ -27,9 +28,12 @@
 //! ```
 mod color_range;
 mod color_representation;
+mod contrast;
 mod hsla;
+mod hsva;
 mod linear_rgba;
 mod mix;
+mod named_colors;
 mod oklaba;
 mod srgba;
 mod testing;
@@ -37,7 +41,9 @@ mod to_css_string;
 
 pub use color_range::*;
 pub use color_representation::*;
+pub use contrast::*;
 pub use hsla::*;
+pub use hsva::*;
 pub use linear_rgba::*;
 pub use mix::*;
 pub use oklaba::*;
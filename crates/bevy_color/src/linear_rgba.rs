@@ -1,4 +1,4 @@
-use crate::{oklaba::Oklaba, to_css_string::ToCssString, Hsla, Mix, SRgba};
+use crate::{oklaba::Oklaba, to_css_string::ToCssString, Hsla, Hsva, Mix, SRgba};
 use bevy::render::color::SrgbColorSpace;
 use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
 use serde::{Deserialize, Serialize};
@@ -39,6 +39,12 @@ impl LinearRgba {
     pub const fn from_components((red, green, blue, alpha): (f32, f32, f32, f32)) -> Self {
         Self::new(red, green, blue, alpha)
     }
+
+    /// Relative luminance of the color as defined by WCAG 2.1, from 0.0 (black) to 1.0
+    /// (white). Alpha is ignored.
+    pub fn relative_luminance(&self) -> f32 {
+        0.2126 * self.red + 0.7152 * self.green + 0.0722 * self.blue
+    }
 }
 
 impl Default for LinearRgba {
@@ -123,6 +129,13 @@ impl From<Hsla> for LinearRgba {
     }
 }
 
+impl From<Hsva> for LinearRgba {
+    #[inline]
+    fn from(value: Hsva) -> Self {
+        LinearRgba::from(SRgba::from(value))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -133,6 +133,23 @@ mod tests {
         assert_approx_eq!(oklaba.alpha, oklaba2.alpha, 0.001);
     }
 
+    #[test]
+    fn test_mix() {
+        let black: Oklaba = SRgba::BLACK.into();
+        let white: Oklaba = SRgba::WHITE.into();
+        // Lightness is perceptually uniform, so the midpoint is halfway in L rather than in
+        // linear light.
+        let gray = black.mix(&white, 0.5);
+        assert_approx_eq!(gray.l, 0.5, 0.001);
+        assert_approx_eq!(gray.a, 0., 0.001);
+        assert_approx_eq!(gray.b, 0., 0.001);
+        assert_eq!(black.mix(&white, 0.), black);
+        assert_eq!(black.mix(&white, 1.), white);
+
+        let red = Oklaba::from(SRgba::RED).mix(&Oklaba::from(SRgba::NONE), 0.5);
+        assert_approx_eq!(red.alpha, 0.5, 0.001);
+    }
+
     #[test]
     fn to_css_string() {
         assert_eq!(
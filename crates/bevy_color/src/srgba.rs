@@ -1,9 +1,11 @@
+use crate::named_colors::CSS_NAMED_COLORS;
 use crate::oklaba::Oklaba;
 use crate::to_css_string::ToCssString;
-use crate::{Hsla, LinearRgba, Mix};
+use crate::{Hsla, Hsva, LinearRgba, Mix};
 use bevy::render::color::{HexColorError, HslRepresentation, SrgbColorSpace};
 use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// Non-linear standard RGB with alpha.
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
@@ -176,6 +178,42 @@ impl SRgba {
         }
     }
 
+    /// Parse a CSS hex color: `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa`. Unlike [`SRgba::hex`],
+    /// the leading `#` is required.
+    ///
+    /// ```
+    /// # use bevy_color::SRgba;
+    /// assert_eq!(SRgba::from_hex("#fff"), Ok(SRgba::WHITE));
+    /// assert!(SRgba::from_hex("fff").is_err());
+    /// ```
+    pub fn from_hex(hex: &str) -> Result<Self, ColorParseError> {
+        match hex.strip_prefix('#') {
+            Some(digits) => Self::hex(digits).map_err(ColorParseError::Hex),
+            None => Err(ColorParseError::MissingHash),
+        }
+    }
+
+    /// Look up one of the CSS named colors, such as `"cornflowerblue"`, or `"transparent"`.
+    /// Names are case-insensitive.
+    ///
+    /// ```
+    /// # use bevy_color::SRgba;
+    /// assert_eq!(SRgba::from_css_name("Red"), Ok(SRgba::RED));
+    /// ```
+    pub fn from_css_name(name: &str) -> Result<Self, ColorParseError> {
+        let name = name.to_ascii_lowercase();
+        if name == "transparent" {
+            return Ok(Self::NONE);
+        }
+        CSS_NAMED_COLORS
+            .binary_search_by_key(&name.as_str(), |(n, _)| *n)
+            .map(|index| {
+                let [r, g, b] = CSS_NAMED_COLORS[index].1;
+                Self::rgb_u8(r, g, b)
+            })
+            .map_err(|_| ColorParseError::UnknownName(name))
+    }
+
     /// New `SRgba` from sRGB colorspace.
     ///
     /// # Arguments
@@ -264,12 +302,41 @@ impl From<Hsla> for SRgba {
     }
 }
 
+impl From<Hsva> for SRgba {
+    fn from(value: Hsva) -> Self {
+        SRgba::from(Hsla::from(value))
+    }
+}
+
 impl From<Oklaba> for SRgba {
     fn from(value: Oklaba) -> Self {
         SRgba::from(LinearRgba::from(value))
     }
 }
 
+/// Error returned when a color string can't be parsed.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ColorParseError {
+    /// A hex color didn't start with `#`.
+    MissingHash,
+    /// A hex color had the wrong number of digits, or a character that isn't a hex digit.
+    Hex(HexColorError),
+    /// The string isn't one of the CSS named colors.
+    UnknownName(String),
+}
+
+impl fmt::Display for ColorParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColorParseError::MissingHash => write!(f, "Hex color must start with '#'"),
+            ColorParseError::Hex(err) => write!(f, "{}", err),
+            ColorParseError::UnknownName(name) => write!(f, "Unknown color name: {}", name),
+        }
+    }
+}
+
+impl std::error::Error for ColorParseError {}
+
 /// Converts hex bytes to an array of RGB\[A\] components
 ///
 /// # Example
@@ -348,6 +415,44 @@ mod tests {
         assert_eq!(SRgba::hex("##fff"), Err(HexColorError::Char('#')));
     }
 
+    #[test]
+    fn from_hex() {
+        assert_eq!(SRgba::from_hex("#888"), Ok(SRgba::rgb_u8(136, 136, 136)));
+        assert_eq!(SRgba::from_hex("#0008"), Ok(SRgba::rgba_u8(0, 0, 0, 136)));
+        assert_eq!(SRgba::from_hex("#222222"), Ok(SRgba::rgb_u8(34, 34, 34)));
+        assert_eq!(
+            SRgba::from_hex("#22222280"),
+            Ok(SRgba::rgba_u8(34, 34, 34, 128))
+        );
+        assert_eq!(SRgba::from_hex("222"), Err(ColorParseError::MissingHash));
+        assert_eq!(
+            SRgba::from_hex("#12345"),
+            Err(ColorParseError::Hex(HexColorError::Length))
+        );
+        assert_eq!(
+            SRgba::from_hex("#ggg"),
+            Err(ColorParseError::Hex(HexColorError::Char('g')))
+        );
+    }
+
+    #[test]
+    fn from_css_name() {
+        assert_eq!(SRgba::from_css_name("white"), Ok(SRgba::WHITE));
+        assert_eq!(
+            SRgba::from_css_name("CornflowerBlue"),
+            Ok(SRgba::rgb_u8(100, 149, 237))
+        );
+        assert_eq!(
+            SRgba::from_css_name("rebeccapurple"),
+            Ok(SRgba::rgb_u8(102, 51, 153))
+        );
+        assert_eq!(SRgba::from_css_name("transparent"), Ok(SRgba::NONE));
+        assert_eq!(
+            SRgba::from_css_name("blurple"),
+            Err(ColorParseError::UnknownName("blurple".to_string()))
+        );
+    }
+
     #[test]
     fn to_css_string() {
         assert_eq!(SRgba::WHITE.to_css_string(), "rgba(255 255 255 1)");
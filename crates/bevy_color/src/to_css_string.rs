@@ -7,8 +7,8 @@ pub trait ToCssString {
     /// use bevy_color::SRgba;
     /// use bevy_color::Oklaba;
     /// use bevy_color::ToCssString;
-    /// let css = SRgba::WHITE.to_css_string(); // "rgba(255 0 0 1)"
-    /// let css = Oklaba::from(SRgba::RED).to_css_string(); // "color(oklab 62.796% -0.005 0.123 1)"
+    /// let css = SRgba::WHITE.to_css_string(); // "rgba(255 255 255 1)"
+    /// let css = Oklaba::from(SRgba::RED).to_css_string(); // "color(oklab 62.796% 0.224863 0.125846 1)"
     /// ```
     fn to_css_string(&self) -> String;
 }
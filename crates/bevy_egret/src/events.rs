@@ -1,3 +1,4 @@
+use bevy::a11y::Focus;
 use bevy::ecs::event::Event;
 use bevy::prelude::*;
 use bevy_mod_picking::prelude::*;
@@ -15,7 +16,40 @@ impl Plugin for EgretEventsPlugin {
         .add_event::<Clicked>()
         .add_event::<ValueChanged<f32>>()
         .add_event::<MenuEvent>()
-        .add_event::<SplitterEvent>();
+        .add_event::<SplitterEvent>()
+        .add_systems(Update, keyboard_activation);
+    }
+}
+
+/// Component which allows a widget to be activated via the keyboard when it has focus.
+/// Pressing Enter or Space sends a [`Clicked`] event, the same as clicking with the pointer.
+#[derive(Component, Clone)]
+pub struct KeyboardActivation {
+    pub id: &'static str,
+    pub disabled: bool,
+}
+
+/// Sends a [`Clicked`] event when the focused widget is activated with Enter or Space.
+pub fn keyboard_activation(
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    focus: Res<Focus>,
+    query: Query<&KeyboardActivation>,
+    mut writer: EventWriter<Clicked>,
+) {
+    let Some(focus) = focus.0 else {
+        return;
+    };
+    let Ok(activation) = query.get(focus) else {
+        return;
+    };
+    for key in [KeyCode::Enter, KeyCode::NumpadEnter, KeyCode::Space] {
+        // Consume the key so that it doesn't also scroll or trigger other handlers.
+        if keys.clear_just_pressed(key) && !activation.disabled {
+            writer.send(Clicked {
+                target: focus,
+                id: activation.id,
+            });
+        }
     }
 }
 
@@ -82,3 +116,68 @@ pub struct SplitterEvent {
     pub id: &'static str,
     pub value: f32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup(activation: KeyboardActivation) -> (App, Entity) {
+        let mut app = App::new();
+        app.init_resource::<ButtonInput<KeyCode>>()
+            .add_event::<Clicked>()
+            .add_systems(Update, keyboard_activation);
+        let button = app.world.spawn(activation).id();
+        app.insert_resource(Focus(Some(button)));
+        (app, button)
+    }
+
+    #[test]
+    fn test_enter_activates() {
+        let (mut app, button) = setup(KeyboardActivation {
+            id: "ok",
+            disabled: false,
+        });
+        app.world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Enter);
+        app.update();
+
+        let events = app.world.resource::<Events<Clicked>>();
+        let clicked: Vec<&Clicked> = events.get_reader().read(events).collect();
+        assert_eq!(clicked.len(), 1);
+        assert_eq!(clicked[0].target, button);
+        assert_eq!(clicked[0].id, "ok");
+    }
+
+    #[test]
+    fn test_space_consumed() {
+        let (mut app, _) = setup(KeyboardActivation {
+            id: "ok",
+            disabled: false,
+        });
+        app.world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Space);
+        app.update();
+
+        assert_eq!(app.world.resource::<Events<Clicked>>().len(), 1);
+        assert!(!app
+            .world
+            .resource::<ButtonInput<KeyCode>>()
+            .just_pressed(KeyCode::Space));
+    }
+
+    #[test]
+    fn test_disabled() {
+        let (mut app, _) = setup(KeyboardActivation {
+            id: "ok",
+            disabled: true,
+        });
+        app.world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Enter);
+        app.update();
+
+        assert!(app.world.resource::<Events<Clicked>>().is_empty());
+    }
+}
@@ -9,7 +9,7 @@ use bevy_mod_picking::{events::PointerCancel, prelude::*};
 use bevy_quill::prelude::*;
 use bevy_tabindex::TabIndex;
 
-use crate::Clicked;
+use crate::{Clicked, KeyboardActivation};
 
 const CLS_PRESSED: &str = "pressed";
 const CLS_DISABLED: &str = "disabled";
@@ -67,6 +67,9 @@ pub fn button<'a, V: View + Clone, S: StyleTuple, C: ClassNames<'a>>(
                 }
             }),
         ))
+        .with(move |mut e| {
+            e.insert(KeyboardActivation { id, disabled });
+        })
         .styled(cx.props.style.clone())
         .children(cx.props.children.clone())
 }
@@ -1,9 +1,11 @@
 mod button;
 mod menu;
 mod slider;
+mod spinner;
 mod splitter;
 
 pub use button::*;
 pub use menu::*;
 pub use slider::*;
+pub use spinner::*;
 pub use splitter::*;
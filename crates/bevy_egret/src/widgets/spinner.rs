@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+use bevy_quill::prelude::*;
+use static_init::dynamic;
+
+#[dynamic]
+static STYLE_SPINNER: StyleHandle = StyleHandle::build(|ss| {
+    ss.width(24)
+        .height(24)
+        .border(3)
+        .border_color(Color::rgba(1., 1., 1., 0.2))
+});
+
+/// Properties for the spinner widget.
+#[derive(Clone, PartialEq)]
+pub struct SpinnerProps<S: StyleTuple = ()> {
+    /// Rotation speed, in radians per second.
+    pub speed: f32,
+
+    /// Additional styles for the spinner element.
+    pub style: S,
+}
+
+impl<S: StyleTuple + Default> Default for SpinnerProps<S> {
+    fn default() -> Self {
+        Self {
+            speed: std::f32::consts::TAU,
+            style: S::default(),
+        }
+    }
+}
+
+/// A continuously rotating loading indicator. The rotation is time-based, and stops when the
+/// spinner is unmounted, since the animation lives on the spinner's own entity.
+pub fn spinner<S: StyleTuple>(cx: Cx<SpinnerProps<S>>) -> impl View {
+    let speed = cx.props.speed;
+    // Changing the speed preserves the current angle, so the rotation doesn't jump.
+    let spin = StyleHandle::build(|ss| ss.spin(speed));
+    Element::new()
+        .named("spinner")
+        .styled((STYLE_SPINNER.clone(), spin, cx.props.style.clone()))
+}
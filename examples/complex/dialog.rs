@@ -108,7 +108,7 @@ pub fn dialog(mut cx: Cx<DemoDialogProps>) -> impl View {
     let state = cx.use_enter_exit(open, 0.3);
     If::new(
         state != EnterExitState::Exited,
-        Portal::new().children(
+        Portal::root(
             Element::new()
                 .styled(STYLE_DIALOG_OVERLAY.clone())
                 .class_names(state.as_class_name())
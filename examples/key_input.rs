@@ -0,0 +1,95 @@
+//! Example of handling keyboard input on a focused element. Click the field to focus it, type,
+//! press Enter to submit, or Escape to clear.
+
+use bevy::{a11y::Focus, input::keyboard::Key, prelude::*, ui};
+use bevy_mod_picking::{
+    picking_core::{CorePlugin, InteractionPlugin},
+    prelude::*,
+};
+use bevy_quill::prelude::*;
+use static_init::dynamic;
+
+fn main() {
+    App::new()
+        .init_resource::<Entry>()
+        .add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()))
+        .add_plugins((CorePlugin, InputPlugin, InteractionPlugin, BevyUiBackend))
+        .add_plugins(QuillPlugin::default())
+        .add_systems(Startup, (setup, setup_view_root))
+        .run();
+}
+
+#[dynamic]
+static STYLE_MAIN: StyleHandle = StyleHandle::build(|ss| {
+    ss.position(ui::PositionType::Absolute)
+        .left(10.)
+        .top(10.)
+        .display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .row_gap(4)
+});
+
+#[dynamic]
+static STYLE_FIELD: StyleHandle = StyleHandle::build(|ss| {
+    ss.background_color("#222")
+        .border(1)
+        .border_color("#555")
+        .padding(4)
+        .min_width(200)
+        .min_height(24)
+        .when_focused(|ss| ss.border_color("#88f"))
+});
+
+/// Text being edited, and the lines which have been submitted.
+#[derive(Resource, Default)]
+struct Entry {
+    text: String,
+    submitted: Vec<String>,
+}
+
+fn setup_view_root(mut commands: Commands) {
+    commands.spawn(ViewHandle::new(ui_main, ()));
+}
+
+fn ui_main(cx: Cx) -> impl View {
+    let entry = cx.use_resource::<Entry>();
+    Element::new()
+        .styled(STYLE_MAIN.clone())
+        .children((text_field, For::each(&entry.submitted, |line| line.clone())))
+}
+
+fn text_field(cx: Cx) -> impl View {
+    let text = cx.use_resource::<Entry>().text.clone();
+    Element::new()
+        .styled(STYLE_FIELD.clone())
+        .children(text)
+        .insert(On::<Pointer<Click>>::run(
+            |ev: Listener<Pointer<Click>>, mut focus: ResMut<Focus>| {
+                focus.0 = Some(ev.target);
+            },
+        ))
+        .on_key(|event, world| {
+            if !event.is_pressed() {
+                return;
+            }
+            let mut entry = world.resource_mut::<Entry>();
+            match &event.logical_key {
+                Key::Enter => {
+                    let line = std::mem::take(&mut entry.text);
+                    entry.submitted.push(line);
+                }
+                Key::Escape => entry.text.clear(),
+                Key::Backspace => {
+                    entry.text.pop();
+                }
+                Key::Space => entry.text.push(' '),
+                Key::Character(chars) if !event.modifiers.ctrl => entry.text.push_str(chars),
+                _ => return,
+            }
+            event.consume();
+        })
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+}
@@ -0,0 +1,57 @@
+//! Example of a scrolling list.
+
+use bevy::{prelude::*, ui};
+use bevy_mod_picking::{
+    backends::bevy_ui::BevyUiBackend,
+    input::InputPlugin,
+    picking_core::{CorePlugin, InteractionPlugin},
+};
+use bevy_quill::{prelude::*, ScrollDirection};
+use static_init::dynamic;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins((CorePlugin, InputPlugin, InteractionPlugin, BevyUiBackend))
+        .add_plugins(QuillPlugin::default())
+        .add_systems(Startup, (setup, setup_view_root))
+        .add_systems(Update, bevy::window::close_on_esc)
+        .run();
+}
+
+#[dynamic]
+static STYLE_LIST: StyleHandle = StyleHandle::build(|ss| {
+    ss.width(300)
+        .height(400)
+        .margin(20)
+        .border(1)
+        .border_color("#444")
+        .background_color("#222")
+});
+
+#[dynamic]
+static STYLE_ITEM: StyleHandle = StyleHandle::build(|ss| {
+    ss.padding(ui::UiRect::axes(ui::Val::Px(8.), ui::Val::Px(4.)))
+        .color("#ccc")
+        .selector(":nth-child(odd)", |ss| ss.background_color("#2a2a2a"))
+        .selector(":hover", |ss| ss.background_color("#335"))
+});
+
+fn setup_view_root(mut commands: Commands) {
+    commands.spawn(ViewHandle::new(ui_main, ()));
+}
+
+fn ui_main(_cx: Cx) -> impl View {
+    let items: Vec<usize> = (1..=100).collect();
+    ScrollView::new(For::each(&items, |item| {
+        Element::new()
+            .styled(STYLE_ITEM.clone())
+            .children(format!("Item {}", item))
+    }))
+    .direction(ScrollDirection::Vertical)
+    .styled(STYLE_LIST.clone())
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+}
@@ -0,0 +1,37 @@
+//! Example of styles loaded from an asset file. Run with `--features bevy/file_watcher`, then
+//! edit `assets/styles/panel.style.ron` while the example is running to see the changes.
+
+use bevy::{asset::AssetPlugin, prelude::*};
+use bevy_mod_picking::{
+    backends::bevy_ui::BevyUiBackend,
+    input::InputPlugin,
+    picking_core::{CorePlugin, InteractionPlugin},
+};
+use bevy_quill::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(AssetPlugin {
+            watch_for_changes_override: Some(true),
+            ..default()
+        }))
+        .add_plugins((CorePlugin, InputPlugin, InteractionPlugin, BevyUiBackend))
+        .add_plugins(QuillPlugin::default())
+        .add_systems(Startup, (setup, setup_view_root))
+        .add_systems(Update, bevy::window::close_on_esc)
+        .run();
+}
+
+fn setup_view_root(mut commands: Commands) {
+    commands.spawn(ViewHandle::new(ui_main, ()));
+}
+
+fn ui_main(_cx: Cx) -> impl View {
+    Element::new()
+        .style_asset("styles/panel.style.ron")
+        .children(("Styled from an asset", "Hover to change the border"))
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+}
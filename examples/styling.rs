@@ -40,6 +40,17 @@ static STYLE_ASIDE: StyleHandle = StyleHandle::build(|ss| {
         .width(200)
 });
 
+#[dynamic]
+static STYLE_BUTTON: StyleHandle = StyleHandle::build(|ss| {
+    ss.background_color("#333")
+        .border(1)
+        .border_color("#555")
+        .border_radius(6)
+        .padding(UiRect::axes(Val::Px(12.), Val::Px(4.)))
+        .margin(8)
+        .when_hovered(|ss| ss.background_color("#444").border_radius(10))
+});
+
 #[dynamic]
 static STYLE_VSPLITTER: StyleHandle = StyleHandle::build(|ss| {
     ss.background_color("#181818")
@@ -76,7 +87,13 @@ fn setup_view_root(mut commands: Commands) {
 fn ui_main(cx: Cx) -> impl View {
     let counter = cx.use_resource::<Counter>();
     Element::new().styled(STYLE_MAIN.clone()).children((
-        Element::new().children(()).styled(STYLE_ASIDE.clone()),
+        Element::new()
+            .children(
+                Element::new()
+                    .styled(STYLE_BUTTON.clone())
+                    .children("Rounded"),
+            )
+            .styled(STYLE_ASIDE.clone()),
         v_splitter,
         If::new(counter.count & 1 == 0, even, odd),
     ))
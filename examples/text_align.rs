@@ -0,0 +1,56 @@
+//! Example of aligned, multi-line text.
+
+use bevy::{prelude::*, text::BreakLineOn};
+use bevy_mod_picking::{
+    backends::bevy_ui::BevyUiBackend,
+    input::InputPlugin,
+    picking_core::{CorePlugin, InteractionPlugin},
+};
+use bevy_quill::prelude::*;
+use static_init::dynamic;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins((CorePlugin, InputPlugin, InteractionPlugin, BevyUiBackend))
+        .add_plugins(QuillPlugin::default())
+        .add_systems(Startup, (setup, setup_view_root))
+        .add_systems(Update, bevy::window::close_on_esc)
+        .run();
+}
+
+#[dynamic]
+static STYLE_PANEL: StyleHandle = StyleHandle::build(|ss| {
+    ss.width(320)
+        .margin(20)
+        .padding(12)
+        .flex_direction(FlexDirection::Column)
+        .row_gap(12)
+        .background_color("#222")
+        .text_align(JustifyText::Center)
+});
+
+#[dynamic]
+static STYLE_NO_WRAP: StyleHandle = StyleHandle::build(|ss| {
+    ss.text_align(JustifyText::Left)
+        .line_break(BreakLineOn::NoWrap)
+});
+
+fn setup_view_root(mut commands: Commands) {
+    commands.spawn(ViewHandle::new(ui_main, ()));
+}
+
+fn ui_main(_cx: Cx) -> impl View {
+    Element::new().styled(STYLE_PANEL.clone()).children((
+        // Inherits the centered alignment of the panel.
+        "This label is centered within the panel, and wraps onto several lines because it is \
+        wider than the panel.",
+        Element::new()
+            .styled(STYLE_NO_WRAP.clone())
+            .children("This label is left-aligned, and doesn't wrap, so it overflows the panel."),
+    ))
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+}
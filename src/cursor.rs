@@ -1,7 +1,12 @@
-//! Cursor definitions (not done yet)
+//! Cursor definitions
 
-/// 2D Cursor type - subset of standard CSS cursor types
-#[derive(Debug, Clone, Copy, PartialEq)]
+use bevy::{ecs::component::Component, window::CursorIcon};
+use serde::{Deserialize, Serialize};
+
+/// 2D Cursor type - subset of standard CSS cursor types. As a component, this is the cursor
+/// shown while the pointer is over the element or one of its descendants; it is managed by
+/// the style system, use `.cursor()` to set it.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Cursor {
     /// No cursor
     None,
@@ -48,3 +53,26 @@ pub enum Cursor {
     /// Magnifying Glass with Minus
     ZoomOut,
 }
+
+impl Cursor {
+    /// The window cursor icon for this cursor, or `None` if the cursor should be hidden.
+    /// Custom images aren't supported yet, and show the default cursor.
+    pub fn icon(self) -> Option<CursorIcon> {
+        match self {
+            Cursor::None => None,
+            Cursor::CustomImage | Cursor::Default => Some(CursorIcon::Default),
+            Cursor::Pointer => Some(CursorIcon::Pointer),
+            Cursor::Wait => Some(CursorIcon::Wait),
+            Cursor::Crosshair => Some(CursorIcon::Crosshair),
+            Cursor::Text => Some(CursorIcon::Text),
+            Cursor::VerticalText => Some(CursorIcon::VerticalText),
+            Cursor::Move => Some(CursorIcon::Move),
+            Cursor::NotAllowed => Some(CursorIcon::NotAllowed),
+            Cursor::Grab => Some(CursorIcon::Grab),
+            Cursor::ColResize => Some(CursorIcon::ColResize),
+            Cursor::RowResize => Some(CursorIcon::RowResize),
+            Cursor::ZoomIn => Some(CursorIcon::ZoomIn),
+            Cursor::ZoomOut => Some(CursorIcon::ZoomOut),
+        }
+    }
+}
@@ -0,0 +1,243 @@
+use bevy::{prelude::*, ui};
+
+/// Component that lays out the children of an element as inline content, flowing them
+/// horizontally and wrapping onto a new line when the width of the element is exceeded. Added
+/// by [`Element::inline`].
+///
+/// An inline element nested within another inline element is treated as a span: rather than
+/// flowing its children within its own box, they are flowed as part of the enclosing line
+/// run, so that a span such as a link can wrap across a line break. The span's own node is
+/// sized to the bounding box of its contents.
+///
+/// Bevy UI has no native inline layout, so children are positioned absolutely, using their
+/// measured size from the previous layout. Text is not split, so to wrap at word
+/// boundaries, each word should be a separate child.
+///
+/// [`Element::inline`]: crate::Element::inline
+#[derive(Component, Default, Clone, Copy, Debug)]
+pub struct InlineFlow;
+
+/// A leaf of inline content, along with the index of the innermost span containing it.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct InlineFragment {
+    pub(crate) size: Vec2,
+    pub(crate) span: Option<usize>,
+}
+
+/// Result of an inline layout: the position of each fragment and the bounds of each span,
+/// relative to the flow container, as well as the overall size of the content.
+#[derive(Debug, Default)]
+pub(crate) struct InlineLayout {
+    pub(crate) positions: Vec<Vec2>,
+    pub(crate) span_rects: Vec<Rect>,
+    pub(crate) size: Vec2,
+}
+
+/// Flow the fragments left to right, starting a new line whenever a fragment would exceed
+/// `max_width`. A fragment which is wider than `max_width` by itself gets a line of its own.
+/// `span_parents` holds, for each span, the index of the enclosing span.
+pub(crate) fn layout_inline(
+    fragments: &[InlineFragment],
+    span_parents: &[Option<usize>],
+    max_width: f32,
+) -> InlineLayout {
+    let mut layout = InlineLayout::default();
+    let mut span_rects: Vec<Option<Rect>> = vec![None; span_parents.len()];
+    let mut x = 0.;
+    let mut y = 0.;
+    let mut line_height: f32 = 0.;
+    for fragment in fragments {
+        if x > 0. && x + fragment.size.x > max_width {
+            x = 0.;
+            y += line_height;
+            line_height = 0.;
+        }
+        let pos = Vec2::new(x, y);
+        layout.positions.push(pos);
+        x += fragment.size.x;
+        line_height = line_height.max(fragment.size.y);
+        layout.size.x = layout.size.x.max(x);
+
+        // Grow every span which encloses this fragment.
+        let rect = Rect::from_corners(pos, pos + fragment.size);
+        let mut span = fragment.span;
+        while let Some(index) = span {
+            span_rects[index] = Some(match span_rects[index] {
+                Some(r) => r.union(rect),
+                None => rect,
+            });
+            span = span_parents[index];
+        }
+    }
+    layout.size.y = y + line_height;
+    layout.span_rects = span_rects
+        .into_iter()
+        .map(|r| r.unwrap_or_default())
+        .collect();
+    layout
+}
+
+/// Inline content gathered from the descendants of a flow container.
+#[derive(Default)]
+struct InlineContent {
+    fragments: Vec<(Entity, InlineFragment)>,
+    spans: Vec<(Entity, Option<usize>)>,
+}
+
+fn collect_inline(
+    entity: Entity,
+    span: Option<usize>,
+    content: &mut InlineContent,
+    query_children: &Query<&Children>,
+    query_inline: &Query<(), With<InlineFlow>>,
+    query_nodes: &Query<&Node>,
+) {
+    let Ok(children) = query_children.get(entity) else {
+        return;
+    };
+    for child in children.iter() {
+        if query_inline.contains(*child) {
+            content.spans.push((*child, span));
+            let index = content.spans.len() - 1;
+            collect_inline(
+                *child,
+                Some(index),
+                content,
+                query_children,
+                query_inline,
+                query_nodes,
+            );
+        } else if let Ok(node) = query_nodes.get(*child) {
+            content.fragments.push((
+                *child,
+                InlineFragment {
+                    size: node.size(),
+                    span,
+                },
+            ));
+        }
+    }
+}
+
+fn set_position(style: &mut Mut<Style>, pos: Vec2) {
+    let left = ui::Val::Px(pos.x);
+    let top = ui::Val::Px(pos.y);
+    let absolute = ui::PositionType::Absolute;
+    if style.position_type != absolute || style.left != left || style.top != top {
+        style.position_type = absolute;
+        style.left = left;
+        style.top = top;
+    }
+}
+
+/// Lay out the contents of inline flow containers.
+pub(crate) fn update_inline_flow(
+    query_flow: Query<(Entity, &Node, Option<&Parent>), With<InlineFlow>>,
+    query_inline: Query<(), With<InlineFlow>>,
+    query_children: Query<&Children>,
+    query_nodes: Query<&Node>,
+    mut query_styles: Query<&mut Style>,
+) {
+    for (entity, node, parent) in query_flow.iter() {
+        // Nested inline elements are laid out as spans by their flow container.
+        if parent.is_some_and(|p| query_inline.contains(p.get())) {
+            continue;
+        }
+
+        let mut content = InlineContent::default();
+        collect_inline(
+            entity,
+            None,
+            &mut content,
+            &query_children,
+            &query_inline,
+            &query_nodes,
+        );
+        let fragments: Vec<InlineFragment> = content.fragments.iter().map(|(_, f)| *f).collect();
+        let span_parents: Vec<Option<usize>> = content.spans.iter().map(|(_, p)| *p).collect();
+        let layout = layout_inline(&fragments, &span_parents, node.size().x);
+
+        // Children are positioned relative to their parent, which may be a span.
+        for (index, (span_entity, parent_span)) in content.spans.iter().enumerate() {
+            let rect = layout.span_rects[index];
+            let origin = parent_span.map_or(Vec2::ZERO, |p| layout.span_rects[p].min);
+            if let Ok(mut style) = query_styles.get_mut(*span_entity) {
+                set_position(&mut style, rect.min - origin);
+                let width = ui::Val::Px(rect.width());
+                let height = ui::Val::Px(rect.height());
+                if style.width != width || style.height != height {
+                    style.width = width;
+                    style.height = height;
+                }
+            }
+        }
+        for ((fragment_entity, fragment), pos) in
+            content.fragments.iter().zip(layout.positions.iter())
+        {
+            let origin = fragment
+                .span
+                .map_or(Vec2::ZERO, |s| layout.span_rects[s].min);
+            if let Ok(mut style) = query_styles.get_mut(*fragment_entity) {
+                set_position(&mut style, *pos - origin);
+            }
+        }
+
+        // Since the children are positioned absolutely, the container has to be given the
+        // height of its content explicitly.
+        if let Ok(mut style) = query_styles.get_mut(entity) {
+            let height = ui::Val::Px(layout.size.y);
+            if style.min_height != height {
+                style.min_height = height;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(width: f32) -> InlineFragment {
+        InlineFragment {
+            size: Vec2::new(width, 10.),
+            span: None,
+        }
+    }
+
+    #[test]
+    fn test_flow_horizontal() {
+        let layout = layout_inline(&[word(20.), word(30.), word(10.)], &[], 100.);
+        assert_eq!(
+            layout.positions,
+            vec![Vec2::new(0., 0.), Vec2::new(20., 0.), Vec2::new(50., 0.)]
+        );
+        assert_eq!(layout.size, Vec2::new(60., 10.));
+    }
+
+    #[test]
+    fn test_flow_wrap() {
+        let layout = layout_inline(&[word(40.), word(40.), word(40.), word(120.)], &[], 100.);
+        assert_eq!(layout.positions[1], Vec2::new(40., 0.));
+        // Third word doesn't fit on the first line.
+        assert_eq!(layout.positions[2], Vec2::new(0., 10.));
+        // A fragment wider than the container gets a line of its own.
+        assert_eq!(layout.positions[3], Vec2::new(0., 20.));
+        assert_eq!(layout.size, Vec2::new(120., 30.));
+    }
+
+    #[test]
+    fn test_span_wraps_across_lines() {
+        let link = |width| InlineFragment {
+            size: Vec2::new(width, 10.),
+            span: Some(0),
+        };
+        let layout = layout_inline(&[word(60.), link(30.), link(30.)], &[None], 100.);
+        assert_eq!(layout.positions[1], Vec2::new(60., 0.));
+        assert_eq!(layout.positions[2], Vec2::new(0., 10.));
+        // The link's bounds cover both lines.
+        assert_eq!(
+            layout.span_rects[0],
+            Rect::from_corners(Vec2::new(0., 0.), Vec2::new(90., 20.))
+        );
+    }
+}
@@ -4,14 +4,18 @@
 
 #![warn(missing_docs)]
 mod cursor;
+mod inline_flow;
 mod node_span;
 mod plugin;
 mod scrolling;
+mod settings;
 mod style;
 mod view;
 
 pub use cursor::Cursor;
+pub use inline_flow::InlineFlow;
 pub use node_span::NodeSpan;
+pub use node_span::NodeSpanIter;
 #[doc(inline)]
 pub use prelude::*;
 pub use scrolling::*;
@@ -19,6 +23,7 @@ pub use scrolling::*;
 /// Common imports
 pub mod prelude {
     pub use crate::plugin::QuillPlugin;
+    pub use crate::settings::QuillSettings;
     pub use crate::style::*;
     pub use crate::view::*;
 }
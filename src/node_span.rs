@@ -16,6 +16,40 @@ pub enum NodeSpan {
 }
 
 impl NodeSpan {
+    /// Construct a fragment from a sequence of spans. Any spans in the sequence which are
+    /// themselves fragments are spliced in, flattening one level of nesting. `Empty` spans
+    /// are kept as placeholders, so that the position of each item is preserved.
+    pub fn fragment(items: impl IntoIterator<Item = NodeSpan>) -> Self {
+        let mut spans: Vec<NodeSpan> = Vec::new();
+        for item in items {
+            match item {
+                Self::Fragment(nodes) => spans.extend(nodes.into_vec()),
+                _ => spans.push(item),
+            }
+        }
+        Self::Fragment(spans.into_boxed_slice())
+    }
+
+    /// Returns an equivalent span with all nesting and `Empty` placeholders removed. The
+    /// result is `Empty` if there are no entities, a `Node` if there is exactly one, and
+    /// otherwise a flat `Fragment` of `Node`s.
+    pub fn normalize(&self) -> Self {
+        let mut entities = self.iter();
+        match (entities.next(), entities.next()) {
+            (None, _) => Self::Empty,
+            (Some(entity), None) => Self::Node(entity),
+            _ => Self::Fragment(self.iter().map(Self::Node).collect()),
+        }
+    }
+
+    /// Returns true if both spans contain the same entities in the same order, regardless of
+    /// how they are nested. For example, `Node(e)` and `Fragment([Node(e)])` are considered
+    /// the same, since they attach the same entity to the parent. Unlike `==`, this is the
+    /// comparison to use when deciding whether the output of a view needs to be re-attached.
+    pub fn same_entities(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+
     /// Returns the number of actual entities contained in this span.
     pub fn count(&self) -> usize {
         match self {
@@ -27,10 +61,16 @@ impl NodeSpan {
 
     /// Flattens the list of entities into a vector.
     pub fn flatten(&self, out: &mut Vec<Entity>) {
-        match self {
-            Self::Empty => {}
-            Self::Node(entity) => out.push(*entity),
-            Self::Fragment(nodes) => nodes.iter().for_each(|node| node.flatten(out)),
+        out.extend(self.iter());
+    }
+
+    /// Returns an iterator over the entities contained in this span, in order. Nested
+    /// fragments are walked in place; the iterator only allocates when it has to descend into
+    /// a fragment which isn't the last item of its parent.
+    pub fn iter(&self) -> NodeSpanIter<'_> {
+        NodeSpanIter {
+            current: std::slice::from_ref(self).iter(),
+            stack: Vec::new(),
         }
     }
 
@@ -80,3 +120,163 @@ impl Default for NodeSpan {
         Self::Empty
     }
 }
+
+impl<'a> IntoIterator for &'a NodeSpan {
+    type Item = Entity;
+    type IntoIter = NodeSpanIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the entities of a [`NodeSpan`], returned by [`NodeSpan::iter`].
+pub struct NodeSpanIter<'a> {
+    /// Remaining items of the innermost fragment being walked.
+    current: std::slice::Iter<'a, NodeSpan>,
+
+    /// Remaining items of the enclosing fragments.
+    stack: Vec<std::slice::Iter<'a, NodeSpan>>,
+}
+
+impl Iterator for NodeSpanIter<'_> {
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Entity> {
+        loop {
+            match self.current.next() {
+                Some(NodeSpan::Empty) => {}
+                Some(NodeSpan::Node(entity)) => return Some(*entity),
+                Some(NodeSpan::Fragment(nodes)) => {
+                    let rest = std::mem::replace(&mut self.current, nodes.iter());
+                    // When the fragment is the last item, there's nothing to come back to.
+                    if !rest.as_slice().is_empty() {
+                        self.stack.push(rest);
+                    }
+                }
+                None => self.current = self.stack.pop()?,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entities(n: u32) -> Vec<Entity> {
+        (0..n).map(Entity::from_raw).collect()
+    }
+
+    fn flat(span: &NodeSpan) -> Vec<Entity> {
+        let mut out = Vec::new();
+        span.flatten(&mut out);
+        out
+    }
+
+    #[test]
+    fn test_fragment_flattens_one_level() {
+        let e = entities(4);
+        let nested = NodeSpan::Fragment(Box::new([
+            NodeSpan::Node(e[0]),
+            NodeSpan::Fragment(Box::new([NodeSpan::Node(e[1]), NodeSpan::Empty])),
+            NodeSpan::Node(e[2]),
+        ]));
+        let span = NodeSpan::fragment([NodeSpan::Node(e[3]), nested.clone(), NodeSpan::Empty]);
+        // Only one level is flattened, and placeholders are kept.
+        assert_eq!(
+            span,
+            NodeSpan::Fragment(Box::new([
+                NodeSpan::Node(e[3]),
+                NodeSpan::Node(e[0]),
+                NodeSpan::Fragment(Box::new([NodeSpan::Node(e[1]), NodeSpan::Empty])),
+                NodeSpan::Node(e[2]),
+                NodeSpan::Empty,
+            ]))
+        );
+        assert_eq!(flat(&span), vec![e[3], e[0], e[1], e[2]]);
+    }
+
+    #[test]
+    fn test_iter() {
+        let e = entities(5);
+        let span = NodeSpan::Fragment(Box::new([
+            NodeSpan::Empty,
+            NodeSpan::Fragment(Box::new([
+                NodeSpan::Node(e[0]),
+                NodeSpan::Fragment(Box::new([NodeSpan::Node(e[1]), NodeSpan::Empty])),
+                NodeSpan::Node(e[2]),
+            ])),
+            NodeSpan::Fragment(Box::new([])),
+            NodeSpan::Node(e[3]),
+            NodeSpan::Fragment(Box::new([NodeSpan::Fragment(Box::new([NodeSpan::Node(
+                e[4],
+            )]))])),
+        ]));
+        assert_eq!(span.iter().collect::<Vec<_>>(), e);
+        assert_eq!(span.iter().count(), span.count());
+
+        let mut visited = Vec::new();
+        for entity in &span {
+            visited.push(entity);
+        }
+        assert_eq!(visited, e);
+
+        assert_eq!(NodeSpan::Empty.iter().next(), None);
+        assert_eq!(NodeSpan::Node(e[0]).iter().collect::<Vec<_>>(), vec![e[0]]);
+    }
+
+    #[test]
+    fn test_normalize() {
+        let e = entities(3);
+        let nested = NodeSpan::Fragment(Box::new([
+            NodeSpan::Empty,
+            NodeSpan::Fragment(Box::new([
+                NodeSpan::Node(e[0]),
+                NodeSpan::Fragment(Box::new([NodeSpan::Node(e[1])])),
+            ])),
+            NodeSpan::Node(e[2]),
+        ]));
+        let normalized = nested.normalize();
+        assert_ne!(nested, normalized);
+        assert_eq!(
+            normalized,
+            NodeSpan::Fragment(Box::new([
+                NodeSpan::Node(e[0]),
+                NodeSpan::Node(e[1]),
+                NodeSpan::Node(e[2]),
+            ]))
+        );
+        assert_eq!(flat(&nested), flat(&normalized));
+    }
+
+    #[test]
+    fn test_normalize_collapses() {
+        let e = entities(1);
+        let single = NodeSpan::Fragment(Box::new([
+            NodeSpan::Empty,
+            NodeSpan::Fragment(Box::new([NodeSpan::Node(e[0])])),
+        ]));
+        assert_eq!(single.normalize(), NodeSpan::Node(e[0]));
+        let empty = NodeSpan::Fragment(Box::new([NodeSpan::Empty, NodeSpan::Empty]));
+        assert_eq!(empty.normalize(), NodeSpan::Empty);
+    }
+
+    #[test]
+    fn test_same_entities() {
+        let e = entities(2);
+        let node = NodeSpan::Node(e[0]);
+        let wrapped = NodeSpan::Fragment(Box::new([NodeSpan::Node(e[0])]));
+        assert_ne!(node, wrapped);
+        assert!(node.same_entities(&wrapped));
+        assert!(NodeSpan::Empty.same_entities(&NodeSpan::Fragment(Box::new([]))));
+
+        // Genuinely different outputs are still detected.
+        assert!(!node.same_entities(&NodeSpan::Node(e[1])));
+        assert!(!node.same_entities(&NodeSpan::Empty));
+        let pair = NodeSpan::Fragment(Box::new([NodeSpan::Node(e[0]), NodeSpan::Node(e[1])]));
+        let reversed = NodeSpan::Fragment(Box::new([NodeSpan::Node(e[1]), NodeSpan::Node(e[0])]));
+        assert!(!pair.same_entities(&reversed));
+        assert!(!wrapped.same_entities(&pair));
+    }
+}
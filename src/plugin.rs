@@ -1,14 +1,37 @@
 use bevy::{prelude::*, render::texture::ImageSampler, utils::HashSet};
-use bevy_mod_picking::prelude::EventListenerPlugin;
+use bevy_mod_picking::{backend::prelude::PickSet, prelude::EventListenerPlugin};
 
 use crate::{
-    animate_bg_colors, animate_border_colors, animate_layout, animate_transforms,
+    animate_bg_colors, animate_border_colors, animate_layout, animate_opacity, animate_spin,
+    animate_transforms,
+    autofocus::apply_autofocus,
+    coalesced::{flush_coalesced_writes, CoalescedWrites},
+    content_visibility::update_content_visibility,
+    countdown::update_countdowns,
+    drag::update_drag_states,
+    focus_ring::update_focus_visible,
     handle_scroll_events,
+    history::update_histories,
+    hit_slop::hit_slop_backend,
+    hit_test::filter_visible_hits,
+    hover::{update_hover_callbacks, HoveredCallbacks},
+    image_fit::update_image_fit,
+    inline_flow::update_inline_flow,
+    key::dispatch_key_events,
+    lerp::update_lerped_values,
+    order::update_visual_order,
     presenter_state::{PresenterGraphChanged, PresenterStateChanged},
+    pressed::update_pressed_entities,
+    rate_limit::update_rate_limits,
+    scrollbar::update_scrollbar_styles,
+    style_asset::apply_style_assets,
     tracked_resources::TrackedResources,
     tracking::TrackedComponents,
-    update::{update_styles, PreviousFocus},
-    update_scroll_positions, BuildContext, ScrollWheel, ViewHandle,
+    update::{update_cursor_icon, update_styles, PreviousFocus, PreviousPressed},
+    update_scroll_positions, update_sticky_positions,
+    writing_mode::update_vertical_footprints,
+    BuildContext, DragStates, FocusRing, FocusVisible, PressedEntities, QuillSettings, ScrollWheel,
+    StyleAsset, StyleAssetLoader, ViewHandle,
 };
 
 /// Plugin which initializes the Quill library.
@@ -17,26 +40,86 @@ pub struct QuillPlugin {
     /// What image sampler will be used for any [`Image`] assets loaded
     /// through the [`StyleBuilder::background_image`]
     pub default_sampler: ImageSampler,
+
+    /// If set, an outline which is drawn around the focused element whenever focus is
+    /// visible, unless the element's styles define an outline of their own.
+    pub focus_ring: Option<FocusRing>,
+
+    /// Initial value of [`QuillSettings::strict_hooks`], used if the app hasn't inserted
+    /// its own [`QuillSettings`].
+    pub strict_hooks: bool,
 }
 
 impl Plugin for QuillPlugin {
     fn build(&self, app: &mut App) {
+        if !app.world.contains_resource::<QuillSettings>() {
+            app.insert_resource(QuillSettings {
+                strict_hooks: self.strict_hooks,
+                ..default()
+            });
+        }
         app.init_resource::<PreviousFocus>()
+            .init_resource::<PreviousPressed>()
+            .init_resource::<PressedEntities>()
             .insert_resource(QuillPlugin {
-                default_sampler: self.default_sampler.clone()
+                default_sampler: self.default_sampler.clone(),
+                focus_ring: self.focus_ring.clone(),
+                strict_hooks: self.strict_hooks,
             })
+            .init_resource::<FocusVisible>()
+            .init_resource::<DragStates>()
+            .init_resource::<CoalescedWrites>()
+            .init_resource::<HoveredCallbacks>()
+            .init_asset::<StyleAsset>()
+            .init_asset_loader::<StyleAssetLoader>()
             .add_systems(
                 Update,
                 (
-                    (render_views, update_styles).chain(),
+                    (
+                        update_pressed_entities,
+                        update_focus_visible,
+                        update_countdowns,
+                        update_histories,
+                        update_lerped_values,
+                        update_rate_limits,
+                        update_drag_states,
+                        update_hover_callbacks,
+                        dispatch_key_events,
+                        render_views,
+                        apply_autofocus,
+                        flush_coalesced_writes,
+                        apply_style_assets,
+                        update_styles,
+                        update_cursor_icon,
+                        update_visual_order,
+                        update_vertical_footprints,
+                        update_scrollbar_styles,
+                        animate_spin,
+                    )
+                        .chain(),
                     animate_transforms,
                     animate_bg_colors,
                     animate_border_colors,
                     animate_layout,
-                    update_scroll_positions,
+                    animate_opacity,
+                    update_image_fit,
+                    update_inline_flow,
+                    (
+                        update_scroll_positions,
+                        update_sticky_positions,
+                        update_content_visibility,
+                    )
+                        .chain(),
                     handle_scroll_events,
                 ),
             )
+            .add_systems(PreUpdate, hit_slop_backend.in_set(PickSet::Backend))
+            .add_systems(
+                PreUpdate,
+                filter_visible_hits
+                    .after(PickSet::Backend)
+                    .before(PickSet::Focus),
+            )
             .add_plugins(EventListenerPlugin::<ScrollWheel>::default())
             .add_event::<ScrollWheel>();
     }
@@ -49,7 +132,7 @@ const MAX_DIVERGENCE_CT: usize = 30;
 // Phase 2: Use Option::take() to remove the ViewRoot::handle from the World. Use the taken handle
 //          and call AnyViewState::build() on it. Since the handle isn't part of the World we can
 //          freely pass a mutable reference to the World.
-fn render_views(world: &mut World) {
+pub(crate) fn render_views(world: &mut World) {
     let mut divergence_ct: usize = 0;
     let mut prev_change_ct: usize = 0;
     let this_run = world.change_tick();
@@ -161,3 +244,11 @@ fn render_views(world: &mut World) {
         }
     }
 }
+
+/// Run the render loop for a single frame, then clear change trackers so that the next frame
+/// only sees new changes. Used by tests which drive presenters without a full app.
+#[cfg(test)]
+pub(crate) fn render_frame(world: &mut World) {
+    render_views(world);
+    world.clear_trackers();
+}
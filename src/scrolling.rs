@@ -12,6 +12,30 @@ pub struct ScrollWheel {
     pub delta: Vec2,
 }
 
+/// Which axes an element can be scrolled along.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScrollDirection {
+    /// Scroll along the X-axis only.
+    Horizontal,
+    /// Scroll along the Y-axis only.
+    Vertical,
+    /// Scroll along both axes.
+    #[default]
+    Both,
+}
+
+impl ScrollDirection {
+    /// True if scrolling along the X-axis is enabled.
+    pub fn horizontal(self) -> bool {
+        self != ScrollDirection::Vertical
+    }
+
+    /// True if scrolling along the Y-axis is enabled.
+    pub fn vertical(self) -> bool {
+        self != ScrollDirection::Horizontal
+    }
+}
+
 /// Component that enables scrolling on an element
 #[derive(Component, Default)]
 pub struct ScrollArea {
@@ -27,6 +51,10 @@ pub struct ScrollArea {
     /// Size of visible scrolling area
     pub visible_size: Vec2,
 
+    /// Axes along which the content can be scrolled. The scroll position along a disabled
+    /// axis is always zero.
+    pub direction: ScrollDirection,
+
     /// Entity id of the X scrollbar
     pub id_scrollbar_x: Option<Entity>,
 
@@ -37,20 +65,32 @@ pub struct ScrollArea {
 impl ScrollArea {
     /// Offset the current scroll position by the given values.
     pub fn scroll_by(&mut self, dx: f32, dy: f32) {
-        // Apply max constraint first, then min - don't use clamp() here.
-        self.scroll_left = (self.scroll_left + dx)
-            .min(self.content_size.x - self.visible_size.x)
-            .max(0.);
-        self.scroll_top = (self.scroll_top + dy)
-            .min(self.content_size.y - self.visible_size.y)
-            .max(0.);
+        self.scroll_to(self.scroll_left + dx, self.scroll_top + dy);
     }
 
     /// Scroll to the given scroll position (values clamped).
     pub fn scroll_to(&mut self, x: f32, y: f32) {
+        let max = self.max_scroll();
         // Apply max constraint first, then min - don't use clamp() here.
-        self.scroll_left = x.min(self.content_size.x - self.visible_size.x).max(0.);
-        self.scroll_top = y.min(self.content_size.y - self.visible_size.y).max(0.);
+        self.scroll_left = x.min(max.x).max(0.);
+        self.scroll_top = y.min(max.y).max(0.);
+    }
+
+    /// The largest scroll position along each axis, which is zero for disabled axes and for
+    /// content which fits within the visible area.
+    pub fn max_scroll(&self) -> Vec2 {
+        Vec2::new(
+            if self.direction.horizontal() {
+                self.content_size.x - self.visible_size.x
+            } else {
+                0.
+            },
+            if self.direction.vertical() {
+                self.content_size.y - self.visible_size.y
+            } else {
+                0.
+            },
+        )
     }
 }
 
@@ -75,6 +115,86 @@ pub struct ScrollBar {
 #[derive(Component)]
 pub struct ScrollBarThumb;
 
+/// Component which holds the state of an element with sticky positioning. This is managed
+/// by the style system; use `.position(Sticky)` to enable it.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct StickyPosition {
+    /// Distance from the top of the scroll container at which the element is pinned.
+    pub top: ui::Val,
+
+    /// Current offset applied to the element to keep it pinned.
+    pub offset: f32,
+}
+
+impl StickyPosition {
+    pub(crate) fn new(top: ui::Val) -> Self {
+        Self { top, offset: 0. }
+    }
+}
+
+/// Compute the offset needed to keep a sticky element pinned, given (in window coordinates)
+/// the top of the element's un-offset layout position, its height, the pinning position, and
+/// the bottom of its parent. The element is never pushed above its layout position, nor
+/// past the bottom of its parent, so that once the parent scrolls out of view, the element
+/// is released and scrolls with it.
+pub(crate) fn sticky_offset(top: f32, height: f32, pin_at: f32, parent_bottom: f32) -> f32 {
+    (pin_at - top).min(parent_bottom - top - height).max(0.)
+}
+
+/// Adjust the offset of sticky elements to keep them pinned within their scroll container.
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_sticky_positions(
+    mut query_sticky: Query<(
+        Entity,
+        &mut StickyPosition,
+        &mut Style,
+        &Node,
+        &GlobalTransform,
+    )>,
+    query_nodes: Query<(&Node, &GlobalTransform, Option<&ScrollArea>)>,
+    query_parents: Query<&Parent>,
+) {
+    for (entity, mut sticky, mut style, node, gt) in query_sticky.iter_mut() {
+        let Ok(parent) = query_parents.get(entity) else {
+            continue;
+        };
+        let Ok((parent_node, parent_gt, _)) = query_nodes.get(parent.get()) else {
+            continue;
+        };
+
+        // Find the nearest enclosing scroll area.
+        let Some(area_rect) = query_parents
+            .iter_ancestors(entity)
+            .filter_map(|ancestor| query_nodes.get(ancestor).ok())
+            .find(|(_, _, area)| area.is_some())
+            .map(|(area_node, area_gt, _)| area_node.logical_rect(area_gt))
+        else {
+            continue;
+        };
+
+        let pin_at = area_rect.min.y
+            + match sticky.top {
+                ui::Val::Px(px) => px,
+                ui::Val::Percent(pct) => area_rect.height() * pct * 0.01,
+                _ => 0.,
+            };
+
+        // The current layout includes the previously applied offset, so remove it to get the
+        // element's natural position.
+        let rect = node.logical_rect(gt);
+        let offset = sticky_offset(
+            rect.min.y - sticky.offset,
+            rect.height(),
+            pin_at,
+            parent_node.logical_rect(parent_gt).max.y,
+        );
+        if offset != sticky.offset {
+            sticky.offset = offset;
+            style.top = ui::Val::Px(offset);
+        }
+    }
+}
+
 #[allow(clippy::type_complexity)]
 pub(crate) fn update_scroll_positions(
     mut query: Query<(&Node, &mut ScrollArea, &GlobalTransform, &Children)>,
@@ -101,14 +221,8 @@ pub(crate) fn update_scroll_positions(
             scrolling.content_size.x = content_size.width();
             scrolling.content_size.y = content_size.height();
 
-            scrolling.scroll_left = scrolling
-                .scroll_left
-                .min(scrolling.content_size.x - scrolling.visible_size.x)
-                .max(0.);
-            scrolling.scroll_top = scrolling
-                .scroll_top
-                .min(scrolling.content_size.y - scrolling.visible_size.y)
-                .max(0.);
+            let (x, y) = (scrolling.scroll_left, scrolling.scroll_top);
+            scrolling.scroll_to(x, y);
 
             style.left = ui::Val::Px(-scrolling.scroll_left);
             style.top = ui::Val::Px(-scrolling.scroll_top);
@@ -187,3 +301,74 @@ pub(crate) fn handle_scroll_events(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scroll_direction() {
+        let mut area = ScrollArea {
+            content_size: Vec2::new(300., 500.),
+            visible_size: Vec2::new(100., 100.),
+            ..default()
+        };
+        area.scroll_by(50., 450.);
+        assert_eq!((area.scroll_left, area.scroll_top), (50., 400.));
+        area.scroll_by(-100., 0.);
+        assert_eq!((area.scroll_left, area.scroll_top), (0., 400.));
+
+        area.direction = ScrollDirection::Vertical;
+        area.scroll_to(50., 50.);
+        assert_eq!((area.scroll_left, area.scroll_top), (0., 50.));
+
+        area.direction = ScrollDirection::Horizontal;
+        area.scroll_by(20., 20.);
+        assert_eq!((area.scroll_left, area.scroll_top), (20., 0.));
+
+        // Content which fits within the visible area can't be scrolled.
+        area.content_size = Vec2::new(80., 80.);
+        area.direction = ScrollDirection::Both;
+        area.scroll_to(10., 10.);
+        assert_eq!((area.scroll_left, area.scroll_top), (0., 0.));
+    }
+
+    #[test]
+    fn test_sticky_offset() {
+        // A section spanning 100..300 with a 20px header, in a container whose top is at 0.
+        // Scrolling moves everything up by `scroll`.
+        let offset = |scroll: f32| sticky_offset(100. - scroll, 20., 0., 300. - scroll);
+
+        // Not yet scrolled to the header.
+        assert_eq!(offset(0.), 0.);
+        assert_eq!(offset(50.), 0.);
+        assert_eq!(offset(100.), 0.);
+
+        // Header is pinned to the top of the container.
+        assert_eq!(offset(150.), 50.);
+        assert_eq!(100. - 150. + offset(150.), 0.);
+        assert_eq!(offset(280.), 180.);
+
+        // Past the bottom of the section, the header is released and scrolls away.
+        assert_eq!(offset(290.), 180.);
+        assert_eq!(100. - 290. + offset(290.), -10.);
+    }
+
+    #[test]
+    fn test_sticky_siblings() {
+        // Two consecutive sections with their own headers: the second header takes over as
+        // the first section scrolls out of view.
+        let first = |scroll: f32| 0. - scroll + sticky_offset(0. - scroll, 20., 0., 200. - scroll);
+        let second =
+            |scroll: f32| 200. - scroll + sticky_offset(200. - scroll, 20., 0., 400. - scroll);
+
+        assert_eq!(first(100.), 0.);
+        assert_eq!(second(100.), 100.);
+
+        // First header is pushed up by the end of its section as the second approaches.
+        assert_eq!(first(190.), -10.);
+        assert_eq!(second(190.), 10.);
+
+        assert_eq!(second(250.), 0.);
+    }
+}
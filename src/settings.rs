@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+
+/// Resource which controls rebuild and change-detection behavior. It is inserted by
+/// [`QuillPlugin`], unless the app has already inserted it, and can be modified at runtime;
+/// changes take effect on the next frame.
+///
+/// [`QuillPlugin`]: crate::QuillPlugin
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct QuillSettings {
+    /// If true, [`Cx::set_resource`] doesn't mark the resource as changed when the new value
+    /// is equal to the current one, so that presenters which depend on the resource aren't
+    /// rebuilt needlessly.
+    ///
+    /// [`Cx::set_resource`]: crate::Cx::set_resource
+    pub skip_unchanged_resource_writes: bool,
+
+    /// If true, validate that presenters call hooks in the same order on every render, and
+    /// panic with a description of the mismatch if they don't. Only enabled in debug builds.
+    pub strict_hooks: bool,
+
+    /// If true, writes made with [`Cx::write_resource_coalesced`] are combined so that each
+    /// resource changes at most once per frame. If false, each write is applied when the
+    /// presenter finishes building, the same as [`Cx::update_resource`].
+    ///
+    /// [`Cx::write_resource_coalesced`]: crate::Cx::write_resource_coalesced
+    /// [`Cx::update_resource`]: crate::Cx::update_resource
+    pub coalesce_resource_writes: bool,
+
+    /// If true, styles are only recomputed for elements whose styles, classes or selector
+    /// state have changed. If false, every element is restyled every frame, which is slow,
+    /// but can be useful for diagnosing styles which fail to update.
+    pub style_cache_enabled: bool,
+}
+
+impl Default for QuillSettings {
+    fn default() -> Self {
+        Self {
+            skip_unchanged_resource_writes: false,
+            strict_hooks: false,
+            coalesce_resource_writes: true,
+            style_cache_enabled: true,
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+/// Map of named attributes which are attached to a given UiNode. Style selectors can match
+/// on the presence of an attribute (`[disabled]`) or on its value (`[variant=primary]`). Unlike
+/// class names, attributes are keyed, so an element can have only one value for each.
+#[derive(Component, Default, Debug, Clone, PartialEq)]
+pub struct ElementAttributes(pub HashMap<String, String>);
+
+impl ElementAttributes {
+    /// Return the value of the named attribute, if present.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+
+    /// True if the element has the named attribute, regardless of its value.
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains_key(name)
+    }
+
+    /// Set the value of an attribute, replacing any previous value.
+    pub fn set(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.0.insert(name.into(), value.into());
+    }
+
+    /// Remove an attribute from this element.
+    pub fn remove(&mut self, name: &str) {
+        self.0.remove(name);
+    }
+}
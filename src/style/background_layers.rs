@@ -0,0 +1,198 @@
+use bevy::{asset::AssetPath, prelude::*, ui};
+use bevy_mod_picking::prelude::Pickable;
+
+/// One layer of a stacked background. See [`StyleBuilder::background_layers`].
+///
+/// [`StyleBuilder::background_layers`]: crate::StyleBuilder::background_layers
+#[derive(Debug, Clone, PartialEq)]
+pub enum BackgroundLayer {
+    /// A solid color.
+    Color(Color),
+    /// An image, loaded from the given asset path.
+    Image(AssetPath<'static>),
+    /// An image which has already been loaded.
+    ImageHandle(Handle<Image>),
+}
+
+/// Component added to an element which has background layers. Each layer is rendered by a
+/// backing node, which is an absolutely-positioned child that fills the element's padding
+/// box. The backing nodes are kept in front of the element's other children, in the order
+/// the layers were declared, so later layers are painted over earlier ones, and all of them
+/// are painted under the element's content.
+#[derive(Component, Debug, Default, Clone)]
+pub struct BackgroundLayers {
+    layers: Vec<BackgroundLayer>,
+    nodes: Vec<Entity>,
+}
+
+impl BackgroundLayers {
+    /// The backing nodes, in the order the layers were declared.
+    pub fn nodes(&self) -> &[Entity] {
+        &self.nodes
+    }
+}
+
+/// Marker for the backing nodes of background layers. These are not styled by the style
+/// system, and don't respond to pointer events, so that events reach the element.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub(crate) struct BackgroundLayerNode;
+
+fn spawn_layer_node(world: &mut World) -> Entity {
+    world
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: ui::PositionType::Absolute,
+                    left: ui::Val::Px(0.),
+                    top: ui::Val::Px(0.),
+                    width: ui::Val::Percent(100.),
+                    height: ui::Val::Percent(100.),
+                    ..default()
+                },
+                ..default()
+            },
+            Pickable::IGNORE,
+            BackgroundLayerNode,
+            Name::new("background-layer"),
+        ))
+        .id()
+}
+
+fn apply_layer(mut node: EntityWorldMut, layer: &BackgroundLayer) {
+    match layer {
+        BackgroundLayer::Color(color) => {
+            node.remove::<UiImage>();
+            node.insert(BackgroundColor(*color));
+        }
+        BackgroundLayer::ImageHandle(handle) => {
+            node.insert((
+                UiImage {
+                    texture: handle.clone(),
+                    ..default()
+                },
+                BackgroundColor(Color::WHITE),
+            ));
+        }
+        // Paths are resolved to handles by the style system; until then, draw nothing.
+        BackgroundLayer::Image(_) => {
+            node.remove::<UiImage>();
+            node.insert(BackgroundColor(Color::NONE));
+        }
+    }
+}
+
+/// Reconcile the backing nodes of `entity` with the given list of layers. Existing nodes are
+/// reused in order; surplus nodes are despawned, and new nodes are inserted after the
+/// existing ones.
+pub(crate) fn sync_background_layers(
+    world: &mut World,
+    entity: Entity,
+    layers: &[BackgroundLayer],
+) {
+    let unchanged = match world.get::<BackgroundLayers>(entity) {
+        Some(current) => current.layers == layers,
+        None => layers.is_empty(),
+    };
+    if unchanged {
+        return;
+    }
+
+    let mut current = world
+        .entity_mut(entity)
+        .take::<BackgroundLayers>()
+        .unwrap_or_default();
+    if current.nodes.len() > layers.len() {
+        for node in current.nodes.split_off(layers.len()) {
+            world.entity_mut(node).despawn_recursive();
+        }
+    }
+
+    let existing = current.nodes.len();
+    for (index, layer) in layers.iter().enumerate() {
+        if index >= existing {
+            current.nodes.push(spawn_layer_node(world));
+        }
+        apply_layer(world.entity_mut(current.nodes[index]), layer);
+    }
+
+    let mut entt = world.entity_mut(entity);
+    if current.nodes.len() > existing {
+        entt.insert_children(existing, &current.nodes[existing..]);
+    }
+    if !layers.is_empty() {
+        current.layers = layers.to_vec();
+        entt.insert(current);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(world: &World, node: Entity) -> Color {
+        world.get::<BackgroundColor>(node).unwrap().0
+    }
+
+    #[test]
+    fn test_background_layers() {
+        let mut world = World::new();
+        let content = world.spawn(NodeBundle::default()).id();
+        let entity = world.spawn(NodeBundle::default()).add_child(content).id();
+
+        let layers = [
+            BackgroundLayer::Color(Color::RED),
+            BackgroundLayer::Color(Color::BLUE),
+        ];
+        sync_background_layers(&mut world, entity, &layers);
+        let nodes = world
+            .get::<BackgroundLayers>(entity)
+            .unwrap()
+            .nodes()
+            .to_vec();
+        assert_eq!(nodes.len(), 2);
+        // Backing nodes are stacked in declared order, under the content.
+        assert_eq!(
+            world.get::<Children>(entity).unwrap().to_vec(),
+            vec![nodes[0], nodes[1], content]
+        );
+        assert_eq!(fill(&world, nodes[0]), Color::RED);
+        assert_eq!(fill(&world, nodes[1]), Color::BLUE);
+        let pickable = world.get::<Pickable>(nodes[0]).unwrap();
+        assert!(!pickable.is_hoverable && !pickable.should_block_lower);
+
+        // Adding a layer reuses the existing nodes.
+        let layers = [
+            BackgroundLayer::Color(Color::GREEN),
+            BackgroundLayer::Color(Color::BLUE),
+            BackgroundLayer::Color(Color::RED),
+        ];
+        sync_background_layers(&mut world, entity, &layers);
+        let grown = world
+            .get::<BackgroundLayers>(entity)
+            .unwrap()
+            .nodes()
+            .to_vec();
+        assert_eq!(grown[..2], nodes[..]);
+        assert_eq!(fill(&world, grown[0]), Color::GREEN);
+        assert_eq!(
+            world.get::<Children>(entity).unwrap().to_vec(),
+            vec![grown[0], grown[1], grown[2], content]
+        );
+
+        // Removing layers despawns the surplus nodes.
+        sync_background_layers(&mut world, entity, &layers[..1]);
+        assert!(world.get_entity(grown[1]).is_none());
+        assert!(world.get_entity(grown[2]).is_none());
+        assert_eq!(
+            world.get::<Children>(entity).unwrap().to_vec(),
+            vec![grown[0], content]
+        );
+
+        sync_background_layers(&mut world, entity, &[]);
+        assert!(world.get::<BackgroundLayers>(entity).is_none());
+        assert_eq!(
+            world.get::<Children>(entity).unwrap().to_vec(),
+            vec![content]
+        );
+    }
+}
@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+
+/// Component which holds the computed corner radii of a UI node, set by the `border_radius`
+/// style properties.
+///
+/// Bevy 0.13's UI renderer always draws square corners, so this component has no visual
+/// effect on its own; it exists so that custom materials or render passes can read the
+/// resolved radii. It has the same shape as the `BorderRadius` component which Bevy adds in
+/// 0.14, and will be replaced by it when quill moves to that version.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct BorderRadius {
+    pub top_left: Val,
+    pub top_right: Val,
+    pub bottom_left: Val,
+    pub bottom_right: Val,
+}
+
+impl BorderRadius {
+    /// Square corners.
+    pub const ZERO: Self = Self::all(Val::Px(0.));
+
+    /// Use the same radius for all four corners.
+    pub const fn all(radius: Val) -> Self {
+        Self {
+            top_left: radius,
+            top_right: radius,
+            bottom_left: radius,
+            bottom_right: radius,
+        }
+    }
+}
+
+impl Default for BorderRadius {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+
+/// A single drop shadow, as set by the `box_shadow` style property.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShadowStyle {
+    /// Shadow color, including alpha.
+    pub color: Color,
+    /// Horizontal offset of the shadow from the node.
+    pub x_offset: Val,
+    /// Vertical offset of the shadow from the node.
+    pub y_offset: Val,
+    /// How far the shadow's edge is blurred.
+    pub blur_radius: Val,
+    /// How far the shadow extends beyond the node's bounds (or, if negative, inside them).
+    pub spread_radius: Val,
+}
+
+/// Component which holds the computed drop shadows of a UI node, in the order they were
+/// declared; the first shadow is drawn on top.
+///
+/// Bevy 0.13's UI renderer has no shadow support, so this component has no visual effect on
+/// its own; it exists so that custom materials or render passes can read the resolved
+/// shadows. It has the same shape as the `BoxShadow` component which Bevy adds in 0.14, and
+/// will be replaced by it when quill moves to that version.
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+pub struct BoxShadow(pub Vec<ShadowStyle>);
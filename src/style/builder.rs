@@ -3,14 +3,72 @@
 use bevy::{
     asset::{AssetPath, Handle}, log::error, math::Vec3, prelude::Color, render::texture::Image, sprite::ImageScaleMode, ui::{self, ZIndex}
 };
+use bevy::text::{BreakLineOn, JustifyText};
+use bevy_color::LinearRgba;
 
-use crate::{PointerEvents, StyleProp};
+use crate::{
+    BackgroundLayer, ContentVisibility, CurrentColorTarget, Cursor, HitTest, ImageFit,
+    LinearGradient, PointerEvents, ShadowStyle, StyleProp, WritingMode,
+};
 
-use super::{selector::Selector, style_props::SelectorList, transition::Transition};
+use super::{
+    selector::Selector,
+    style_props::SelectorList,
+    transition::{Transition, TransitionProperty},
+};
 
 /// Trait that represents a CSS color
 pub trait ColorParam {
     fn to_val(self) -> Option<Color>;
+
+    /// True if this is the [`CURRENT_COLOR`] sentinel, which is resolved to the element's
+    /// text color during style computation.
+    fn is_current_color(&self) -> bool {
+        false
+    }
+
+    /// True if this is the [`ACCENT_COLOR`] sentinel, which is resolved to the element's
+    /// accent color during style computation.
+    fn is_accent_color(&self) -> bool {
+        false
+    }
+}
+
+/// Placeholder for the element's computed text color, equivalent to CSS `currentColor`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CurrentColor;
+
+/// Color value which resolves to the element's computed text color, including inherited
+/// text color.
+pub const CURRENT_COLOR: CurrentColor = CurrentColor;
+
+impl ColorParam for CurrentColor {
+    fn to_val(self) -> Option<Color> {
+        None
+    }
+
+    fn is_current_color(&self) -> bool {
+        true
+    }
+}
+
+/// Placeholder for the element's accent color, similar to how the CSS `accent-color` property
+/// is used to color the checked state of built-in controls.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AccentColor;
+
+/// Color value which resolves to the element's accent color, as set by
+/// [`StyleBuilder::accent_color`] on the element or one of its ancestors.
+pub const ACCENT_COLOR: AccentColor = AccentColor;
+
+impl ColorParam for AccentColor {
+    fn to_val(self) -> Option<Color> {
+        None
+    }
+
+    fn is_accent_color(&self) -> bool {
+        true
+    }
 }
 
 impl ColorParam for Option<Color> {
@@ -31,7 +89,8 @@ impl ColorParam for &str {
     }
 }
 
-/// Trait that represents a CSS "length"
+/// Trait that represents a CSS "length". Numbers are in logical pixels; any [`ui::Val`] can be
+/// passed for other units, including the viewport units `Vw`, `Vh`, `VMin` and `VMax`.
 pub trait LengthParam {
     fn to_val(self) -> ui::Val;
 }
@@ -65,6 +124,12 @@ impl ZIndexParam for ZIndex {
     }
 }
 
+impl ZIndexParam for Option<ZIndex> {
+    fn to_val(self) -> Option<ZIndex> {
+        self
+    }
+}
+
 impl ZIndexParam for i32 {
     fn to_val(self) -> Option<ZIndex> {
         Some(ZIndex::Local(self))
@@ -106,6 +171,78 @@ impl<H: LengthParam, V: LengthParam> UiRectParam for (H, V) {
     }
 }
 
+/// Sticky positioning, which can be passed to [`StyleBuilder::position`]. A sticky element
+/// is laid out normally, but when its scroll container scrolls, it stays pinned at its `top`
+/// offset from the top of the container, until it reaches the bottom of its parent.
+pub struct Sticky;
+
+/// Trait that represents a CSS "position" value.
+pub trait PositionParam {
+    fn to_prop(self) -> StyleProp;
+}
+
+impl PositionParam for ui::PositionType {
+    fn to_prop(self) -> StyleProp {
+        StyleProp::Position(self)
+    }
+}
+
+impl PositionParam for Sticky {
+    fn to_prop(self) -> StyleProp {
+        StyleProp::Sticky
+    }
+}
+
+/// Trait that represents a CSS "overflow" value: either a single [`ui::OverflowAxis`] which
+/// applies to both axes, or a [`ui::Overflow`] with separate values for each axis.
+///
+/// [`ui::OverflowAxis::Clip`] masks overflowing content to the element's bounds without
+/// making it scrollable. Note that Bevy clips to the rectangular bounds of the element, so
+/// the clip does not follow transforms (such as rotation) applied to the element.
+pub trait OverflowParam {
+    fn to_overflow(self) -> ui::Overflow;
+}
+
+impl OverflowParam for ui::OverflowAxis {
+    fn to_overflow(self) -> ui::Overflow {
+        ui::Overflow { x: self, y: self }
+    }
+}
+
+impl OverflowParam for ui::Overflow {
+    fn to_overflow(self) -> ui::Overflow {
+        self
+    }
+}
+
+/// An interactive state of an element, used by [`StyleBuilder::when`] and related methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElementState {
+    /// The element is being hovered (the `:hover` pseudo-class).
+    Hovered,
+    /// The element is being pressed by a pointer (the `:active` pseudo-class).
+    Pressed,
+    /// The element has keyboard focus (the `:focus` pseudo-class).
+    Focused,
+    /// The element has the `disabled` class. Quill doesn't track whether elements are
+    /// disabled, so this is up to the widget.
+    Disabled,
+}
+
+impl ElementState {
+    fn selector(self) -> &'static str {
+        match self {
+            ElementState::Hovered => ":hover",
+            ElementState::Pressed => ":active",
+            ElementState::Focused => ":focus",
+            ElementState::Disabled => ".disabled",
+        }
+    }
+}
+
+/// Builder for style properties. Properties are recorded in the order in which the builder
+/// methods are called, and applied in that same order, so a later call (for example
+/// `.padding_left()` after `.padding()`) always overrides an earlier one.
 pub struct StyleBuilder {
     pub(crate) props: Vec<StyleProp>,
     pub(crate) selectors: SelectorList,
@@ -119,11 +256,96 @@ impl StyleBuilder {
         }
     }
 
+    /// Reset margin, padding, border widths, and background, border and outline colors to
+    /// a zero baseline. Since properties are applied in order, this should come first, so
+    /// that it doesn't clobber properties set before it.
+    pub fn reset(&mut self) -> &mut Self {
+        self.props.push(StyleProp::Reset);
+        self
+    }
+
     pub fn image_scale(&mut self, scale: Option<ImageScaleMode>) -> &mut Self {
         self.props.push(StyleProp::ImageScale(scale));
         self
     }
 
+    pub fn image_fit(&mut self, fit: ImageFit) -> &mut Self {
+        self.props.push(StyleProp::ImageFit(fit));
+        self
+    }
+
+    /// Extend the pickable area of the element beyond its visual bounds by the given
+    /// number of pixels, without affecting layout.
+    pub fn hit_slop(&mut self, slop: impl Into<Option<f32>>) -> &mut Self {
+        self.props.push(StyleProp::HitSlop(slop.into()));
+        self
+    }
+
+    /// Set the thickness of the scrollbars. Applies to the scrollbars of a scroll area, or to
+    /// the scrollbar element itself.
+    pub fn scrollbar_width(&mut self, width: impl Into<Option<f32>>) -> &mut Self {
+        self.props.push(StyleProp::ScrollbarWidth(width.into()));
+        self
+    }
+
+    /// Set the color of the scrollbar thumb.
+    pub fn scrollbar_thumb_color(&mut self, color: impl ColorParam) -> &mut Self {
+        self.props
+            .push(StyleProp::ScrollbarThumbColor(color.to_val()));
+        self
+    }
+
+    /// Set the color of the scrollbar track.
+    pub fn scrollbar_track_color(&mut self, color: impl ColorParam) -> &mut Self {
+        self.props
+            .push(StyleProp::ScrollbarTrackColor(color.to_val()));
+        self
+    }
+
+    /// Skip styling of this element's descendants while it is scrolled out of view.
+    /// See [`ContentVisibility`].
+    pub fn content_visibility(&mut self, visibility: ContentVisibility) -> &mut Self {
+        self.props.push(StyleProp::ContentVisibility(visibility));
+        self
+    }
+
+    /// Lay out the element vertically, by rotating it. See [`WritingMode`].
+    pub fn writing_mode(&mut self, mode: WritingMode) -> &mut Self {
+        self.props.push(StyleProp::WritingMode(mode));
+        self
+    }
+
+    /// Set the color of the text caret in a text input.
+    pub fn caret_color(&mut self, color: impl ColorParam) -> &mut Self {
+        self.props.push(StyleProp::CaretColor(color.to_val()));
+        self
+    }
+
+    /// Set the color of the selection highlight in a text input.
+    pub fn selection_color(&mut self, color: impl ColorParam) -> &mut Self {
+        self.props.push(StyleProp::SelectionColor(color.to_val()));
+        self
+    }
+
+    /// Choose whether the element's entire bounding box responds to pointer events, or only
+    /// the visible pixels of its background image.
+    pub fn hit_test(&mut self, hit_test: HitTest) -> &mut Self {
+        self.props.push(StyleProp::HitTest(hit_test));
+        self
+    }
+
+    /// Stack multiple backgrounds behind the element's content, such as a translucent color
+    /// over a pattern image. Layers are painted in the order given, so later layers are
+    /// drawn over earlier ones. Each layer is rendered by a backing node which doesn't
+    /// respond to pointer events; see [`BackgroundLayers`].
+    ///
+    /// [`BackgroundLayers`]: crate::BackgroundLayers
+    pub fn background_layers(&mut self, layers: &[BackgroundLayer]) -> &mut Self {
+        self.props
+            .push(StyleProp::BackgroundLayers(Vec::from(layers)));
+        self
+    }
+
     pub fn background_image(&mut self, img: Option<AssetPath<'static>>) -> &mut Self {
         self.props.push(StyleProp::BackgroundImage(img));
         self
@@ -134,13 +356,57 @@ impl StyleBuilder {
         self
     }
 
+    /// Fill the background with a linear gradient, which replaces any background image.
+    /// `angle` is in degrees, as in CSS: 0 runs bottom to top, 90 runs left to right. Stop
+    /// positions may be percentages along the gradient line, or `Val::Auto` to space them
+    /// evenly. The gradient is rendered to a texture which is stretched over the node, and is
+    /// tinted by the background color like any other background image.
+    ///
+    /// ```ignore
+    /// ss.background_gradient(180., [
+    ///     (Val::Auto, SRgba::hex("#444").unwrap()),
+    ///     (Val::Auto, SRgba::hex("#2a2a2a").unwrap()),
+    /// ])
+    /// ```
+    pub fn background_gradient<C: Into<LinearRgba>>(
+        &mut self,
+        angle: f32,
+        stops: impl IntoIterator<Item = (ui::Val, C)>,
+    ) -> &mut Self {
+        self.props
+            .push(StyleProp::BackgroundGradient(LinearGradient {
+                angle,
+                stops: stops
+                    .into_iter()
+                    .map(|(pos, color)| (pos, color.into()))
+                    .collect(),
+            }));
+        self
+    }
+
     pub fn background_color(&mut self, color: impl ColorParam) -> &mut Self {
-        self.props.push(StyleProp::BackgroundColor(color.to_val()));
+        if color.is_current_color() {
+            self.props
+                .push(StyleProp::CurrentColor(CurrentColorTarget::Background));
+        } else if color.is_accent_color() {
+            self.props
+                .push(StyleProp::UseAccentColor(CurrentColorTarget::Background));
+        } else {
+            self.props.push(StyleProp::BackgroundColor(color.to_val()));
+        }
         self
     }
 
     pub fn border_color(&mut self, color: impl ColorParam) -> &mut Self {
-        self.props.push(StyleProp::BorderColor(color.to_val()));
+        if color.is_current_color() {
+            self.props
+                .push(StyleProp::CurrentColor(CurrentColorTarget::Border));
+        } else if color.is_accent_color() {
+            self.props
+                .push(StyleProp::UseAccentColor(CurrentColorTarget::Border));
+        } else {
+            self.props.push(StyleProp::BorderColor(color.to_val()));
+        }
         self
     }
 
@@ -149,23 +415,54 @@ impl StyleBuilder {
         self
     }
 
+    /// Set the accent color, which is inherited by descendants, and which controls use to
+    /// color their active or checked states via [`ACCENT_COLOR`].
+    pub fn accent_color(&mut self, color: impl ColorParam) -> &mut Self {
+        self.props.push(StyleProp::AccentColor(color.to_val()));
+        self
+    }
+
+    /// Set the stacking order of this element relative to its siblings. An `i32` is a
+    /// [`ZIndex::Local`]; pass `None` to remove a z-index set by an earlier style.
     pub fn z_index(&mut self, index: impl ZIndexParam) -> &mut Self {
         self.props.push(StyleProp::ZIndex(index.to_val()));
         self
     }
 
+    /// Set the stacking order of this element relative to all other UI nodes, regardless of
+    /// where it appears in the hierarchy. Useful for popups such as menus and tooltips.
+    pub fn z_index_global(&mut self, index: i32) -> &mut Self {
+        self.props
+            .push(StyleProp::ZIndex(Some(ZIndex::Global(index))));
+        self
+    }
+
+    /// Set the visual order of this element within its parent. See [`VisualOrder`].
+    ///
+    /// [`VisualOrder`]: crate::VisualOrder
+    pub fn order(&mut self, order: i32) -> &mut Self {
+        self.props.push(StyleProp::Order(order));
+        self
+    }
+
     pub fn display(&mut self, disp: ui::Display) -> &mut Self {
         self.props.push(StyleProp::Display(disp));
         self
     }
 
-    pub fn position(&mut self, pos: ui::PositionType) -> &mut Self {
-        self.props.push(StyleProp::Position(pos));
+    pub fn position(&mut self, pos: impl PositionParam) -> &mut Self {
+        self.props.push(pos.to_prop());
         self
     }
 
-    pub fn overflow(&mut self, ov: ui::OverflowAxis) -> &mut Self {
-        self.props.push(StyleProp::Overflow(ov));
+    pub fn overflow(&mut self, ov: impl OverflowParam) -> &mut Self {
+        let ov = ov.to_overflow();
+        if ov.x == ov.y {
+            self.props.push(StyleProp::Overflow(ov.x));
+        } else {
+            self.props.push(StyleProp::OverflowX(ov.x));
+            self.props.push(StyleProp::OverflowY(ov.y));
+        }
         self
     }
 
@@ -214,6 +511,18 @@ impl StyleBuilder {
         self
     }
 
+    /// Set the width as a percentage of the width of the viewport. Shortcut for
+    /// `.width(Val::Vw(vw))`.
+    pub fn width_vw(&mut self, vw: f32) -> &mut Self {
+        self.width(ui::Val::Vw(vw))
+    }
+
+    /// Set the height as a percentage of the height of the viewport. Shortcut for
+    /// `.height(Val::Vh(vh))`.
+    pub fn height_vh(&mut self, vh: f32) -> &mut Self {
+        self.height(ui::Val::Vh(vh))
+    }
+
     pub fn min_width(&mut self, length: impl LengthParam) -> &mut Self {
         self.props.push(StyleProp::MinWidth(length.to_val()));
         self
@@ -321,7 +630,17 @@ impl StyleBuilder {
         self
     }
 
-    // Flex(ExprList),
+    /// Shorthand for the CSS `flex: <n>` form: sets `flex-grow` to `n`, `flex-shrink` to 1
+    /// and `flex-basis` to 0, so that siblings share the available space in proportion to
+    /// `n`. Note that `flex(0)` is CSS `flex: 0` (which still allows the element to shrink
+    /// below its content size), not `flex: none`, which would also disable shrinking and use
+    /// an automatic basis.
+    pub fn flex(&mut self, n: u32) -> &mut Self {
+        self.props.push(StyleProp::FlexGrow(n as f32));
+        self.props.push(StyleProp::FlexShrink(1.));
+        self.props.push(StyleProp::FlexBasis(ui::Val::Px(0.)));
+        self
+    }
 
     pub fn flex_grow(&mut self, n: f32) -> &mut Self {
         self.props.push(StyleProp::FlexGrow(n));
@@ -448,10 +767,16 @@ impl StyleBuilder {
         self
     }
 
-    // LineBreak(BreakLineOn),
-
     pub fn outline_color(&mut self, color: impl ColorParam) -> &mut Self {
-        self.props.push(StyleProp::OutlineColor(color.to_val()));
+        if color.is_current_color() {
+            self.props
+                .push(StyleProp::CurrentColor(CurrentColorTarget::Outline));
+        } else if color.is_accent_color() {
+            self.props
+                .push(StyleProp::UseAccentColor(CurrentColorTarget::Outline));
+        } else {
+            self.props.push(StyleProp::OutlineColor(color.to_val()));
+        }
         self
     }
 
@@ -465,11 +790,83 @@ impl StyleBuilder {
         self
     }
 
+    /// Set the radius of all four corners. See [`BorderRadius`](crate::BorderRadius) for
+    /// how the radius is rendered.
+    pub fn border_radius(&mut self, length: impl LengthParam) -> &mut Self {
+        self.props.push(StyleProp::BorderRadius(length.to_val()));
+        self
+    }
+
+    pub fn border_radius_top_left(&mut self, length: impl LengthParam) -> &mut Self {
+        self.props
+            .push(StyleProp::BorderRadiusTopLeft(length.to_val()));
+        self
+    }
+
+    pub fn border_radius_top_right(&mut self, length: impl LengthParam) -> &mut Self {
+        self.props
+            .push(StyleProp::BorderRadiusTopRight(length.to_val()));
+        self
+    }
+
+    pub fn border_radius_bottom_left(&mut self, length: impl LengthParam) -> &mut Self {
+        self.props
+            .push(StyleProp::BorderRadiusBottomLeft(length.to_val()));
+        self
+    }
+
+    pub fn border_radius_bottom_right(&mut self, length: impl LengthParam) -> &mut Self {
+        self.props
+            .push(StyleProp::BorderRadiusBottomRight(length.to_val()));
+        self
+    }
+
+    /// Add a drop shadow. Calling this more than once in the same style stacks the shadows,
+    /// with the first one on top; a style applied later, such as a `:hover` selector,
+    /// replaces the whole stack. Passing `None` as the color removes all shadows. See
+    /// [`BoxShadow`](crate::BoxShadow) for how shadows are rendered.
+    pub fn box_shadow(
+        &mut self,
+        color: impl ColorParam,
+        x_offset: impl LengthParam,
+        y_offset: impl LengthParam,
+        blur_radius: impl LengthParam,
+        spread_radius: impl LengthParam,
+    ) -> &mut Self {
+        let Some(color) = color.to_val() else {
+            self.props.push(StyleProp::BoxShadow(Vec::new()));
+            return self;
+        };
+        let shadow = ShadowStyle {
+            color,
+            x_offset: x_offset.to_val(),
+            y_offset: y_offset.to_val(),
+            blur_radius: blur_radius.to_val(),
+            spread_radius: spread_radius.to_val(),
+        };
+        match self.props.iter_mut().rev().find_map(|prop| match prop {
+            StyleProp::BoxShadow(shadows) => Some(shadows),
+            _ => None,
+        }) {
+            // An earlier `None` clears the stack; later shadows start a new one.
+            Some(shadows) => shadows.push(shadow),
+            None => self.props.push(StyleProp::BoxShadow(vec![shadow])),
+        }
+        self
+    }
+
     pub fn pointer_events(&mut self, pe: PointerEvents) -> &mut Self {
         self.props.push(StyleProp::PointerEvents(pe));
         self
     }
 
+    /// Set the cursor shown while the pointer is over this element or its descendants, unless
+    /// a descendant sets a cursor of its own.
+    pub fn cursor(&mut self, cursor: Cursor) -> &mut Self {
+        self.props.push(StyleProp::Cursor(cursor));
+        self
+    }
+
     pub fn font(&mut self, path: Option<AssetPath<'static>>) -> &mut Self {
         self.props.push(StyleProp::Font(path));
         self
@@ -480,6 +877,50 @@ impl StyleBuilder {
         self
     }
 
+    /// Set the horizontal alignment of lines of text within the text node. Inherited by
+    /// descendants.
+    pub fn text_align(&mut self, align: JustifyText) -> &mut Self {
+        self.props.push(StyleProp::TextAlign(align));
+        self
+    }
+
+    /// Set how text is wrapped when it exceeds the width of the text node. Inherited by
+    /// descendants.
+    pub fn line_break(&mut self, line_break: BreakLineOn) -> &mut Self {
+        self.props.push(StyleProp::LineBreak(line_break));
+        self
+    }
+
+    /// Set the height of a line of text, as a multiple of the font size. Inherited by
+    /// descendants. See [`TextSpacing`](crate::TextSpacing).
+    pub fn line_height(&mut self, val: f32) -> &mut Self {
+        self.props.push(StyleProp::LineHeight(val));
+        self
+    }
+
+    /// Set the extra space between letters, in logical pixels. Inherited by descendants. See
+    /// [`TextSpacing`](crate::TextSpacing).
+    pub fn letter_spacing(&mut self, val: f32) -> &mut Self {
+        self.props.push(StyleProp::LetterSpacing(val));
+        self
+    }
+
+    /// Set the opacity of this element, from 0 (transparent) to 1 (opaque). Like CSS, this
+    /// also fades the element's descendants: the opacity of each element is multiplied by that
+    /// of its ancestors, and applied to its background, border, outline, shadow and text
+    /// colors.
+    pub fn opacity(&mut self, opacity: f32) -> &mut Self {
+        self.props.push(StyleProp::Opacity(opacity.clamp(0., 1.)));
+        self
+    }
+
+    /// Continuously rotate the element about its center, at the given speed in radians per
+    /// second. Pass `None` to stop spinning.
+    pub fn spin(&mut self, speed: impl Into<Option<f32>>) -> &mut Self {
+        self.props.push(StyleProp::Spin(speed.into()));
+        self
+    }
+
     pub fn scale_x(&mut self, scale: f32) -> &mut Self {
         self.props.push(StyleProp::ScaleX(scale));
         self
@@ -505,12 +946,88 @@ impl StyleBuilder {
         self
     }
 
+    /// Set the point about which rotation and scaling are applied, relative to the top-left
+    /// corner of the node. The default is the center of the node. Percentages are relative
+    /// to the node's size.
+    pub fn transform_origin(&mut self, x: impl LengthParam, y: impl LengthParam) -> &mut Self {
+        self.props
+            .push(StyleProp::TransformOrigin(x.to_val(), y.to_val()));
+        self
+    }
+
     pub fn transition(&mut self, transition: &[Transition]) -> &mut Self {
         self.props
             .push(StyleProp::Transition(Vec::from(transition)));
         self
     }
 
+    /// Set the delay before the transition for the given property starts. This modifies the
+    /// most recent [`transition`](Self::transition) declaration for that property.
+    pub fn transition_delay(&mut self, property: TransitionProperty, delay: f32) -> &mut Self {
+        let transition = self.props.iter_mut().rev().find_map(|prop| match prop {
+            StyleProp::Transition(transitions) => {
+                transitions.iter_mut().find(|t| t.property == property)
+            }
+            _ => None,
+        });
+        match transition {
+            Some(transition) => transition.delay = delay,
+            None => error!("transition_delay: no transition for {:?}", property),
+        }
+        self
+    }
+
+    /// Add styles which only apply while the element is hovered. Shorthand for
+    /// `.selector("&:hover", ...)`.
+    pub fn when_hovered(
+        &mut self,
+        builder_fn: impl FnOnce(&mut StyleBuilder) -> &mut StyleBuilder,
+    ) -> &mut Self {
+        self.when(&[ElementState::Hovered], builder_fn)
+    }
+
+    /// Add styles which only apply while the element is pressed. Shorthand for
+    /// `.selector("&:active", ...)`.
+    pub fn when_pressed(
+        &mut self,
+        builder_fn: impl FnOnce(&mut StyleBuilder) -> &mut StyleBuilder,
+    ) -> &mut Self {
+        self.when(&[ElementState::Pressed], builder_fn)
+    }
+
+    /// Add styles which only apply while the element has focus. Shorthand for
+    /// `.selector("&:focus", ...)`.
+    pub fn when_focused(
+        &mut self,
+        builder_fn: impl FnOnce(&mut StyleBuilder) -> &mut StyleBuilder,
+    ) -> &mut Self {
+        self.when(&[ElementState::Focused], builder_fn)
+    }
+
+    /// Add styles which only apply while the element has the `disabled` class. Shorthand for
+    /// `.selector("&.disabled", ...)`.
+    pub fn when_disabled(
+        &mut self,
+        builder_fn: impl FnOnce(&mut StyleBuilder) -> &mut StyleBuilder,
+    ) -> &mut Self {
+        self.when(&[ElementState::Disabled], builder_fn)
+    }
+
+    /// Add styles which only apply while the element is in all of the given states at once,
+    /// for example both hovered and pressed. As with selectors, these are applied after the
+    /// base styles, in the order they were declared.
+    pub fn when(
+        &mut self,
+        states: &[ElementState],
+        builder_fn: impl FnOnce(&mut StyleBuilder) -> &mut StyleBuilder,
+    ) -> &mut Self {
+        let mut expr = String::from("&");
+        for state in states {
+            expr.push_str(state.selector());
+        }
+        self.selector(&expr, builder_fn)
+    }
+
     /// Add a selector expression to this style declaration.
     pub fn selector(
         &mut self,
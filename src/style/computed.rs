@@ -1,8 +1,23 @@
-use super::style_props::PointerEvents;
+use super::background_layers::{sync_background_layers, BackgroundLayer};
+use super::border_radius::BorderRadius;
+use super::box_shadow::{BoxShadow, ShadowStyle};
+use super::content_visibility::{ContentVisibility, ContentVisibilityState};
+use super::gradient::{gradient_texture, LinearGradient};
+use super::hit_slop::HitSlop;
+use super::hit_test::HitTest;
+use super::image_fit::{ImageFit, ImageFitLayout};
+use super::order::VisualOrder;
+use super::scrollbar::ScrollbarStyle;
+use super::spin::Spin;
+use super::style_props::{CurrentColorTarget, PointerEvents};
+use super::text_input::TextInputStyle;
+use super::text_spacing::TextSpacing;
 use super::transition::{
     AnimatedBackgroundColor, AnimatedBorderColor, AnimatedLayout, AnimatedLayoutProp,
-    AnimatedTransform, Transition, TransitionProperty, TransitionState,
+    AnimatedOpacity, AnimatedTransform, Transition, TransitionProperty, TransitionState,
 };
+use super::writing_mode::{footprint_margin, VerticalFootprint, WritingMode};
+use crate::{Cursor, RichTextNode, StickyPosition};
 use bevy::asset::AssetPath;
 use bevy::ecs::system::Command;
 use bevy::prelude::*;
@@ -12,6 +27,14 @@ use bevy::ui::ContentSize;
 use bevy::utils::HashMap;
 use bevy_mod_picking::prelude::Pickable;
 
+fn resolve_origin(val: Val, size: f32) -> f32 {
+    match val {
+        Val::Px(px) => px - size * 0.5,
+        Val::Percent(pct) => size * (pct * 0.01 - 0.5),
+        _ => 0.,
+    }
+}
+
 /// A computed style represents the composition of one or more `ElementStyle`s.
 #[derive(Default, Clone, Debug)]
 #[doc(hidden)]
@@ -24,31 +47,57 @@ pub struct ComputedStyle {
     pub font_size: Option<f32>,
     pub font: Option<AssetPath<'static>>,
     pub font_handle: Option<Handle<Font>>,
+    pub line_height: Option<f32>,
+    pub letter_spacing: Option<f32>,
     pub line_break: Option<BreakLineOn>,
+    pub writing_mode: WritingMode,
 
     // pub text_style: TextStyle,
     pub border_color: Option<Color>,
     pub background_color: Option<Color>,
     pub outline_color: Option<Color>,
+    pub current_color: Vec<CurrentColorTarget>,
+    pub accent_color: Option<Color>,
+    pub accent_color_targets: Vec<CurrentColorTarget>,
     pub outline_width: Val,
     pub outline_offset: Val,
+    pub border_radius: BorderRadius,
+    pub box_shadow: Vec<ShadowStyle>,
     pub z_index: Option<ZIndex>,
+    pub opacity: Option<f32>,
+    pub inherited_opacity: Option<f32>,
+    pub order: i32,
+    pub sticky: bool,
+    pub content_visibility: ContentVisibility,
 
     // Transform properties
     pub scale_x: Option<f32>,
     pub scale_y: Option<f32>,
     pub rotation: Option<f32>,
     pub translation: Option<Vec3>,
+    pub transform_origin: Option<(Val, Val)>,
+    pub spin: Option<f32>,
 
     // Image properties
     pub image: Option<ComputedImage>,
     pub image_scale: Option<ImageScaleMode>,
+    pub image_fit: ImageFit,
     pub image_handle: Option<Handle<Image>>,
+    pub background_layers: Vec<BackgroundLayer>,
     pub flip_x: bool,
     pub flip_y: bool,
 
     // Picking properties
     pub pickable: Option<PointerEvents>,
+    pub hit_slop: Option<f32>,
+    pub hit_test: HitTest,
+    pub cursor: Option<Cursor>,
+
+    // Scrollbar properties
+    pub scrollbar: ScrollbarStyle,
+
+    // Text input properties
+    pub text_input: TextInputStyle,
 
     // Transitiions
     pub transitions: Vec<Transition>,
@@ -59,12 +108,94 @@ impl ComputedStyle {
     pub fn new() -> Self {
         Self { ..default() }
     }
+
+    /// The opacity of the element combined with that of its ancestors.
+    pub fn effective_opacity(&self) -> f32 {
+        self.inherited_opacity.unwrap_or(1.) * self.opacity.unwrap_or(1.)
+    }
+
+    /// Compute the node transform from the transform properties, given the size of the node.
+    /// Rotation and scaling are applied about the transform origin.
+    pub fn transform(&self, node_size: Vec2) -> Transform {
+        let mut transform = Transform::default();
+        transform.translation = self.translation.unwrap_or(transform.translation);
+        transform.scale.x = self.scale_x.unwrap_or(1.);
+        transform.scale.y = self.scale_y.unwrap_or(1.);
+        transform.rotate_z(self.rotation.unwrap_or(0.) + self.writing_mode.rotation());
+        if let Some((x, y)) = self.transform_origin {
+            // Offset of the origin from the center of the node, since that is where
+            // the node's transform is anchored.
+            let origin = Vec3::new(
+                resolve_origin(x, node_size.x),
+                resolve_origin(y, node_size.y),
+                0.,
+            );
+            transform.translation += origin - transform.rotation * (transform.scale * origin);
+        }
+        transform
+    }
+
+    /// Reset the box model and decoration properties (margin, padding, border widths, and
+    /// background, border and outline colors) to a known baseline, regardless of any defaults
+    /// supplied by the element.
+    pub(crate) fn reset(&mut self) {
+        self.style.margin = UiRect::ZERO;
+        self.style.padding = UiRect::ZERO;
+        self.style.border = UiRect::ZERO;
+        self.background_color = None;
+        self.border_color = None;
+        self.outline_color = None;
+        self.outline_width = Val::ZERO;
+        self.outline_offset = Val::ZERO;
+        self.current_color.clear();
+        self.accent_color_targets.clear();
+    }
+
+    /// Mark whether the given color property should track the text color.
+    pub(crate) fn set_current_color(&mut self, target: CurrentColorTarget, enable: bool) {
+        self.current_color.retain(|t| *t != target);
+        self.accent_color_targets.retain(|t| *t != target);
+        if enable {
+            self.current_color.push(target);
+        }
+    }
+
+    pub(crate) fn set_accent_color(&mut self, target: CurrentColorTarget, enable: bool) {
+        self.set_current_color(target, false);
+        if enable {
+            self.accent_color_targets.push(target);
+        }
+    }
+
+    fn set_target_color(&mut self, target: CurrentColorTarget, color: Option<Color>) {
+        match target {
+            CurrentColorTarget::Background => self.background_color = color,
+            CurrentColorTarget::Border => self.border_color = color,
+            CurrentColorTarget::Outline => self.outline_color = color,
+        }
+    }
+
+    /// Replace any color properties which use [`CURRENT_COLOR`] with the computed text
+    /// color, and any which use [`ACCENT_COLOR`] with the computed accent color. Should be
+    /// called after all styles have been applied.
+    ///
+    /// [`CURRENT_COLOR`]: crate::CURRENT_COLOR
+    /// [`ACCENT_COLOR`]: crate::ACCENT_COLOR
+    pub fn resolve_current_color(&mut self) {
+        for target in self.current_color.clone() {
+            self.set_target_color(target, self.color);
+        }
+        for target in self.accent_color_targets.clone() {
+            self.set_target_color(target, self.accent_color);
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
 pub enum ComputedImage {
     Handle(Handle<Image>),
     Path(AssetPath<'static>),
+    Gradient(LinearGradient),
 }
 
 /// Custom command that updates the style of an entity.
@@ -74,10 +205,62 @@ pub struct UpdateComputedStyle {
 }
 
 impl Command for UpdateComputedStyle {
-    fn apply(self, world: &mut World) {
-        let Some(mut e) = world.get_entity_mut(self.entity) else {
+    fn apply(mut self, world: &mut World) {
+        if world.get_entity(self.entity).is_none() {
             return;
-        };
+        }
+
+        // Gradients are rendered to a texture, which needs access to the image assets.
+        if let Some(ComputedImage::Gradient(gradient)) = &self.computed.image {
+            self.computed.image_handle = Some(gradient_texture(world, gradient));
+        }
+
+        let mut e = world.entity_mut(self.entity);
+
+        // An animated opacity moves towards the computed value from wherever it currently is.
+        let mut opacity = self.computed.effective_opacity();
+        let opacity_transition = self
+            .computed
+            .transitions
+            .iter()
+            .find(|t| t.property == TransitionProperty::Opacity);
+        let target = self.computed.opacity.unwrap_or(1.);
+        match (opacity_transition, e.get_mut::<AnimatedOpacity>()) {
+            (Some(transition), Some(mut anim)) => {
+                if anim.target != target {
+                    anim.origin = anim.value();
+                    anim.target = target;
+                    anim.state = TransitionState::new(transition.clone());
+                }
+                opacity = self.computed.inherited_opacity.unwrap_or(1.) * anim.value();
+            }
+            (Some(transition), None) => {
+                e.insert(AnimatedOpacity {
+                    state: TransitionState::new(transition.clone()),
+                    origin: target,
+                    target,
+                });
+            }
+            (None, Some(_)) => {
+                e.remove::<AnimatedOpacity>();
+            }
+            (None, None) => {}
+        }
+
+        // Fade the element's colors. Text colors are faded below, since they default to white.
+        if opacity < 1. {
+            let colors = [
+                &mut self.computed.background_color,
+                &mut self.computed.border_color,
+                &mut self.computed.outline_color,
+            ];
+            for color in colors.into_iter().flatten() {
+                color.set_a(color.a() * opacity);
+            }
+            for shadow in self.computed.box_shadow.iter_mut() {
+                shadow.color.set_a(shadow.color.a() * opacity);
+            }
+        }
 
         let mut is_animated_bg_color = false;
         let mut is_animated_border_color = false;
@@ -86,6 +269,42 @@ impl Command for UpdateComputedStyle {
 
         let mut next_style = self.computed.style;
 
+        // For sticky elements, `top` is the pinning threshold; the actual offset is managed by
+        // the scrolling system, so preserve it.
+        match (self.computed.sticky, e.get_mut::<StickyPosition>()) {
+            (true, Some(mut sticky)) => {
+                if sticky.top != next_style.top {
+                    sticky.top = next_style.top;
+                }
+                next_style.top = Val::Px(sticky.offset);
+            }
+            (true, None) => {
+                e.insert(StickyPosition::new(next_style.top));
+                next_style.top = Val::Px(0.);
+            }
+            (false, Some(_)) => {
+                e.remove::<StickyPosition>();
+            }
+            (false, None) => {}
+        }
+
+        // For vertical elements, the margins are managed by the writing mode system.
+        match (
+            self.computed.writing_mode.is_vertical(),
+            e.get::<VerticalFootprint>(),
+        ) {
+            (true, Some(footprint)) => {
+                next_style.margin = footprint_margin(footprint.size);
+            }
+            (true, None) => {
+                e.insert(VerticalFootprint::default());
+            }
+            (false, Some(_)) => {
+                e.remove::<VerticalFootprint>();
+            }
+            (false, None) => {}
+        }
+
         self.computed
             .transitions
             .iter()
@@ -103,6 +322,7 @@ impl Command for UpdateComputedStyle {
                 | TransitionProperty::BorderTop
                 | TransitionProperty::BorderRight
                 | TransitionProperty::BorderBottom => is_animated_layout = true,
+                TransitionProperty::Opacity => (),
             });
 
         let bg_image = self.computed.image_handle;
@@ -141,10 +361,7 @@ impl Command for UpdateComputedStyle {
                         | TransitionProperty::BorderTop
                         | TransitionProperty::BorderRight
                         | TransitionProperty::BorderBottom => {
-                            let mut ap = AnimatedLayoutProp::new(TransitionState {
-                                transition: tr.clone(),
-                                clock: 0.,
-                            });
+                            let mut ap = AnimatedLayoutProp::new(TransitionState::new(tr.clone()));
                             ap.update(tr.property, &mut next_style, 0., true);
                             anim.0.insert(tr.property, ap);
                         }
@@ -164,12 +381,17 @@ impl Command for UpdateComputedStyle {
             e.insert(next_style);
         }
 
+        // Rich text sections carry their own styles, which aren't overridden.
+        let rich_text = e.contains::<RichTextNode>();
         if let Some(mut text) = e.get_mut::<Text>() {
             // White is the default.
-            let color = self.computed.color.unwrap_or(Color::WHITE);
-            for section in text.sections.iter_mut() {
-                if section.style.color != color {
-                    section.style.color = color;
+            let mut color = self.computed.color.unwrap_or(Color::WHITE);
+            color.set_a(color.a() * opacity);
+            if !rich_text {
+                for section in text.sections.iter_mut() {
+                    if section.style.color != color {
+                        section.style.color = color;
+                    }
                 }
             }
 
@@ -179,7 +401,13 @@ impl Command for UpdateComputedStyle {
                 }
             }
 
-            if let Some(font_size) = self.computed.font_size {
+            if let Some(align) = self.computed.alignment {
+                if text.justify != align {
+                    text.justify = align;
+                }
+            }
+
+            if let Some(font_size) = self.computed.font_size.filter(|_| !rich_text) {
                 for section in text.sections.iter_mut() {
                     if section.style.font_size != font_size {
                         section.style.font_size = font_size;
@@ -187,19 +415,67 @@ impl Command for UpdateComputedStyle {
                 }
             }
 
-            if let Some(ref font) = self.computed.font_handle {
+            if let Some(font) = self.computed.font_handle.as_ref().filter(|_| !rich_text) {
                 for section in text.sections.iter_mut() {
                     if section.style.font != *font {
                         section.style.font = font.clone();
                     }
                 }
             }
+
+            let spacing = TextSpacing {
+                line_height: self.computed.line_height,
+                letter_spacing: self.computed.letter_spacing,
+            };
+            match e.get_mut::<TextSpacing>() {
+                Some(_) if spacing.is_empty() => {
+                    e.remove::<TextSpacing>();
+                }
+                Some(mut current) => {
+                    if *current != spacing {
+                        *current = spacing;
+                    }
+                }
+                None if !spacing.is_empty() => {
+                    e.insert(spacing);
+                }
+                None => {}
+            }
         }
 
         if is_animated_bg_color {
+            let transition = self
+                .computed
+                .transitions
+                .iter()
+                .find(|t| t.property == TransitionProperty::BackgroundColor)
+                .unwrap();
+            // Images require a background color to be set.
+            let color = match (self.computed.background_color, &bg_image) {
+                (Some(color), _) => color,
+                (None, Some(_)) => BackgroundColor::DEFAULT.0,
+                (None, None) => Color::NONE,
+            };
+            let current = e.get::<BackgroundColor>().map(|bg| bg.0);
             match e.get_mut::<AnimatedBackgroundColor>() {
-                Some(_) => todo!(),
-                None => todo!(),
+                Some(mut at) => {
+                    if at.target != color {
+                        // Start from the color being displayed, which may be mid-transition.
+                        at.origin = current.unwrap_or(at.target);
+                        at.target = color;
+                        at.state = TransitionState::new(transition.clone());
+                    }
+                }
+                None => {
+                    e.insert(AnimatedBackgroundColor {
+                        state: TransitionState::new(transition.clone()),
+                        origin: color,
+                        target: color,
+                    });
+                }
+            }
+            if current.is_none() {
+                e.insert(BackgroundColor(color));
             }
         } else {
             e.remove::<AnimatedBackgroundColor>();
@@ -232,9 +508,33 @@ impl Command for UpdateComputedStyle {
         }
 
         if is_animated_border_color {
+            let transition = self
+                .computed
+                .transitions
+                .iter()
+                .find(|t| t.property == TransitionProperty::BorderColor)
+                .unwrap();
+            let color = self.computed.border_color.unwrap_or(Color::NONE);
+            let current = e.get::<BorderColor>().map(|bc| bc.0);
             match e.get_mut::<AnimatedBorderColor>() {
-                Some(_) => todo!(),
-                None => todo!(),
+                Some(mut at) => {
+                    if at.target != color {
+                        // Start from the color being displayed, which may be mid-transition.
+                        at.origin = current.unwrap_or(at.target);
+                        at.target = color;
+                        at.state = TransitionState::new(transition.clone());
+                    }
+                }
+                None => {
+                    e.insert(AnimatedBorderColor {
+                        state: TransitionState::new(transition.clone()),
+                        origin: color,
+                        target: color,
+                    });
+                }
+            }
+            if current.is_none() {
+                e.insert(BorderColor(color));
             }
         } else {
             e.remove::<AnimatedBorderColor>();
@@ -300,14 +600,34 @@ impl Command for UpdateComputedStyle {
         match (e.get_mut::<ImageScaleMode>(), self.computed.image_scale) {
             (Some(mut scale), Some(img_scale)) => {
                 *scale = img_scale;
-            },
+            }
             (Some(_), None) => {
                 e.remove::<ImageScaleMode>();
             }
             (None, Some(img_scale)) => {
                 e.insert(img_scale);
-            },
-            (None, None) => { }
+            }
+            (None, None) => {}
+        }
+
+        // Fill is the default behavior of UiImage, and doesn't require an atlas.
+        match (e.get_mut::<ImageFit>(), self.computed.image_fit) {
+            (Some(_), ImageFit::Fill) => {
+                // Leave alone any atlas which wasn't inserted by `update_image_fit`.
+                if e.contains::<ImageFitLayout>() {
+                    e.remove::<(ImageFitLayout, TextureAtlas)>();
+                }
+                e.remove::<ImageFit>();
+            }
+            (Some(mut fit), image_fit) => {
+                if *fit != image_fit {
+                    *fit = image_fit;
+                }
+            }
+            (None, ImageFit::Fill) => {}
+            (None, image_fit) => {
+                e.insert(image_fit);
+            }
         }
 
         // Update outline
@@ -330,6 +650,39 @@ impl Command for UpdateComputedStyle {
             (None, None) => {}
         }
 
+        // Update corner radii. Square corners don't need a component.
+        let radius = self.computed.border_radius;
+        match e.get_mut::<BorderRadius>() {
+            Some(_) if radius == BorderRadius::ZERO => {
+                e.remove::<BorderRadius>();
+            }
+            Some(mut border_radius) => {
+                if *border_radius != radius {
+                    *border_radius = radius;
+                }
+            }
+            None if radius != BorderRadius::ZERO => {
+                e.insert(radius);
+            }
+            None => {}
+        }
+
+        // Update shadows
+        match e.get_mut::<BoxShadow>() {
+            Some(_) if self.computed.box_shadow.is_empty() => {
+                e.remove::<BoxShadow>();
+            }
+            Some(mut box_shadow) => {
+                if box_shadow.0 != self.computed.box_shadow {
+                    box_shadow.0.clone_from(&self.computed.box_shadow);
+                }
+            }
+            None if !self.computed.box_shadow.is_empty() => {
+                e.insert(BoxShadow(self.computed.box_shadow.clone()));
+            }
+            None => {}
+        }
+
         // Update Z-Index
         match (self.computed.z_index, e.get::<ZIndex>()) {
             // Don't change if value is the same
@@ -347,6 +700,124 @@ impl Command for UpdateComputedStyle {
             (None, None) => {}
         }
 
+        match (self.computed.cursor, e.get_mut::<Cursor>()) {
+            (Some(cursor), Some(mut current)) => {
+                if *current != cursor {
+                    *current = cursor;
+                }
+            }
+            (None, Some(_)) => {
+                e.remove::<Cursor>();
+            }
+            (Some(cursor), None) => {
+                e.insert(cursor);
+            }
+            (None, None) => {}
+        }
+
+        match (self.computed.hit_slop, e.get_mut::<HitSlop>()) {
+            (Some(slop), Some(mut hit_slop)) => {
+                if hit_slop.0 != slop {
+                    hit_slop.0 = slop;
+                }
+            }
+            (None, Some(_)) => {
+                e.remove::<HitSlop>();
+            }
+            (Some(slop), None) => {
+                e.insert(HitSlop(slop));
+            }
+            (None, None) => {}
+        }
+
+        match (self.computed.scrollbar, e.get_mut::<ScrollbarStyle>()) {
+            (scrollbar, Some(_)) if scrollbar.is_empty() => {
+                e.remove::<ScrollbarStyle>();
+            }
+            (scrollbar, Some(mut current)) => {
+                if *current != scrollbar {
+                    *current = scrollbar;
+                }
+            }
+            (scrollbar, None) if !scrollbar.is_empty() => {
+                e.insert(scrollbar);
+            }
+            _ => {}
+        }
+
+        match (self.computed.text_input, e.get_mut::<TextInputStyle>()) {
+            (text_input, Some(_)) if text_input.is_empty() => {
+                e.remove::<TextInputStyle>();
+            }
+            (text_input, Some(mut current)) => {
+                if *current != text_input {
+                    *current = text_input;
+                }
+            }
+            (text_input, None) if !text_input.is_empty() => {
+                e.insert(text_input);
+            }
+            _ => {}
+        }
+
+        match (self.computed.spin, e.get_mut::<Spin>()) {
+            (Some(speed), Some(mut spin)) => {
+                if spin.speed != speed {
+                    spin.speed = speed;
+                }
+            }
+            (None, Some(_)) => {
+                e.remove::<Spin>();
+            }
+            (Some(speed), None) => {
+                e.insert(Spin::new(speed));
+            }
+            (None, None) => {}
+        }
+
+        match (
+            self.computed.content_visibility,
+            e.contains::<ContentVisibilityState>(),
+        ) {
+            (ContentVisibility::Auto, false) => {
+                e.insert(ContentVisibilityState::default());
+            }
+            (ContentVisibility::Visible, true) => {
+                e.remove::<ContentVisibilityState>();
+            }
+            _ => {}
+        }
+
+        match (self.computed.order, e.get_mut::<VisualOrder>()) {
+            (0, Some(_)) => {
+                e.remove::<VisualOrder>();
+            }
+            (0, None) => {}
+            (order, Some(mut current)) => {
+                if current.0 != order {
+                    current.0 = order;
+                }
+            }
+            (order, None) => {
+                e.insert(VisualOrder(order));
+            }
+        }
+
+        match (self.computed.hit_test, e.get_mut::<HitTest>()) {
+            (HitTest::BoundingBox, Some(_)) => {
+                e.remove::<HitTest>();
+            }
+            (HitTest::BoundingBox, None) => {}
+            (hit_test, Some(mut current)) => {
+                if *current != hit_test {
+                    *current = hit_test;
+                }
+            }
+            (hit_test, None) => {
+                e.insert(hit_test);
+            }
+        }
+
         // Update Pickable
         match (self.computed.pickable, e.get_mut::<Pickable>()) {
             (Some(pe), Some(mut pickable)) => {
@@ -365,11 +836,9 @@ impl Command for UpdateComputedStyle {
             (None, None) => {}
         }
 
-        let mut transform = Transform::default();
-        transform.translation = self.computed.translation.unwrap_or(transform.translation);
-        transform.scale.x = self.computed.scale_x.unwrap_or(1.);
-        transform.scale.y = self.computed.scale_y.unwrap_or(1.);
-        transform.rotate_z(self.computed.rotation.unwrap_or(0.));
+        // Percentage origins are resolved against the most recent layout.
+        let node_size = e.get::<Node>().map(|n| n.size()).unwrap_or_default();
+        let transform = self.computed.transform(node_size);
         if is_animated_transform {
             let prev_transform = *e.get_mut::<Transform>().unwrap();
             let transition = self
@@ -385,10 +854,7 @@ impl Command for UpdateComputedStyle {
                         || at.target.rotation != transform.rotation
                     {
                         e.insert(AnimatedTransform {
-                            state: TransitionState {
-                                transition: transition.clone(),
-                                clock: 0.,
-                            },
+                            state: TransitionState::new(transition.clone()),
                             origin: prev_transform,
                             target: transform,
                         });
@@ -396,10 +862,7 @@ impl Command for UpdateComputedStyle {
                 }
                 None => {
                     e.insert(AnimatedTransform {
-                        state: TransitionState {
-                            transition: transition.clone(),
-                            clock: 0.,
-                        },
+                        state: TransitionState::new(transition.clone()),
                         origin: transform,
                         target: transform,
                     });
@@ -420,5 +883,136 @@ impl Command for UpdateComputedStyle {
                 }
             }
         }
+
+        sync_background_layers(world, self.entity, &self.computed.background_layers);
+    }
+}
+
+/// Command that updates the styles of several entities, applying each [`UpdateComputedStyle`]
+/// in order.
+pub(crate) struct UpdateComputedStyles(pub(crate) Vec<UpdateComputedStyle>);
+
+impl Command for UpdateComputedStyles {
+    fn apply(self, world: &mut World) {
+        for update in self.0 {
+            update.apply(world);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use super::*;
+
+    #[test]
+    fn test_transform_origin() {
+        let size = Vec2::new(100., 100.);
+        let mut computed = ComputedStyle::new();
+        computed.rotation = Some(FRAC_PI_2);
+        let center = computed.transform(size);
+        assert!(center.translation.abs_diff_eq(Vec3::ZERO, 1e-4));
+
+        computed.transform_origin = Some((Val::Px(0.), Val::Px(0.)));
+        let top_left = computed.transform(size);
+        assert_eq!(top_left.rotation, center.rotation);
+        assert!(top_left
+            .translation
+            .abs_diff_eq(Vec3::new(-100., 0., 0.), 1e-4));
+
+        // Percentages resolve against node size.
+        computed.transform_origin = Some((Val::Percent(0.), Val::Percent(0.)));
+        assert!(computed
+            .transform(size)
+            .translation
+            .abs_diff_eq(top_left.translation, 1e-4));
+    }
+
+    #[test]
+    fn test_transform_origin_composed() {
+        let size = Vec2::new(100., 50.);
+        let mut computed = ComputedStyle::new();
+        computed.scale_x = Some(2.);
+        computed.scale_y = Some(2.);
+        computed.translation = Some(Vec3::new(10., 0., 0.));
+        computed.transform_origin = Some((Val::Percent(100.), Val::Percent(50.)));
+        // Scaling about the right edge shifts the node left by half its width.
+        let transform = computed.transform(size);
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(-40., 0., 0.), 1e-4));
+    }
+
+    #[test]
+    fn test_writing_mode_vertical() {
+        let mut world = World::new();
+        let footprint = VerticalFootprint {
+            size: Vec2::new(80., 20.),
+        };
+        let entity = world
+            .spawn((Style::default(), Transform::default(), footprint))
+            .id();
+        let mut computed = ComputedStyle::new();
+        computed.writing_mode = WritingMode::VerticalRl;
+        UpdateComputedStyle { entity, computed }.apply(&mut world);
+
+        // The label is rotated a quarter turn.
+        let transform = world.get::<Transform>(entity).unwrap();
+        assert!(transform
+            .rotation
+            .abs_diff_eq(Quat::from_rotation_z(FRAC_PI_2), 1e-4));
+
+        // The margins swap the width and height of its layout footprint.
+        let margin = world.get::<Style>(entity).unwrap().margin;
+        assert_eq!(margin.left, Val::Px(-30.));
+        assert_eq!(margin.right, Val::Px(-30.));
+        assert_eq!(margin.top, Val::Px(30.));
+        assert_eq!(margin.bottom, Val::Px(30.));
+    }
+
+    #[test]
+    fn test_image_fit_fill() {
+        let mut world = World::new();
+        let layout = Handle::<TextureAtlasLayout>::default();
+        let atlas = || TextureAtlas {
+            layout: layout.clone(),
+            index: 3,
+        };
+
+        // An atlas created to fit the image is removed when switching back to `Fill`.
+        let fitted = world
+            .spawn((
+                Style::default(),
+                ImageFit::Cover,
+                atlas(),
+                ImageFitLayout {
+                    rect: Rect::default(),
+                    layout: layout.clone(),
+                },
+            ))
+            .id();
+        UpdateComputedStyle {
+            entity: fitted,
+            computed: ComputedStyle::new(),
+        }
+        .apply(&mut world);
+        let e = world.entity(fitted);
+        assert!(!e.contains::<ImageFit>());
+        assert!(!e.contains::<ImageFitLayout>());
+        assert!(!e.contains::<TextureAtlas>());
+
+        // An atlas belonging to the user is preserved.
+        let user = world
+            .spawn((Style::default(), ImageFit::Cover, atlas()))
+            .id();
+        UpdateComputedStyle {
+            entity: user,
+            computed: ComputedStyle::new(),
+        }
+        .apply(&mut world);
+        let e = world.entity(user);
+        assert!(!e.contains::<ImageFit>());
+        assert_eq!(e.get::<TextureAtlas>().unwrap().index, 3);
     }
 }
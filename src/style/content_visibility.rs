@@ -0,0 +1,74 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::ScrollArea;
+
+/// Controls whether the contents of an element are styled when the element is off-screen,
+/// similar to the CSS `content-visibility` property.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ContentVisibility {
+    /// The contents of the element are always styled.
+    #[default]
+    Visible,
+
+    /// Styling of the element's descendants is skipped while the element is scrolled out of
+    /// view of its enclosing [`ScrollArea`]. The element itself is still styled, and keeps its
+    /// size. When it comes back into view, the descendants are restyled, so any changes that
+    /// happened while the element was off-screen are picked up.
+    Auto,
+}
+
+/// Distance, in logical pixels, outside of the visible area of a scroll area within which
+/// elements are considered on-screen, so that they are restyled before they become visible.
+pub(crate) const CONTENT_VISIBILITY_MARGIN: f32 = 200.;
+
+/// Tracks whether an element with [`ContentVisibility::Auto`] is currently off-screen.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct ContentVisibilityState {
+    pub(crate) offscreen: bool,
+}
+
+/// Returns true if `rect` is far enough outside of `viewport` to be considered off-screen.
+pub(crate) fn is_offscreen(rect: Rect, viewport: Rect) -> bool {
+    viewport
+        .inflate(CONTENT_VISIBILITY_MARGIN)
+        .intersect(rect)
+        .is_empty()
+}
+
+/// Update the off-screen state of elements with [`ContentVisibility::Auto`], based on the
+/// visible area of the nearest enclosing scroll area.
+pub(crate) fn update_content_visibility(
+    mut query: Query<(Entity, &mut ContentVisibilityState, &Node, &GlobalTransform)>,
+    query_areas: Query<(&Node, &GlobalTransform), With<ScrollArea>>,
+    query_parents: Query<&Parent>,
+) {
+    for (entity, mut state, node, gt) in query.iter_mut() {
+        let viewport = query_parents
+            .iter_ancestors(entity)
+            .find_map(|ancestor| query_areas.get(ancestor).ok())
+            .map(|(area_node, area_gt)| area_node.logical_rect(area_gt));
+        let offscreen = match viewport {
+            Some(viewport) => is_offscreen(node.logical_rect(gt), viewport),
+            None => false,
+        };
+        if state.offscreen != offscreen {
+            state.offscreen = offscreen;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_offscreen() {
+        let viewport = Rect::new(0., 0., 100., 100.);
+        assert!(!is_offscreen(Rect::new(0., 50., 100., 70.), viewport));
+        // Within the margin.
+        assert!(!is_offscreen(Rect::new(0., 250., 100., 270.), viewport));
+        assert!(is_offscreen(Rect::new(0., 400., 100., 420.), viewport));
+        assert!(is_offscreen(Rect::new(0., -400., 100., -380.), viewport));
+    }
+}
@@ -0,0 +1,131 @@
+use bevy::{input::ButtonInput, prelude::*};
+
+use super::computed::ComputedStyle;
+
+/// Default outline drawn around the focused element when focus is visible, that is, when
+/// focus was most recently changed via the keyboard. See [`QuillPlugin::focus_ring`].
+///
+/// [`QuillPlugin::focus_ring`]: crate::QuillPlugin::focus_ring
+#[derive(Debug, Clone, PartialEq)]
+pub struct FocusRing {
+    /// Color of the ring.
+    pub color: Color,
+    /// Thickness of the ring.
+    pub width: Val,
+    /// Gap between the ring and the element's border.
+    pub offset: Val,
+}
+
+impl Default for FocusRing {
+    fn default() -> Self {
+        Self {
+            color: Color::rgb(0.3, 0.5, 1.0),
+            width: Val::Px(2.),
+            offset: Val::Px(1.),
+        }
+    }
+}
+
+impl FocusRing {
+    /// Apply the focus ring to a computed style, unless the style already defines an outline.
+    /// If the element has an accent color, the ring uses it instead of the ring's own color.
+    pub(crate) fn apply_to(&self, computed: &mut ComputedStyle) {
+        if computed.outline_color.is_none() {
+            computed.outline_color = Some(computed.accent_color.unwrap_or(self.color));
+            computed.outline_width = self.width;
+            computed.outline_offset = self.offset;
+        }
+    }
+}
+
+/// Resource which indicates whether keyboard focus should be shown. This becomes true when
+/// the user presses a key, and false when the user presses a mouse button, mirroring the
+/// browser heuristic for `:focus-visible`.
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq)]
+pub struct FocusVisible(pub bool);
+
+/// System which updates [`FocusVisible`] based on the most recent input device.
+pub(crate) fn update_focus_visible(
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    mut visible: ResMut<FocusVisible>,
+) {
+    if mouse.get_just_pressed().next().is_some() {
+        visible.set_if_neq(FocusVisible(false));
+    } else if keys.get_just_pressed().next().is_some() {
+        visible.set_if_neq(FocusVisible(true));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::system::SystemState, utils::HashMap};
+
+    use super::*;
+    use crate::{
+        style::background_layers::BackgroundLayerNode, ElementAttributes, ElementClasses,
+        LogicalChildren, PressedEntities, SelectorMatcher,
+    };
+
+    /// Compute the style for `entity`, which has focus, the same way `update_styles` does.
+    fn compute(world: &mut World, entity: Entity, ring: &FocusRing) -> ComputedStyle {
+        #[allow(clippy::type_complexity)]
+        let mut state: SystemState<(
+            Query<Ref<ElementClasses>>,
+            Query<Ref<ElementAttributes>>,
+            Query<&Parent, (With<Node>, With<Visibility>)>,
+            Query<&Children, (With<Node>, With<Visibility>)>,
+            Query<&LogicalChildren>,
+            Query<(), With<BackgroundLayerNode>>,
+            Res<FocusVisible>,
+        )> = SystemState::new(world);
+        let (classes, attributes, parents, children, logical, layers, visible) = state.get(world);
+        let hover_map = HashMap::new();
+        let pressed = PressedEntities::default();
+        let matcher = SelectorMatcher::new(
+            &classes,
+            &attributes,
+            &parents,
+            &children,
+            &logical,
+            &layers,
+            &hover_map,
+            Some(entity),
+            visible.0,
+            &pressed,
+        );
+        let mut computed = ComputedStyle::new();
+        if matcher.is_focus_visible(&entity) {
+            ring.apply_to(&mut computed);
+        }
+        computed
+    }
+
+    #[test]
+    fn test_focus_ring() {
+        let mut app = App::new();
+        app.init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .init_resource::<FocusVisible>()
+            .add_systems(Update, update_focus_visible);
+        let entity = app.world.spawn(NodeBundle::default()).id();
+        let ring = FocusRing::default();
+
+        // Keyboard focus shows the ring.
+        app.world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Tab);
+        app.update();
+        let computed = compute(&mut app.world, entity, &ring);
+        assert_eq!(computed.outline_color, Some(ring.color));
+
+        // Mouse focus hides it.
+        app.world.resource_mut::<ButtonInput<KeyCode>>().clear();
+        app.world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        app.update();
+        let computed = compute(&mut app.world, entity, &ring);
+        assert_eq!(computed.outline_color, None);
+    }
+}
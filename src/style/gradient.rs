@@ -0,0 +1,255 @@
+use bevy::{
+    prelude::*,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+        texture::ImageSampler,
+    },
+    utils::HashMap,
+};
+use bevy_color::{LinearRgba, Mix, Oklaba, SRgba};
+
+/// Width and height of generated gradient textures. The texture is stretched to fit the node,
+/// and sampled with linear filtering, so it doesn't need to be large.
+const GRADIENT_SIZE: u32 = 64;
+
+/// A linear gradient, used as a background by the `background_gradient` style property.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearGradient {
+    /// Direction of the gradient line in degrees, as in CSS: 0 points up, 90 points right.
+    pub angle: f32,
+    /// Color stops, as pairs of position along the gradient line and color.
+    pub stops: Vec<(Val, LinearRgba)>,
+}
+
+impl LinearGradient {
+    /// Resolve the stop positions to fractions of the gradient line, following the CSS rules
+    /// for stops whose position is missing or out of order. Only percentages are meaningful,
+    /// since the texture doesn't know the size of the node; stops with any other kind of
+    /// position are treated as unpositioned, and spaced evenly between their neighbors.
+    fn resolve_stops(&self) -> Vec<(f32, Oklaba)> {
+        let count = self.stops.len();
+        let mut positions: Vec<Option<f32>> = self
+            .stops
+            .iter()
+            .map(|(pos, _)| match pos {
+                Val::Percent(pct) => Some(pct / 100.),
+                _ => None,
+            })
+            .collect();
+        if let Some(first) = positions.first_mut() {
+            first.get_or_insert(0.);
+        }
+        if let Some(last) = positions.last_mut() {
+            last.get_or_insert(1.);
+        }
+
+        // Stops may not go backwards.
+        let mut max = f32::MIN;
+        for pos in positions.iter_mut().flatten() {
+            max = max.max(*pos);
+            *pos = max;
+        }
+
+        // Distribute unpositioned stops evenly between the positioned stops on either side.
+        let mut start = 0;
+        for end in 1..count {
+            if let Some(end_pos) = positions[end] {
+                let start_pos = positions[start].unwrap();
+                for i in start + 1..end {
+                    let t = (i - start) as f32 / (end - start) as f32;
+                    positions[i] = Some(start_pos + (end_pos - start_pos) * t);
+                }
+                start = end;
+            }
+        }
+
+        positions
+            .into_iter()
+            .zip(self.stops.iter())
+            .map(|(pos, (_, color))| (pos.unwrap(), Oklaba::from(*color)))
+            .collect()
+    }
+
+    /// Color at position `t` along the gradient line. Colors are interpolated in Oklab space,
+    /// which avoids the muddy midpoints of interpolating in RGB.
+    fn color_at(stops: &[(f32, Oklaba)], t: f32) -> Oklaba {
+        let Some(index) = stops.iter().position(|(pos, _)| *pos > t) else {
+            return stops
+                .last()
+                .map_or(Oklaba::new(0., 0., 0., 0.), |(_, c)| *c);
+        };
+        if index == 0 {
+            return stops[0].1;
+        }
+        let (p0, c0) = stops[index - 1];
+        let (p1, c1) = stops[index];
+        c0.mix(&c1, (t - p0) / (p1 - p0))
+    }
+
+    /// Render the gradient into a square texture.
+    fn to_image(&self) -> Image {
+        let stops = self.resolve_stops();
+        let (sin, cos) = self.angle.to_radians().sin_cos();
+        // Length of the gradient line, which is long enough that the corners of the box
+        // receive the first and last colors.
+        let length = sin.abs() + cos.abs();
+        let mut data = Vec::with_capacity((GRADIENT_SIZE * GRADIENT_SIZE * 4) as usize);
+        for y in 0..GRADIENT_SIZE {
+            for x in 0..GRADIENT_SIZE {
+                let px = (x as f32 + 0.5) / GRADIENT_SIZE as f32 - 0.5;
+                let py = (y as f32 + 0.5) / GRADIENT_SIZE as f32 - 0.5;
+                // Image rows go down, so "up" is negative y.
+                let t = (px * sin - py * cos) / length + 0.5;
+                let color = SRgba::from(Self::color_at(&stops, t));
+                data.extend(
+                    [color.red, color.green, color.blue, color.alpha]
+                        .map(|c| (c.clamp(0., 1.) * 255.).round() as u8),
+                );
+            }
+        }
+        let mut image = Image::new(
+            Extent3d {
+                width: GRADIENT_SIZE,
+                height: GRADIENT_SIZE,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::RENDER_WORLD,
+        );
+        image.sampler = ImageSampler::linear();
+        image
+    }
+
+    /// Hashable key which identifies gradients with identical parameters.
+    fn key(&self) -> Vec<u32> {
+        let mut key = vec![self.angle.to_bits()];
+        for (pos, color) in self.stops.iter() {
+            key.push(match pos {
+                Val::Percent(pct) => pct.to_bits(),
+                _ => f32::NAN.to_bits(),
+            });
+            key.extend([color.red, color.green, color.blue, color.alpha].map(f32::to_bits));
+        }
+        key
+    }
+}
+
+/// Cache of generated gradient textures, so that elements with identical gradients share a
+/// single image. Entries are never evicted.
+#[derive(Resource, Default)]
+pub(crate) struct GradientCache(HashMap<Vec<u32>, Handle<Image>>);
+
+/// Return the texture for a gradient, generating it if it isn't in the cache.
+pub(crate) fn gradient_texture(world: &mut World, gradient: &LinearGradient) -> Handle<Image> {
+    let key = gradient.key();
+    if let Some(handle) = world
+        .get_resource::<GradientCache>()
+        .and_then(|cache| cache.0.get(&key))
+    {
+        return handle.clone();
+    }
+    let handle = world
+        .resource_mut::<Assets<Image>>()
+        .add(gradient.to_image());
+    world
+        .get_resource_or_insert_with(GradientCache::default)
+        .0
+        .insert(key, handle.clone());
+    handle
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::Command;
+
+    use super::*;
+    use crate::{compute_style, ElementStyles, StyleHandle, UpdateComputedStyle};
+
+    fn gradient(angle: f32, stops: &[(Val, SRgba)]) -> LinearGradient {
+        LinearGradient {
+            angle,
+            stops: stops.iter().map(|(v, c)| (*v, (*c).into())).collect(),
+        }
+    }
+
+    fn pixel(image: &Image, x: u32, y: u32) -> [u8; 4] {
+        let i = ((y * GRADIENT_SIZE + x) * 4) as usize;
+        image.data[i..i + 4].try_into().unwrap()
+    }
+
+    #[test]
+    fn test_resolve_stops() {
+        let g = gradient(
+            90.,
+            &[
+                (Val::Auto, SRgba::RED),
+                (Val::Auto, SRgba::GREEN),
+                (Val::Percent(60.), SRgba::BLUE),
+                (Val::Percent(40.), SRgba::WHITE),
+                (Val::Auto, SRgba::BLACK),
+            ],
+        );
+        let positions: Vec<f32> = g.resolve_stops().iter().map(|(p, _)| *p).collect();
+        assert_eq!(positions, vec![0., 0.3, 0.6, 0.6, 1.]);
+    }
+
+    #[test]
+    fn test_gradient_image() {
+        // Left to right, black to white.
+        let image =
+            gradient(90., &[(Val::Auto, SRgba::BLACK), (Val::Auto, SRgba::WHITE)]).to_image();
+        let left = pixel(&image, 0, 0);
+        let right = pixel(&image, GRADIENT_SIZE - 1, 0);
+        assert!(left[0] < 8 && left[3] == 255);
+        assert!(right[0] > 247);
+        // Columns are uniform.
+        assert_eq!(pixel(&image, 10, 0), pixel(&image, 10, GRADIENT_SIZE - 1));
+
+        // Bottom to top.
+        let image =
+            gradient(0., &[(Val::Auto, SRgba::BLACK), (Val::Auto, SRgba::WHITE)]).to_image();
+        assert!(pixel(&image, 0, 0)[0] > 247);
+        assert!(pixel(&image, 0, GRADIENT_SIZE - 1)[0] < 8);
+    }
+
+    #[test]
+    fn test_gradient_cache() {
+        let mut world = World::new();
+        world.init_resource::<Assets<Image>>();
+        let a = gradient(45., &[(Val::Auto, SRgba::RED), (Val::Auto, SRgba::BLUE)]);
+        let b = gradient(45., &[(Val::Auto, SRgba::RED), (Val::Auto, SRgba::BLUE)]);
+        let c = gradient(90., &[(Val::Auto, SRgba::RED), (Val::Auto, SRgba::BLUE)]);
+        let ha = gradient_texture(&mut world, &a);
+        assert_eq!(gradient_texture(&mut world, &b), ha);
+        assert_ne!(gradient_texture(&mut world, &c), ha);
+        assert_eq!(world.resource::<Assets<Image>>().len(), 2);
+    }
+
+    #[test]
+    fn test_gradient_background() {
+        let mut world = World::new();
+        world.init_resource::<Assets<Image>>();
+        let style = StyleHandle::build(|ss| {
+            ss.background_gradient(180., [(Val::Auto, SRgba::WHITE), (Val::Auto, SRgba::BLACK)])
+        });
+        let panels: Vec<Entity> = (0..2)
+            .map(|_| {
+                world
+                    .spawn((NodeBundle::default(), ElementStyles::new(&[style.clone()])))
+                    .id()
+            })
+            .collect();
+        for entity in panels.iter().copied() {
+            let computed = compute_style(&mut world, entity);
+            UpdateComputedStyle { entity, computed }.apply(&mut world);
+        }
+
+        // Both panels share the same texture.
+        let texture = &world.get::<UiImage>(panels[0]).unwrap().texture;
+        assert_eq!(&world.get::<UiImage>(panels[1]).unwrap().texture, texture);
+        assert_eq!(world.resource::<Assets<Image>>().len(), 1);
+    }
+}
@@ -0,0 +1,110 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+use bevy_mod_picking::backend::prelude::*;
+
+/// Component which extends the pickable area of a UI node beyond its visual bounds, by the
+/// given number of logical pixels on each side. Does not affect layout or rendering.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct HitSlop(pub f32);
+
+/// Given a point, and a list of candidate nodes with their visual bounds and slop, return
+/// the node whose slop region contains the point. Points inside a node's visual bounds are
+/// ignored, since those are handled by the regular UI picking backend. If the slop regions of
+/// several nodes overlap, the node whose center is nearest to the point wins.
+pub(crate) fn pick_slop(
+    point: Vec2,
+    candidates: impl Iterator<Item = (Entity, Rect, f32)>,
+) -> Option<Entity> {
+    candidates
+        .filter(|(_, rect, slop)| !rect.contains(point) && rect.inset(*slop).contains(point))
+        .map(|(entity, rect, _)| (entity, rect.center().distance_squared(point)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(entity, _)| entity)
+}
+
+/// Picking backend which reports hits within the slop region of nodes which have a
+/// [`HitSlop`] component.
+pub(crate) fn hit_slop_backend(
+    pointers: Query<(&PointerId, &PointerLocation)>,
+    cameras: Query<(Entity, &Camera)>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    nodes: Query<(Entity, &Node, &GlobalTransform, &HitSlop, &ViewVisibility)>,
+    mut output: EventWriter<PointerHits>,
+) {
+    for (pointer, location) in pointers.iter() {
+        let Some(location) = location.location() else {
+            continue;
+        };
+        let Some((camera_entity, camera)) = cameras
+            .iter()
+            .filter(|(_, camera)| {
+                camera.is_active
+                    && camera.target.normalize(primary_window.get_single().ok())
+                        == Some(location.target.clone())
+            })
+            .max_by_key(|(_, camera)| camera.order)
+        else {
+            continue;
+        };
+
+        let candidates = nodes
+            .iter()
+            .filter(|(.., visibility)| visibility.get())
+            .map(|(entity, node, transform, slop, _)| {
+                (entity, node.logical_rect(transform), slop.0)
+            });
+        if let Some(entity) = pick_slop(location.position, candidates) {
+            // Slop hits are placed behind all regular UI hits, so that an element's visual
+            // bounds always take priority over a neighbor's slop region.
+            let hit = HitData::new(camera_entity, f32::MAX, None, None);
+            output.send(PointerHits::new(
+                *pointer,
+                vec![(entity, hit)],
+                camera.order as f32 + 0.5,
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn button(index: u32, min_x: f32) -> (Entity, Rect, f32) {
+        (
+            Entity::from_raw(index),
+            Rect::new(min_x, 0., min_x + 20., 20.),
+            10.,
+        )
+    }
+
+    #[test]
+    fn test_slop_hit() {
+        let buttons = [button(0, 0.)];
+        // Just outside the visual bounds, within the slop.
+        assert_eq!(
+            pick_slop(Vec2::new(25., 10.), buttons.into_iter()),
+            Some(Entity::from_raw(0))
+        );
+        assert_eq!(
+            pick_slop(Vec2::new(10., -5.), buttons.into_iter()),
+            Some(Entity::from_raw(0))
+        );
+        // Outside the slop.
+        assert_eq!(pick_slop(Vec2::new(35., 10.), buttons.into_iter()), None);
+        // Inside the visual bounds is left to the UI backend.
+        assert_eq!(pick_slop(Vec2::new(10., 10.), buttons.into_iter()), None);
+    }
+
+    #[test]
+    fn test_slop_overlap() {
+        let buttons = [button(0, 0.), button(1, 30.)];
+        assert_eq!(
+            pick_slop(Vec2::new(24., 10.), buttons.into_iter()),
+            Some(Entity::from_raw(0))
+        );
+        assert_eq!(
+            pick_slop(Vec2::new(26., 10.), buttons.into_iter()),
+            Some(Entity::from_raw(1))
+        );
+    }
+}
@@ -0,0 +1,328 @@
+use bevy::{ecs::event::ManualEventReader, prelude::*, render::render_resource::TextureFormat};
+use bevy_mod_picking::backend::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Controls which part of an element responds to pointer events.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HitTest {
+    /// The entire bounding box of the element is hit-testable.
+    #[default]
+    BoundingBox,
+
+    /// Only the visible (non-transparent) pixels of the element's background image are
+    /// hit-testable. If the image has not loaded yet, or its pixel format can't be sampled,
+    /// the bounding box is used instead.
+    Visible,
+}
+
+/// Alpha values at or below this threshold are considered transparent.
+const ALPHA_THRESHOLD: u8 = 0;
+
+/// Return the alpha value of the pixel at normalized coordinates `uv` within the image, or
+/// `None` if the image's pixel format is not supported.
+pub(crate) fn image_alpha_at(image: &Image, uv: Vec2) -> Option<u8> {
+    let bytes_per_pixel = match image.texture_descriptor.format {
+        TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => 4,
+        _ => return None,
+    };
+    let (width, height) = (image.width(), image.height());
+    if width == 0 || height == 0 {
+        return None;
+    }
+    let x = ((uv.x.clamp(0., 1.) * width as f32) as u32).min(width - 1);
+    let y = ((uv.y.clamp(0., 1.) * height as f32) as u32).min(height - 1);
+    let offset = ((y * width + x) * bytes_per_pixel + 3) as usize;
+    image.data.get(offset).copied()
+}
+
+/// Returns false if the point lies over a transparent pixel of the image. `source` is the
+/// region of the image, in texels, which is mapped onto the node, as set by a texture atlas;
+/// `None` means the whole image. Points outside of the node bounds, and images which can't be
+/// sampled, count as hits.
+pub(crate) fn is_visible_hit(
+    image: &Image,
+    source: Option<Rect>,
+    rect: Rect,
+    flip_x: bool,
+    flip_y: bool,
+    point: Vec2,
+) -> bool {
+    let size = rect.size();
+    if size.x <= 0. || size.y <= 0. {
+        return true;
+    }
+    let mut uv = (point - rect.min) / size;
+    if flip_x {
+        uv.x = 1. - uv.x;
+    }
+    if flip_y {
+        uv.y = 1. - uv.y;
+    }
+    if let Some(source) = source {
+        uv = (source.min + uv * source.size()) / image.size_f32();
+    }
+    match image_alpha_at(image, uv) {
+        Some(alpha) => alpha > ALPHA_THRESHOLD,
+        None => true,
+    }
+}
+
+/// System which removes hits on transparent pixels of elements with [`HitTest::Visible`]
+/// from the hits reported by the picking backends. Only hits which arrived since the previous
+/// run are examined, and the event queue is only rewritten if one of them was removed.
+pub(crate) fn filter_visible_hits(
+    mut reader: Local<ManualEventReader<PointerHits>>,
+    mut hits: ResMut<Events<PointerHits>>,
+    pointers: Query<(&PointerId, &PointerLocation)>,
+    nodes: Query<(
+        &HitTest,
+        &Node,
+        &GlobalTransform,
+        &UiImage,
+        Option<&TextureAtlas>,
+    )>,
+    images: Res<Assets<Image>>,
+    layouts: Res<Assets<TextureAtlasLayout>>,
+) {
+    let new_hits: Vec<PointerHits> = reader.read(&hits).cloned().collect();
+    if nodes.is_empty() || new_hits.is_empty() {
+        return;
+    }
+    let mut removed = false;
+    let filtered: Vec<PointerHits> = new_hits
+        .into_iter()
+        .map(|mut pointer_hits| {
+            let location = pointers
+                .iter()
+                .find(|(id, _)| **id == pointer_hits.pointer)
+                .and_then(|(_, location)| location.location());
+            if let Some(location) = location {
+                let count = pointer_hits.picks.len();
+                pointer_hits.picks.retain(|(entity, _)| {
+                    let Ok((hit_test, node, transform, image, atlas)) = nodes.get(*entity) else {
+                        return true;
+                    };
+                    if *hit_test != HitTest::Visible {
+                        return true;
+                    }
+                    // Image not loaded yet: fall back to the bounding box.
+                    let Some(texture) = images.get(&image.texture) else {
+                        return true;
+                    };
+                    let source = atlas.and_then(|atlas| {
+                        layouts
+                            .get(&atlas.layout)
+                            .and_then(|layout| layout.textures.get(atlas.index))
+                            .copied()
+                    });
+                    is_visible_hit(
+                        texture,
+                        source,
+                        node.logical_rect(transform),
+                        image.flip_x,
+                        image.flip_y,
+                        location.position,
+                    )
+                });
+                removed |= pointer_hits.picks.len() != count;
+            }
+            pointer_hits
+        })
+        .collect();
+    if !removed {
+        return;
+    }
+    // Events can't be edited in place, so replace the queue with the filtered hits. Hits from
+    // the previous frame have already been seen by the focus systems, and are dropped.
+    hits.clear();
+    hits.send_batch(
+        filtered
+            .into_iter()
+            .filter(|pointer_hits| !pointer_hits.picks.is_empty()),
+    );
+    // Don't examine the filtered hits again on the next run.
+    reader.clear(&hits);
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        reflect::Struct,
+        render::{
+            camera::NormalizedRenderTarget,
+            render_asset::RenderAssetUsages,
+            render_resource::{Extent3d, TextureDimension},
+        },
+    };
+
+    use bevy_mod_picking::pointer::Location;
+
+    use super::*;
+
+    /// A 2x1 image whose left pixel is transparent and right pixel is opaque.
+    fn half_transparent() -> Image {
+        Image::new(
+            Extent3d {
+                width: 2,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            vec![255, 255, 255, 0, 255, 255, 255, 255],
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        )
+    }
+
+    #[test]
+    fn test_alpha_at() {
+        let image = half_transparent();
+        assert_eq!(image_alpha_at(&image, Vec2::new(0.25, 0.5)), Some(0));
+        assert_eq!(image_alpha_at(&image, Vec2::new(0.75, 0.5)), Some(255));
+        assert_eq!(image_alpha_at(&image, Vec2::new(1., 1.)), Some(255));
+    }
+
+    #[test]
+    fn test_visible_hit() {
+        let image = half_transparent();
+        let rect = Rect::new(100., 100., 140., 120.);
+        // Transparent region: no hit.
+        assert!(!is_visible_hit(
+            &image,
+            None,
+            rect,
+            false,
+            false,
+            Vec2::new(110., 110.)
+        ));
+        // Opaque region: hit.
+        assert!(is_visible_hit(
+            &image,
+            None,
+            rect,
+            false,
+            false,
+            Vec2::new(130., 110.)
+        ));
+        // Flipped horizontally, the transparent pixel is on the right.
+        assert!(is_visible_hit(
+            &image,
+            None,
+            rect,
+            true,
+            false,
+            Vec2::new(110., 110.)
+        ));
+        assert!(!is_visible_hit(
+            &image,
+            None,
+            rect,
+            true,
+            false,
+            Vec2::new(130., 110.)
+        ));
+    }
+
+    #[test]
+    fn test_unsupported_format() {
+        let image = Image::new_fill(
+            Extent3d::default(),
+            TextureDimension::D2,
+            &[0; 16],
+            TextureFormat::Rgba32Float,
+            RenderAssetUsages::default(),
+        );
+        let rect = Rect::new(0., 0., 10., 10.);
+        assert!(is_visible_hit(
+            &image,
+            None,
+            rect,
+            false,
+            false,
+            Vec2::new(5., 5.)
+        ));
+    }
+
+    #[test]
+    fn test_atlas_source() {
+        let image = half_transparent();
+        let rect = Rect::new(0., 0., 10., 10.);
+        // With only the opaque right-hand pixel mapped onto the node, the whole node is a hit.
+        let right = Some(Rect::new(1., 0., 2., 1.));
+        assert!(is_visible_hit(
+            &image,
+            right,
+            rect,
+            false,
+            false,
+            Vec2::new(1., 5.)
+        ));
+        // Cropping to the left pixel makes the whole node transparent.
+        let left = Some(Rect::new(0., 0., 1., 1.));
+        assert!(!is_visible_hit(
+            &image,
+            left,
+            rect,
+            false,
+            false,
+            Vec2::new(9., 5.)
+        ));
+    }
+
+    #[test]
+    fn test_filter_does_not_resend() {
+        let mut app = App::new();
+        app.add_event::<PointerHits>()
+            .init_resource::<Assets<Image>>()
+            .init_resource::<Assets<TextureAtlasLayout>>()
+            .add_systems(PreUpdate, filter_visible_hits);
+        let image = app
+            .world
+            .resource_mut::<Assets<Image>>()
+            .add(half_transparent());
+        // Layout normally sets the node's size, which is otherwise read-only.
+        let mut node = Node::default();
+        *node
+            .field_mut("calculated_size")
+            .and_then(|size| size.downcast_mut::<Vec2>())
+            .unwrap() = Vec2::new(20., 10.);
+        let transparent = app
+            .world
+            .spawn((
+                HitTest::Visible,
+                node,
+                GlobalTransform::from_translation(Vec3::new(10., 5., 0.)),
+                UiImage::new(image),
+            ))
+            .id();
+        let other = app.world.spawn_empty().id();
+        // The pointer is over the transparent left half of the node.
+        app.world.spawn((
+            PointerId::Mouse,
+            PointerLocation::new(Location {
+                target: NormalizedRenderTarget::Image(Handle::default()),
+                position: Vec2::new(5., 5.),
+            }),
+        ));
+
+        let hit = HitData::new(other, 0., None, None);
+        app.world.send_event(PointerHits::new(
+            PointerId::Mouse,
+            vec![(transparent, hit.clone()), (other, hit)],
+            0.,
+        ));
+        let mut picks = Vec::new();
+        for _ in 0..3 {
+            app.update();
+            let hits = app.world.resource::<Events<PointerHits>>();
+            picks.push(
+                hits.iter_current_update_events()
+                    .map(|hits| hits.picks.len())
+                    .sum::<usize>(),
+            );
+        }
+        // The transparent node is filtered out once, and the hits aren't sent again.
+        assert_eq!(picks, vec![1, 0, 0]);
+        assert!(app.world.resource::<Events<PointerHits>>().is_empty());
+    }
+}
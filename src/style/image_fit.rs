@@ -0,0 +1,206 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Controls how a background image is fitted to the bounds of its node, similar to the CSS
+/// `object-fit` property.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum ImageFit {
+    /// Stretch the image to fill the node, ignoring aspect ratio.
+    #[default]
+    Fill,
+
+    /// Scale the image to fit within the node, preserving aspect ratio. The uncovered area
+    /// is filled according to the image sampler's address mode, so images which are meant to
+    /// be letterboxed should be loaded with `ImageAddressMode::ClampToBorder`.
+    Contain,
+
+    /// Scale the image to cover the entire node, preserving aspect ratio. The parts of the
+    /// image which fall outside of the node are cropped.
+    Cover,
+}
+
+impl ImageFit {
+    /// Compute the region of the source image, in texel coordinates, which should be mapped
+    /// onto a node of the given size. For `Contain`, the region may extend beyond the bounds
+    /// of the image.
+    pub fn source_rect(&self, image_size: Vec2, node_size: Vec2) -> Rect {
+        let full = Rect::from_corners(Vec2::ZERO, image_size);
+        if image_size.x <= 0. || image_size.y <= 0. || node_size.x <= 0. || node_size.y <= 0. {
+            return full;
+        }
+        let node_aspect = node_size.x / node_size.y;
+        let image_aspect = image_size.x / image_size.y;
+        let size = match self {
+            ImageFit::Fill => return full,
+            ImageFit::Contain if image_aspect > node_aspect => {
+                Vec2::new(image_size.x, image_size.x / node_aspect)
+            }
+            ImageFit::Contain => Vec2::new(image_size.y * node_aspect, image_size.y),
+            ImageFit::Cover if image_aspect > node_aspect => {
+                Vec2::new(image_size.y * node_aspect, image_size.y)
+            }
+            ImageFit::Cover => Vec2::new(image_size.x, image_size.x / node_aspect),
+        };
+        Rect::from_center_size(image_size * 0.5, size)
+    }
+}
+
+/// Tracks the texture atlas layout which was generated to fit an image to its node. This also
+/// marks the node's [`TextureAtlas`] as belonging to [`update_image_fit`], so that it can be
+/// removed when the fit changes back to [`ImageFit::Fill`].
+#[derive(Component)]
+#[doc(hidden)]
+pub struct ImageFitLayout {
+    pub(crate) rect: Rect,
+    pub(crate) layout: Handle<TextureAtlasLayout>,
+}
+
+/// System which recomputes the visible region of fitted images whenever the node is
+/// resized or the image changes.
+pub(crate) fn update_image_fit(
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &Node,
+        &UiImage,
+        &ImageFit,
+        Option<&mut ImageFitLayout>,
+    )>,
+    images: Res<Assets<Image>>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    for (entity, node, image, fit, fit_layout) in query.iter_mut() {
+        // Image may not be loaded yet.
+        let Some(texture) = images.get(&image.texture) else {
+            continue;
+        };
+        let image_size = texture.size_f32();
+        let rect = fit.source_rect(image_size, node.size());
+        match fit_layout {
+            Some(mut fit_layout) => {
+                if fit_layout.rect != rect {
+                    fit_layout.rect = rect;
+                    if let Some(layout) = layouts.get_mut(&fit_layout.layout) {
+                        layout.size = image_size;
+                        layout.textures[0] = rect;
+                    }
+                }
+            }
+            None => {
+                let mut layout = TextureAtlasLayout::new_empty(image_size);
+                layout.add_texture(rect);
+                let layout = layouts.add(layout);
+                commands.entity(entity).insert((
+                    ImageFitLayout {
+                        rect,
+                        layout: layout.clone(),
+                    },
+                    TextureAtlas { layout, index: 0 },
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        reflect::Struct,
+        render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+    };
+
+    use super::*;
+
+    /// Run `update_image_fit` for a 200x100 image in a 50x50 node, returning the texture
+    /// region mapped onto the node.
+    fn fitted_rect(fit: ImageFit) -> Rect {
+        let mut app = App::new();
+        app.init_resource::<Assets<Image>>()
+            .init_resource::<Assets<TextureAtlasLayout>>()
+            .add_systems(Update, update_image_fit);
+        let image = app
+            .world
+            .resource_mut::<Assets<Image>>()
+            .add(Image::new_fill(
+                Extent3d {
+                    width: 200,
+                    height: 100,
+                    depth_or_array_layers: 1,
+                },
+                TextureDimension::D2,
+                &[0, 0, 0, 255],
+                TextureFormat::Rgba8Unorm,
+                default(),
+            ));
+        // Layout normally sets the node's size, which is otherwise read-only.
+        let mut node = Node::default();
+        *node
+            .field_mut("calculated_size")
+            .and_then(|size| size.downcast_mut::<Vec2>())
+            .unwrap() = Vec2::new(50., 50.);
+        let entity = app.world.spawn((node, UiImage::new(image), fit)).id();
+        app.update();
+
+        let e = app.world.entity(entity);
+        let atlas = e.get::<TextureAtlas>().unwrap();
+        assert_eq!(atlas.index, 0);
+        assert_eq!(e.get::<ImageFitLayout>().unwrap().layout, atlas.layout);
+        let layouts = app.world.resource::<Assets<TextureAtlasLayout>>();
+        let layout = layouts.get(&atlas.layout).unwrap();
+        assert_eq!(layout.size, Vec2::new(200., 100.));
+        layout.textures[0]
+    }
+
+    #[test]
+    fn test_update_fill() {
+        assert_eq!(fitted_rect(ImageFit::Fill), Rect::new(0., 0., 200., 100.));
+    }
+
+    #[test]
+    fn test_update_contain() {
+        assert_eq!(
+            fitted_rect(ImageFit::Contain),
+            Rect::new(0., -50., 200., 150.)
+        );
+    }
+
+    #[test]
+    fn test_update_cover() {
+        assert_eq!(fitted_rect(ImageFit::Cover), Rect::new(50., 0., 150., 100.));
+    }
+
+    #[test]
+    fn test_fill() {
+        let rect = ImageFit::Fill.source_rect(Vec2::new(200., 100.), Vec2::new(50., 50.));
+        assert_eq!(rect, Rect::new(0., 0., 200., 100.));
+    }
+
+    #[test]
+    fn test_contain() {
+        // Wide image in a square node: letterbox top and bottom.
+        let rect = ImageFit::Contain.source_rect(Vec2::new(200., 100.), Vec2::new(50., 50.));
+        assert_eq!(rect, Rect::new(0., -50., 200., 150.));
+
+        // Tall image in a square node: letterbox left and right.
+        let rect = ImageFit::Contain.source_rect(Vec2::new(100., 200.), Vec2::new(50., 50.));
+        assert_eq!(rect, Rect::new(-50., 0., 150., 200.));
+    }
+
+    #[test]
+    fn test_cover() {
+        // Wide image in a square node: crop left and right.
+        let rect = ImageFit::Cover.source_rect(Vec2::new(200., 100.), Vec2::new(50., 50.));
+        assert_eq!(rect, Rect::new(50., 0., 150., 100.));
+
+        // Tall image in a wide node: crop top and bottom.
+        let rect = ImageFit::Cover.source_rect(Vec2::new(100., 200.), Vec2::new(100., 50.));
+        assert_eq!(rect, Rect::new(0., 75., 100., 125.));
+    }
+
+    #[test]
+    fn test_degenerate() {
+        // Node not laid out yet.
+        let rect = ImageFit::Cover.source_rect(Vec2::new(100., 200.), Vec2::ZERO);
+        assert_eq!(rect, Rect::new(0., 0., 100., 200.));
+    }
+}
@@ -1,29 +1,88 @@
+mod attributes;
+pub(crate) mod background_layers;
+mod border_radius;
+mod box_shadow;
 mod builder;
 mod classes;
 mod computed;
+pub(crate) mod content_visibility;
+pub(crate) mod focus_ring;
+pub(crate) mod gradient;
+pub(crate) mod hit_slop;
+pub(crate) mod hit_test;
+pub(crate) mod image_fit;
+pub(crate) mod pressed;
+mod reactive_style;
+pub(crate) mod scrollbar;
+pub(crate) mod order;
 mod selector;
 mod selector_matcher;
+mod spin;
+pub(crate) mod style_asset;
+mod style_data;
 mod style_handle;
 mod style_props;
 mod style_tuple;
+mod text_input;
+mod text_spacing;
 mod transition;
 pub(crate) mod update;
+pub(crate) mod writing_mode;
 
+pub use attributes::ElementAttributes;
+pub use background_layers::BackgroundLayer;
+pub use background_layers::BackgroundLayers;
+pub use border_radius::BorderRadius;
+pub use box_shadow::BoxShadow;
+pub use box_shadow::ShadowStyle;
+pub use builder::AccentColor;
+pub use builder::CurrentColor;
+pub use builder::ElementState;
+pub use builder::OverflowParam;
+pub use builder::PositionParam;
+pub use builder::Sticky;
+pub use builder::ACCENT_COLOR;
+pub use builder::CURRENT_COLOR;
 pub use classes::ClassNames;
 pub use classes::ElementClasses;
 pub use computed::ComputedStyle;
 pub use computed::UpdateComputedStyle;
+pub use content_visibility::ContentVisibility;
+pub use focus_ring::FocusRing;
+pub use focus_ring::FocusVisible;
+pub use gradient::LinearGradient;
+pub use hit_slop::HitSlop;
+pub use hit_test::HitTest;
+pub use image_fit::ImageFit;
+pub use order::LogicalChildren;
+pub use order::VisualOrder;
+pub use pressed::PressedEntities;
+pub use reactive_style::ReactiveStyle;
+pub use reactive_style::ReactiveStylePlugin;
+pub use scrollbar::ScrollbarStyle;
 pub(crate) use selector::Selector;
 pub(crate) use selector_matcher::SelectorMatcher;
+pub use spin::animate_spin;
+pub use spin::Spin;
+pub use style_asset::ElementStyleAsset;
+pub use style_asset::StyleAsset;
+pub use style_asset::StyleAssetError;
+pub use style_asset::StyleAssetLoader;
 pub use style_handle::ElementStyles;
 pub use style_handle::StyleHandle;
+pub use style_props::CurrentColorTarget;
 pub use style_props::PointerEvents;
 pub use style_props::StyleProp;
 pub use style_tuple::StyleTuple;
+pub use text_input::TextInputStyle;
+pub use text_spacing::TextSpacing;
 pub use transition::animate_bg_colors;
 pub use transition::animate_border_colors;
 pub use transition::animate_layout;
+pub use transition::animate_opacity;
 pub use transition::animate_transforms;
 pub use transition::timing;
 pub use transition::Transition;
 pub use transition::TransitionProperty;
+pub use update::compute_style;
+pub use writing_mode::WritingMode;
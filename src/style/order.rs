@@ -0,0 +1,164 @@
+use bevy::{prelude::*, utils::HashMap, utils::HashSet};
+
+/// Component which controls the visual order of an element within its parent, similar to
+/// the CSS `order` property. Added by [`StyleBuilder::order`]. Children are laid out in
+/// ascending order, and elements with the same order (including those with no order, which
+/// is treated as zero) keep their logical order relative to each other.
+///
+/// [`StyleBuilder::order`]: crate::StyleBuilder::order
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct VisualOrder(pub i32);
+
+/// Component added to an element whose children have been visually reordered, which records
+/// the logical (view) order of the children. Logic which depends on document order, such as
+/// keyboard navigation, should use this rather than [`Children`] when it is present.
+#[derive(Component, Debug, Default, Clone)]
+pub struct LogicalChildren {
+    logical: Vec<Entity>,
+    visual: Vec<Entity>,
+}
+
+impl LogicalChildren {
+    /// The children of the element, in logical order.
+    pub fn get(&self) -> &[Entity] {
+        &self.logical
+    }
+
+    /// The logical order of `children`, the element's current [`Children`]. If the children
+    /// have changed since they were last sorted, then their current order is the logical one.
+    pub(crate) fn resolve<'a>(&'a self, children: &'a [Entity]) -> &'a [Entity] {
+        if children.iter().eq(self.visual.iter()) {
+            &self.logical
+        } else {
+            children
+        }
+    }
+}
+
+/// Sort `logical` by visual order, keeping ties in logical order.
+pub(crate) fn visual_order(logical: &[Entity], order: impl Fn(Entity) -> i32) -> Vec<Entity> {
+    let mut visual = logical.to_vec();
+    visual.sort_by_key(|e| order(*e));
+    visual
+}
+
+fn sort_children(children: &mut Mut<Children>, order: &[Entity]) {
+    if children.iter().eq(order.iter()) {
+        return;
+    }
+    let index: HashMap<Entity, usize> = order.iter().enumerate().map(|(i, e)| (*e, i)).collect();
+    children.sort_by_key(|e| index.get(e).copied().unwrap_or(usize::MAX));
+}
+
+/// System which reorders the [`Children`] of elements according to the [`VisualOrder`] of
+/// each child.
+pub(crate) fn update_visual_order(
+    mut commands: Commands,
+    query_changed: Query<&Parent, Changed<VisualOrder>>,
+    mut removed: RemovedComponents<VisualOrder>,
+    query_parents: Query<&Parent>,
+    mut query_children: Query<(Entity, &mut Children, Option<&mut LogicalChildren>)>,
+    query_order: Query<&VisualOrder>,
+) {
+    let mut parents: HashSet<Entity> = query_changed.iter().map(|p| p.get()).collect();
+    parents.extend(
+        removed
+            .read()
+            .filter_map(|e| query_parents.get(e).ok())
+            .map(|p| p.get()),
+    );
+    // Children which were re-assembled by the view need to be sorted again.
+    parents.extend(
+        query_children
+            .iter_mut()
+            .filter(|(_, children, logical)| logical.is_some() && children.is_changed())
+            .map(|(e, _, _)| e),
+    );
+
+    for parent in parents {
+        let Ok((_, mut children, logical)) = query_children.get_mut(parent) else {
+            continue;
+        };
+
+        // If the children are not as we left them, then they were changed by something else,
+        // and their current order is the new logical order.
+        let logical_order: Vec<Entity> = match logical {
+            Some(ref l) => l.resolve(&children).to_vec(),
+            None => children.to_vec(),
+        };
+
+        if !logical_order.iter().any(|e| query_order.contains(*e)) {
+            // No ordered children, so restore logical order.
+            if logical.is_some() {
+                sort_children(&mut children, &logical_order);
+                commands.entity(parent).remove::<LogicalChildren>();
+            }
+            continue;
+        }
+
+        let visual = visual_order(&logical_order, |e| {
+            query_order.get(e).map_or(0, |order| order.0)
+        });
+        sort_children(&mut children, &visual);
+        match logical {
+            Some(mut l) => {
+                l.logical = logical_order;
+                l.visual = visual;
+            }
+            None => {
+                commands.entity(parent).insert(LogicalChildren {
+                    logical: logical_order,
+                    visual,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    #[test]
+    fn test_visual_order() {
+        let mut world = World::new();
+        let a = world.spawn(VisualOrder(2)).id();
+        let b = world.spawn_empty().id();
+        let c = world.spawn(VisualOrder(-1)).id();
+        let d = world.spawn(VisualOrder(2)).id();
+        let parent = world.spawn_empty().push_children(&[a, b, c, d]).id();
+
+        world.run_system_once(update_visual_order);
+        // Ties keep their logical order.
+        assert_eq!(
+            world.get::<Children>(parent).unwrap().to_vec(),
+            vec![c, b, a, d]
+        );
+        // Logical order, as used for keyboard navigation, is unchanged.
+        assert_eq!(
+            world.get::<LogicalChildren>(parent).unwrap().get(),
+            &[a, b, c, d]
+        );
+
+        // Changing an order re-sorts from the logical order.
+        world.get_mut::<VisualOrder>(c).unwrap().0 = 3;
+        world.run_system_once(update_visual_order);
+        assert_eq!(
+            world.get::<Children>(parent).unwrap().to_vec(),
+            vec![b, a, d, c]
+        );
+
+        // Removing all orders restores the logical order.
+        for e in [a, c, d] {
+            world.entity_mut(e).remove::<VisualOrder>();
+        }
+        world.run_system_once(update_visual_order);
+        assert_eq!(
+            world.get::<Children>(parent).unwrap().to_vec(),
+            vec![a, b, c, d]
+        );
+        assert!(world.get::<LogicalChildren>(parent).is_none());
+    }
+}
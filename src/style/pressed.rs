@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy_mod_picking::{
+    pointer::{InputPress, PointerId, PressDirection},
+    prelude::*,
+};
+
+/// Resource which tracks which entity, if any, each pointer is currently pressing. This is
+/// used to determine whether to apply the `:active` pseudo-class.
+///
+/// An entity remains pressed until the pointer button is released, even if the pointer has
+/// moved off of the entity in the meantime.
+#[derive(Resource, Default, Clone, PartialEq, Debug)]
+pub struct PressedEntities(pub(crate) HashMap<PointerId, Entity>);
+
+impl PressedEntities {
+    /// Record that the given pointer has pressed the given entity.
+    pub fn press(&mut self, pointer: PointerId, entity: Entity) {
+        self.0.insert(pointer, entity);
+    }
+
+    /// Record that the given pointer has been released.
+    pub fn release(&mut self, pointer: PointerId) {
+        self.0.remove(&pointer);
+    }
+
+    /// Iterate over all entities which are currently pressed.
+    pub fn iter(&self) -> impl Iterator<Item = &Entity> {
+        self.0.values()
+    }
+}
+
+/// System which updates the set of pressed entities from picking events.
+pub(crate) fn update_pressed_entities(
+    mut pressed: ResMut<PressedEntities>,
+    mut down_events: EventReader<Pointer<Down>>,
+    mut input_events: EventReader<InputPress>,
+) {
+    // Releases are read from raw input, since the pointer may no longer be over the
+    // entity which was pressed.
+    for ev in input_events.read() {
+        if ev.direction == PressDirection::Up {
+            pressed.release(ev.pointer_id);
+        }
+    }
+    for ev in down_events.read() {
+        pressed.press(ev.pointer_id, ev.target);
+    }
+}
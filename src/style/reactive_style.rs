@@ -0,0 +1,172 @@
+use std::{marker::PhantomData, sync::Arc};
+
+use bevy::prelude::*;
+
+use super::{
+    builder::StyleBuilder, style_handle::ElementStyles, update::update_styles, StyleHandle,
+};
+
+/// Component which computes part of an element's style from a props component `P` on the
+/// same element. Whenever `P` changes, the style callback is run again and the element is
+/// restyled, without rebuilding the view that produced it. The resulting style is applied
+/// after the element's other styles.
+///
+/// Requires [`ReactiveStylePlugin<P>`] to be installed.
+///
+/// ```ignore
+/// Element::new().insert((
+///     PanelWidth(200.),
+///     ReactiveStyle::new(|width: &PanelWidth, ss| ss.width(width.0)),
+/// ))
+/// ```
+#[derive(Component)]
+pub struct ReactiveStyle<P: Component> {
+    style: Arc<StyleFn<P>>,
+}
+
+type StyleFn<P> = dyn for<'a> Fn(&P, &'a mut StyleBuilder) -> &'a mut StyleBuilder + Send + Sync;
+
+impl<P: Component> ReactiveStyle<P> {
+    /// Construct a new reactive style from a builder callback, which is passed the props.
+    pub fn new<F>(style: F) -> Self
+    where
+        F: for<'a> Fn(&P, &'a mut StyleBuilder) -> &'a mut StyleBuilder + Send + Sync + 'static,
+    {
+        Self {
+            style: Arc::new(style),
+        }
+    }
+}
+
+impl<P: Component> Clone for ReactiveStyle<P> {
+    fn clone(&self) -> Self {
+        Self {
+            style: self.style.clone(),
+        }
+    }
+}
+
+/// Plugin which keeps [`ReactiveStyle<P>`] components up to date. One instance is needed for
+/// each props type.
+pub struct ReactiveStylePlugin<P: Component>(PhantomData<P>);
+
+impl<P: Component> Default for ReactiveStylePlugin<P> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<P: Component> Plugin for ReactiveStylePlugin<P> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, update_reactive_styles::<P>.before(update_styles));
+    }
+}
+
+/// Re-evaluate reactive styles whose props (or callback) have changed, and store the result
+/// in the element's [`ElementStyles`], which will cause the element to be restyled.
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_reactive_styles<P: Component>(
+    mut commands: Commands,
+    mut query: Query<
+        (
+            Entity,
+            Ref<P>,
+            Ref<ReactiveStyle<P>>,
+            Option<&mut ElementStyles>,
+        ),
+        Or<(Changed<P>, Changed<ReactiveStyle<P>>)>,
+    >,
+    mut query_removed: Query<&mut ElementStyles, Without<ReactiveStyle<P>>>,
+    mut removed: RemovedComponents<ReactiveStyle<P>>,
+) {
+    for (entity, props, reactive, element_styles) in query.iter_mut() {
+        let style = StyleHandle::build(|ss| (reactive.style)(&props, ss));
+        match element_styles {
+            Some(mut element_styles) => element_styles.set_reactive(Some(style)),
+            None => {
+                let mut element_styles = ElementStyles::default();
+                element_styles.set_reactive(Some(style));
+                commands.entity(entity).insert(element_styles);
+            }
+        }
+    }
+
+    for entity in removed.read() {
+        if let Ok(mut element_styles) = query_removed.get_mut(entity) {
+            if element_styles.reactive.is_some() {
+                element_styles.set_reactive(None);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::{compute_style, ElementClasses};
+
+    #[derive(Component)]
+    struct PanelWidth(f32);
+
+    #[derive(Component)]
+    struct Unrelated;
+
+    static STYLE_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    #[test]
+    fn test_reactive_style() {
+        let mut app = App::new();
+        app.add_systems(Update, update_reactive_styles::<PanelWidth>);
+        let base = StyleHandle::build(|ss| ss.width(10).height(10));
+        let panel = app
+            .world
+            .spawn((
+                NodeBundle::default(),
+                ElementClasses::default(),
+                ElementStyles::new(&[base.clone()]),
+                PanelWidth(200.),
+                ReactiveStyle::new(|width: &PanelWidth, ss| {
+                    STYLE_CALLS.fetch_add(1, Ordering::Relaxed);
+                    ss.width(width.0)
+                }),
+            ))
+            .id();
+        app.update();
+        assert_eq!(STYLE_CALLS.load(Ordering::Relaxed), 1);
+        let computed = compute_style(&mut app.world, panel);
+        assert_eq!(computed.style.width, Val::Px(200.));
+        assert_eq!(computed.style.height, Val::Px(10.));
+
+        // Nothing is restyled while the props are untouched, even if the element changes.
+        app.update();
+        app.world.entity_mut(panel).insert(Unrelated);
+        app.update();
+        assert_eq!(STYLE_CALLS.load(Ordering::Relaxed), 1);
+
+        // Changing the props restyles the element.
+        app.world.get_mut::<PanelWidth>(panel).unwrap().0 = 300.;
+        app.update();
+        assert_eq!(STYLE_CALLS.load(Ordering::Relaxed), 2);
+        let computed = compute_style(&mut app.world, panel);
+        assert_eq!(computed.style.width, Val::Px(300.));
+
+        // Replacing the element's styles, as a view rebuild would, keeps the reactive style.
+        app.world
+            .get_mut::<ElementStyles>(panel)
+            .unwrap()
+            .update(&[base]);
+        let computed = compute_style(&mut app.world, panel);
+        assert_eq!(computed.style.width, Val::Px(300.));
+
+        // Removing the reactive style removes its properties.
+        app.world
+            .entity_mut(panel)
+            .remove::<ReactiveStyle<PanelWidth>>();
+        app.update();
+        let computed = compute_style(&mut app.world, panel);
+        assert_eq!(computed.style.width, Val::Px(10.));
+        assert_eq!(STYLE_CALLS.load(Ordering::Relaxed), 2);
+    }
+}
@@ -0,0 +1,168 @@
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+use crate::{ScrollArea, ScrollBar, ScrollBarThumb};
+
+/// Component which holds the scrollbar styles for an element. When placed on a
+/// [`ScrollArea`], the styles apply to all of its scrollbars; when placed on a [`ScrollBar`]
+/// itself, the styles override those inherited from the scroll area. This allows the thumb
+/// to be restyled via selectors such as `:hover` on the scrollbar element.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq)]
+pub struct ScrollbarStyle {
+    /// Thickness of the scrollbar track, in logical pixels.
+    pub width: Option<f32>,
+
+    /// Color of the scrollbar thumb.
+    pub thumb_color: Option<Color>,
+
+    /// Color of the scrollbar track.
+    pub track_color: Option<Color>,
+}
+
+impl ScrollbarStyle {
+    /// True if none of the scrollbar properties are set.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Return a new style in which the properties set in `other` take precedence.
+    pub fn merge(&self, other: &ScrollbarStyle) -> ScrollbarStyle {
+        ScrollbarStyle {
+            width: other.width.or(self.width),
+            thumb_color: other.thumb_color.or(self.thumb_color),
+            track_color: other.track_color.or(self.track_color),
+        }
+    }
+}
+
+/// System which copies scrollbar styles from scroll areas and scrollbars onto the
+/// scrollbar track and thumb entities.
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_scrollbar_styles(
+    query_area: Query<(Ref<ScrollArea>, Option<Ref<ScrollbarStyle>>)>,
+    query_bar: Query<(Entity, &ScrollBar, Option<Ref<ScrollbarStyle>>, &Children)>,
+    mut query_track: Query<(&mut Style, &mut BackgroundColor), With<ScrollBar>>,
+    mut query_thumb: Query<&mut BackgroundColor, (With<ScrollBarThumb>, Without<ScrollBar>)>,
+    mut removed: RemovedComponents<ScrollbarStyle>,
+) {
+    // Styles which were removed (for example, a `:hover` style no longer matching) need to
+    // fall back to the inherited style.
+    let removed: HashSet<Entity> = removed.read().collect();
+    for (id_bar, bar, bar_style, children) in query_bar.iter() {
+        let Ok((area, area_style)) = query_area.get(bar.id_scroll_area) else {
+            continue;
+        };
+        let area_changed = area.is_added()
+            || removed.contains(&bar.id_scroll_area)
+            || area_style.as_ref().is_some_and(|s| s.is_changed());
+        let bar_changed =
+            removed.contains(&id_bar) || bar_style.as_ref().is_some_and(|s| s.is_changed());
+        if !area_changed && !bar_changed {
+            continue;
+        }
+
+        let resolved = match (area_style, bar_style) {
+            (Some(a), Some(b)) => a.merge(&b),
+            (Some(a), None) => *a,
+            (None, Some(b)) => *b,
+            (None, None) => continue,
+        };
+
+        if let Ok((mut style, mut bg)) = query_track.get_mut(id_bar) {
+            if let Some(width) = resolved.width {
+                if bar.vertical {
+                    style.width = Val::Px(width);
+                } else {
+                    style.height = Val::Px(width);
+                }
+            }
+            if let Some(color) = resolved.track_color {
+                bg.0 = color;
+            }
+        }
+
+        if let Some(color) = resolved.thumb_color {
+            if let Some(mut bg) = children
+                .first()
+                .and_then(|thumb| query_thumb.get_mut(*thumb).ok())
+            {
+                bg.0 = color;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    #[test]
+    fn test_merge() {
+        let area = ScrollbarStyle {
+            width: Some(8.),
+            thumb_color: Some(Color::RED),
+            track_color: Some(Color::GRAY),
+        };
+        let hover = ScrollbarStyle {
+            thumb_color: Some(Color::BLUE),
+            ..default()
+        };
+        let merged = area.merge(&hover);
+        assert_eq!(merged.width, Some(8.));
+        assert_eq!(merged.thumb_color, Some(Color::BLUE));
+        assert_eq!(merged.track_color, Some(Color::GRAY));
+        assert!(ScrollbarStyle::default().is_empty());
+        assert!(!merged.is_empty());
+    }
+
+    #[test]
+    fn test_thumb_colors() {
+        let mut world = World::new();
+        let area = world
+            .spawn((
+                ScrollArea::default(),
+                ScrollbarStyle {
+                    width: Some(8.),
+                    thumb_color: Some(Color::RED),
+                    track_color: Some(Color::GRAY),
+                },
+            ))
+            .id();
+        let thumb = world
+            .spawn((ScrollBarThumb, BackgroundColor::default()))
+            .id();
+        let bar = world
+            .spawn((
+                ScrollBar {
+                    vertical: true,
+                    id_scroll_area: area,
+                    min_thumb_size: 10.,
+                },
+                Style::default(),
+                BackgroundColor::default(),
+            ))
+            .add_child(thumb)
+            .id();
+
+        world.run_system_once(update_scrollbar_styles);
+        assert_eq!(world.get::<BackgroundColor>(thumb).unwrap().0, Color::RED);
+        assert_eq!(world.get::<BackgroundColor>(bar).unwrap().0, Color::GRAY);
+        assert_eq!(world.get::<Style>(bar).unwrap().width, Val::Px(8.));
+
+        // Simulate a `:hover` style matching on the scrollbar element.
+        world.entity_mut(bar).insert(ScrollbarStyle {
+            thumb_color: Some(Color::BLUE),
+            ..default()
+        });
+        world.run_system_once(update_scrollbar_styles);
+        assert_eq!(world.get::<BackgroundColor>(thumb).unwrap().0, Color::BLUE);
+        assert_eq!(world.get::<BackgroundColor>(bar).unwrap().0, Color::GRAY);
+
+        // Hover ends: thumb reverts to the scroll area's color.
+        world.entity_mut(bar).remove::<ScrollbarStyle>();
+        world.run_system_once(update_scrollbar_styles);
+        assert_eq!(world.get::<BackgroundColor>(thumb).unwrap().0, Color::RED);
+    }
+}
@@ -2,9 +2,9 @@ use std::fmt;
 
 use winnow::{
     ascii::space0,
-    combinator::{alt, opt, preceded, repeat, separated},
+    combinator::{alt, delimited, opt, preceded, repeat, separated},
     stream::AsChar,
-    token::{one_of, take_while},
+    token::{one_of, take_till, take_while},
     PResult, Parser,
 };
 
@@ -13,16 +13,20 @@ use winnow::{
 ///
 /// * Current element (`&`)
 /// * Classname matching
+/// * Attribute matching, by presence (`[name]`) or value (`[name=value]`)
 /// * Parent element (`>`) pattern
+/// * Pseudo-classes such as `:hover`, `:focus`, `:first-child` and `:nth-child(an+b)`
 /// * Multiple patterns can be specified by commas.
 ///
 /// Examples:
 /// ```css
 ///   &
 ///   &.name
+///   &[variant=primary]
 ///   :hover
 ///   .state > &
 ///   .state > * > &.name
+///   &:nth-child(odd)
 /// ```
 ///
 /// Selectors must target the "current element": this means that the "`&`" selector is
@@ -37,9 +41,16 @@ pub enum Selector {
     /// Match an element with a specific class name.
     Class(String, Box<Selector>),
 
+    /// Match an element which has the named attribute and, if a value is given, whose
+    /// attribute has that value.
+    Attribute(String, Option<String>, Box<Selector>),
+
     /// Element that is being hovered.
     Hover(Box<Selector>),
 
+    /// Element that is being pressed by a pointer.
+    Active(Box<Selector>),
+
     /// Element that currently has keyboard focus.
     Focus(Box<Selector>),
 
@@ -55,6 +66,9 @@ pub enum Selector {
     /// Element is the last child of its parent.
     LastChild(Box<Selector>),
 
+    /// Element's 1-based index among its siblings is `a * n + b` for some `n >= 0`.
+    NthChild(i32, i32, Box<Selector>),
+
     /// Reference to the current element.
     Current(Box<Selector>),
 
@@ -68,9 +82,12 @@ pub enum Selector {
 
 enum SelectorToken<'s> {
     Class(&'s str),
+    Attribute(&'s str, Option<&'s str>),
     Hover,
+    Active,
     FirstChild,
     LastChild,
+    NthChild(i32, i32),
     Focus,
     FocusWithin,
     FocusVisible,
@@ -93,6 +110,37 @@ fn class_name<'s>(input: &mut &'s str) -> PResult<SelectorToken<'s>> {
     .parse_next(input)
 }
 
+fn attribute_name<'s>(input: &mut &'s str) -> PResult<&'s str> {
+    (
+        one_of(AsChar::is_alpha),
+        take_while(0.., (AsChar::is_alphanum, '-', '_')),
+    )
+        .recognize()
+        .parse_next(input)
+}
+
+fn attribute_value<'s>(input: &mut &'s str) -> PResult<&'s str> {
+    alt((
+        delimited('"', take_till(0.., '"'), '"'),
+        delimited('\'', take_till(0.., '\''), '\''),
+        take_while(1.., (AsChar::is_alphanum, '-', '_')),
+    ))
+    .parse_next(input)
+}
+
+fn attribute<'s>(input: &mut &'s str) -> PResult<SelectorToken<'s>> {
+    delimited(
+        ('[', space0),
+        (
+            attribute_name,
+            opt(preceded((space0, '=', space0), attribute_value)),
+        ),
+        (space0, ']'),
+    )
+    .map(|(name, value)| SelectorToken::Attribute(name, value))
+    .parse_next(input)
+}
+
 fn hover<'s>(input: &mut &'s str) -> PResult<SelectorToken<'s>> {
     ":hover"
         .recognize()
@@ -100,6 +148,13 @@ fn hover<'s>(input: &mut &'s str) -> PResult<SelectorToken<'s>> {
         .parse_next(input)
 }
 
+fn active<'s>(input: &mut &'s str) -> PResult<SelectorToken<'s>> {
+    ":active"
+        .recognize()
+        .map(|_| SelectorToken::Active)
+        .parse_next(input)
+}
+
 fn focus<'s>(input: &mut &'s str) -> PResult<SelectorToken<'s>> {
     ":focus"
         .recognize()
@@ -135,6 +190,43 @@ fn last_child<'s>(input: &mut &'s str) -> PResult<SelectorToken<'s>> {
         .parse_next(input)
 }
 
+/// Parse the argument of `:nth-child()`, which is either `odd`, `even`, or of the form
+/// `an+b`, where either term may be omitted.
+fn nth_child_arg(arg: &str) -> Option<(i32, i32)> {
+    let arg: String = arg.chars().filter(|c| !c.is_whitespace()).collect();
+    match arg.to_ascii_lowercase().as_str() {
+        "odd" => Some((2, 1)),
+        "even" => Some((2, 0)),
+        arg => match arg.split_once('n') {
+            Some((a, b)) => {
+                let a = match a {
+                    "" | "+" => 1,
+                    "-" => -1,
+                    _ => a.parse().ok()?,
+                };
+                let b = match b {
+                    "" => 0,
+                    _ if b.starts_with(['+', '-']) => b.parse().ok()?,
+                    _ => return None,
+                };
+                Some((a, b))
+            }
+            None => Some((0, arg.parse().ok()?)),
+        },
+    }
+}
+
+fn nth_child<'s>(input: &mut &'s str) -> PResult<SelectorToken<'s>> {
+    delimited(
+        ":nth-child(",
+        take_while(1.., (AsChar::is_alphanum, '+', '-', ' ')),
+        ')',
+    )
+    .verify_map(nth_child_arg)
+    .map(|(a, b)| SelectorToken::NthChild(a, b))
+    .parse_next(input)
+}
+
 fn simple_selector<'s>(input: &mut &'s str) -> PResult<(Option<char>, Vec<SelectorToken<'s>>)> {
     (
         opt(alt(('*', '&'))),
@@ -142,9 +234,12 @@ fn simple_selector<'s>(input: &mut &'s str) -> PResult<(Option<char>, Vec<Select
             0..,
             alt((
                 class_name,
+                attribute,
                 hover,
+                active,
                 first_child,
                 last_child,
+                nth_child,
                 focus,
                 focus_within,
                 focus_visible,
@@ -162,15 +257,28 @@ fn combo_selector(input: &mut &str) -> PResult<Box<Selector>> {
             SelectorToken::Class(cls) => {
                 sel = Box::new(Selector::Class(cls.into(), sel));
             }
+            SelectorToken::Attribute(name, value) => {
+                sel = Box::new(Selector::Attribute(
+                    name.into(),
+                    value.map(String::from),
+                    sel,
+                ));
+            }
             SelectorToken::Hover => {
                 sel = Box::new(Selector::Hover(sel));
             }
+            SelectorToken::Active => {
+                sel = Box::new(Selector::Active(sel));
+            }
             SelectorToken::FirstChild => {
                 sel = Box::new(Selector::FirstChild(sel));
             }
             SelectorToken::LastChild => {
                 sel = Box::new(Selector::LastChild(sel));
             }
+            SelectorToken::NthChild(a, b) => {
+                sel = Box::new(Selector::NthChild(a, b, sel));
+            }
             SelectorToken::Focus => {
                 sel = Box::new(Selector::Focus(sel));
             }
@@ -217,15 +325,28 @@ impl Selector {
                     SelectorToken::Class(cls) => {
                         sel = Box::new(Selector::Class(cls.into(), sel));
                     }
+                    SelectorToken::Attribute(name, value) => {
+                        sel = Box::new(Selector::Attribute(
+                            name.into(),
+                            value.map(String::from),
+                            sel,
+                        ));
+                    }
                     SelectorToken::Hover => {
                         sel = Box::new(Selector::Hover(sel));
                     }
+                    SelectorToken::Active => {
+                        sel = Box::new(Selector::Active(sel));
+                    }
                     SelectorToken::FirstChild => {
                         sel = Box::new(Selector::FirstChild(sel));
                     }
                     SelectorToken::LastChild => {
                         sel = Box::new(Selector::LastChild(sel));
                     }
+                    SelectorToken::NthChild(a, b) => {
+                        sel = Box::new(Selector::NthChild(a, b, sel));
+                    }
                     SelectorToken::Focus => {
                         sel = Box::new(Selector::Focus(sel));
                     }
@@ -252,13 +373,15 @@ impl Selector {
     pub(crate) fn depth(&self) -> usize {
         match self {
             Selector::Accept => 1,
-            Selector::Class(_, next) => next.depth(),
+            Selector::Class(_, next) | Selector::Attribute(_, _, next) => next.depth(),
             Selector::Hover(next)
+            | Selector::Active(next)
             | Selector::Focus(next)
             | Selector::FocusWithin(next)
             | Selector::FocusVisible(next)
             | Selector::FirstChild(next)
-            | Selector::LastChild(next) => next.depth(),
+            | Selector::LastChild(next)
+            | Selector::NthChild(_, _, next) => next.depth(),
             Selector::Current(next) => next.depth(),
             Selector::Parent(next) => next.depth() + 1,
             Selector::Either(opts) => opts.iter().map(|next| next.depth()).max().unwrap_or(0),
@@ -269,13 +392,15 @@ impl Selector {
     pub(crate) fn uses_hover(&self) -> bool {
         match self {
             Selector::Accept => false,
-            Selector::Class(_, next) => next.uses_hover(),
+            Selector::Class(_, next) | Selector::Attribute(_, _, next) => next.uses_hover(),
             Selector::Hover(_) => true,
-            Selector::Focus(next)
+            Selector::Active(next)
+            | Selector::Focus(next)
             | Selector::FocusWithin(next)
             | Selector::FocusVisible(next)
             | Selector::FirstChild(next)
             | Selector::LastChild(next)
+            | Selector::NthChild(_, _, next)
             | Selector::Current(next) => next.uses_hover(),
             Selector::Parent(next) => next.uses_hover(),
             Selector::Either(opts) => opts
@@ -286,26 +411,47 @@ impl Selector {
         }
     }
 
-    /// Returns whether this selector uses the hover pseudo-class.
+    /// Returns whether this selector uses the focus-within pseudo-class.
     pub(crate) fn uses_focus_within(&self) -> bool {
         match self {
             Selector::Accept => false,
-            Selector::Class(_, next) => next.uses_hover(),
+            Selector::Class(_, next) | Selector::Attribute(_, _, next) => next.uses_focus_within(),
             Selector::FocusWithin(_) => true,
             Selector::Hover(next)
+            | Selector::Active(next)
             | Selector::Focus(next)
             | Selector::FocusVisible(next)
             | Selector::FirstChild(next)
             | Selector::LastChild(next)
-            | Selector::Current(next) => next.uses_hover(),
-            Selector::Parent(next) => next.uses_hover(),
+            | Selector::NthChild(_, _, next)
+            | Selector::Current(next) => next.uses_focus_within(),
+            Selector::Parent(next) => next.uses_focus_within(),
             Selector::Either(opts) => opts
                 .iter()
-                .map(|next| next.uses_hover())
+                .map(|next| next.uses_focus_within())
                 .max()
                 .unwrap_or(false),
         }
     }
+
+    /// Returns whether this selector depends on the position of an element among its siblings,
+    /// meaning that it must be re-evaluated when siblings are added, removed or reordered.
+    pub(crate) fn uses_child_position(&self) -> bool {
+        match self {
+            Selector::Accept => false,
+            Selector::FirstChild(_) | Selector::LastChild(_) | Selector::NthChild(_, _, _) => true,
+            Selector::Class(_, next)
+            | Selector::Attribute(_, _, next)
+            | Selector::Hover(next)
+            | Selector::Active(next)
+            | Selector::Focus(next)
+            | Selector::FocusWithin(next)
+            | Selector::FocusVisible(next)
+            | Selector::Current(next)
+            | Selector::Parent(next) => next.uses_child_position(),
+            Selector::Either(opts) => opts.iter().any(|next| next.uses_child_position()),
+        }
+    }
 }
 
 impl std::str::FromStr for Selector {
@@ -319,6 +465,22 @@ impl std::str::FromStr for Selector {
     }
 }
 
+/// Format an attribute selector term, quoting the value if it isn't a plain identifier.
+fn attribute_string(name: &str, value: Option<&str>) -> String {
+    match value {
+        None => format!("[{}]", name),
+        Some(value)
+            if !value.is_empty()
+                && value
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '-' || c == '_') =>
+        {
+            format!("[{}={}]", name, value)
+        }
+        Some(value) => format!("[{}=\"{}\"]", name, value),
+    }
+}
+
 impl fmt::Display for Selector {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -327,22 +489,44 @@ impl fmt::Display for Selector {
                 // Because 'current' comes first, reverse order
                 let mut str = String::with_capacity(64);
                 let mut p = prev.as_ref();
-                while let Selector::Class(name, desc) = p {
-                    str.insert_str(0, name);
-                    str.insert(0, '.');
-                    p = desc.as_ref()
+                loop {
+                    match p {
+                        Selector::Class(name, desc) => {
+                            str.insert_str(0, name);
+                            str.insert(0, '.');
+                            p = desc.as_ref()
+                        }
+                        Selector::Attribute(name, value, desc) => {
+                            str.insert_str(0, &attribute_string(name, value.as_deref()));
+                            p = desc.as_ref()
+                        }
+                        _ => break,
+                    }
                 }
                 str.insert(0, '&');
                 write!(f, "{}{}", p, str)
             }
 
             Selector::Class(name, prev) => write!(f, "{}.{}", prev, name),
+            Selector::Attribute(name, value, prev) => {
+                write!(f, "{}{}", prev, attribute_string(name, value.as_deref()))
+            }
             Selector::Hover(prev) => write!(f, "{}:hover", prev),
+            Selector::Active(prev) => write!(f, "{}:active", prev),
             Selector::Focus(prev) => write!(f, "{}:focus", prev),
             Selector::FocusWithin(prev) => write!(f, "{}:focus-within", prev),
             Selector::FocusVisible(prev) => write!(f, "{}:focus-visible", prev),
             Selector::FirstChild(prev) => write!(f, "{}:first-child", prev),
             Selector::LastChild(prev) => write!(f, "{}:last-child", prev),
+            Selector::NthChild(a, b, prev) => match (a, b) {
+                (0, _) => write!(f, "{}:nth-child({})", prev, b),
+                (1, 0) => write!(f, "{}:nth-child(n)", prev),
+                (-1, 0) => write!(f, "{}:nth-child(-n)", prev),
+                (_, 0) => write!(f, "{}:nth-child({}n)", prev, a),
+                (1, _) => write!(f, "{}:nth-child(n{:+})", prev, b),
+                (-1, _) => write!(f, "{}:nth-child(-n{:+})", prev, b),
+                _ => write!(f, "{}:nth-child({}n{:+})", prev, a, b),
+            },
             Selector::Parent(prev) => match prev.as_ref() {
                 Selector::Parent(_) => write!(f, "{}* > ", prev),
                 _ => write!(f, "{} > ", prev),
@@ -445,6 +629,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_active() {
+        assert_eq!(
+            ":active".parse::<Selector>().unwrap(),
+            Selector::Active(Box::new(Selector::Accept))
+        );
+        assert_eq!(
+            ".foo:active".parse::<Selector>().unwrap().to_string(),
+            ".foo:active",
+        );
+    }
+
     #[test]
     fn test_parse_first_last_child() {
         assert_eq!(
@@ -471,6 +667,81 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_nth_child() {
+        assert_eq!(
+            ":nth-child(2n+1)".parse::<Selector>().unwrap(),
+            Selector::NthChild(2, 1, Box::new(Selector::Accept))
+        );
+        assert_eq!(
+            ".foo:nth-child(3)".parse::<Selector>().unwrap(),
+            Selector::NthChild(
+                0,
+                3,
+                Box::new(Selector::Class("foo".into(), Box::new(Selector::Accept)))
+            )
+        );
+        assert_eq!(
+            ":nth-child(odd)".parse::<Selector>().unwrap(),
+            ":nth-child(2n + 1)".parse::<Selector>().unwrap()
+        );
+        assert_eq!(
+            ":nth-child(even)".parse::<Selector>().unwrap(),
+            Selector::NthChild(2, 0, Box::new(Selector::Accept))
+        );
+        assert_eq!(
+            ":nth-child(-n+3)".parse::<Selector>().unwrap(),
+            Selector::NthChild(-1, 3, Box::new(Selector::Accept))
+        );
+        assert!(":nth-child(2n1)".parse::<Selector>().is_err());
+        assert!(":nth-child()".parse::<Selector>().is_err());
+
+        for sel in [
+            ":nth-child(2n+1)",
+            ":nth-child(n)",
+            ":nth-child(-n+3)",
+            ".foo:nth-child(3n-2)",
+            ":nth-child(4)",
+        ] {
+            assert_eq!(sel.parse::<Selector>().unwrap().to_string(), sel);
+        }
+    }
+
+    #[test]
+    fn test_parse_attribute() {
+        assert_eq!(
+            "[disabled]".parse::<Selector>().unwrap(),
+            Selector::Attribute("disabled".into(), None, Box::new(Selector::Accept))
+        );
+        assert_eq!(
+            "&.button[variant=primary]".parse::<Selector>().unwrap(),
+            Selector::Current(Box::new(Selector::Attribute(
+                "variant".into(),
+                Some("primary".into()),
+                Box::new(Selector::Class("button".into(), Box::new(Selector::Accept)))
+            )))
+        );
+        assert_eq!(
+            "[ label = \"Save as\" ]".parse::<Selector>().unwrap(),
+            Selector::Attribute(
+                "label".into(),
+                Some("Save as".into()),
+                Box::new(Selector::Accept)
+            )
+        );
+        assert!("[variant=]".parse::<Selector>().is_err());
+        assert!("[=primary]".parse::<Selector>().is_err());
+
+        for sel in [
+            "&[disabled]",
+            "&.button[variant=primary]",
+            "[variant=primary] > &",
+            "[label=\"Save as\"]",
+        ] {
+            assert_eq!(sel.parse::<Selector>().unwrap().to_string(), sel);
+        }
+    }
+
     #[test]
     fn test_parse_parent() {
         assert_eq!(
@@ -508,4 +779,20 @@ mod tests {
             ))
         );
     }
+
+    #[test]
+    fn test_uses_focus_within() {
+        let focus_within = || Box::new(Selector::FocusWithin(Box::new(Selector::Accept)));
+        assert!(Selector::Class("foo".into(), focus_within()).uses_focus_within());
+        assert!(Selector::Attribute("disabled".into(), None, focus_within()).uses_focus_within());
+        assert!(
+            Selector::Class("bar".into(), Box::new(Selector::Parent(focus_within())))
+                .uses_focus_within()
+        );
+
+        // Hover does not imply focus-within.
+        let hover = Box::new(Selector::Hover(Box::new(Selector::Accept)));
+        assert!(!Selector::Class("foo".into(), hover.clone()).uses_focus_within());
+        assert!(!Selector::Attribute("disabled".into(), None, hover).uses_focus_within());
+    }
 }
@@ -3,30 +3,48 @@ use bevy::utils::HashMap;
 use bevy_mod_picking::backend::HitData;
 use bevy_mod_picking::pointer::PointerId;
 
-use crate::{ElementClasses, Selector};
+use crate::{ElementAttributes, ElementClasses, PressedEntities, Selector};
+
+use super::{background_layers::BackgroundLayerNode, order::LogicalChildren};
 
 pub struct SelectorMatcher<'w, 's, 'h> {
     classes_query: &'h Query<'w, 's, Ref<'static, ElementClasses>>,
+    attributes_query: &'h Query<'w, 's, Ref<'static, ElementAttributes>>,
     parent_query: &'h Query<'w, 's, &'static Parent, (With<Node>, With<Visibility>)>,
     children_query: &'h Query<'w, 's, &'static Children, (With<Node>, With<Visibility>)>,
+    logical_children_query: &'h Query<'w, 's, &'static LogicalChildren>,
+    layers_query: &'h Query<'w, 's, (), With<BackgroundLayerNode>>,
     hover_map: &'h HashMap<PointerId, HashMap<Entity, HitData>>,
     focus: Option<Entity>,
+    focus_visible: bool,
+    pressed: &'h PressedEntities,
 }
 
 impl<'w, 's, 'h> SelectorMatcher<'w, 's, 'h> {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         query: &'h Query<'w, 's, Ref<'static, ElementClasses>>,
+        attributes_query: &'h Query<'w, 's, Ref<'static, ElementAttributes>>,
         parent_query: &'h Query<'w, 's, &'static Parent, (With<Node>, With<Visibility>)>,
         children_query: &'h Query<'w, 's, &'static Children, (With<Node>, With<Visibility>)>,
+        logical_children_query: &'h Query<'w, 's, &'static LogicalChildren>,
+        layers_query: &'h Query<'w, 's, (), With<BackgroundLayerNode>>,
         hover_map: &'h HashMap<PointerId, HashMap<Entity, HitData>>,
         focus: Option<Entity>,
+        focus_visible: bool,
+        pressed: &'h PressedEntities,
     ) -> Self {
         Self {
             classes_query: query,
+            attributes_query,
             parent_query,
             children_query,
+            logical_children_query,
+            layers_query,
             hover_map,
             focus,
+            focus_visible,
+            pressed,
         }
     }
 
@@ -48,6 +66,24 @@ impl<'w, 's, 'h> SelectorMatcher<'w, 's, 'h> {
         }
     }
 
+    /// True if the given entity, or a descendant of it, is being pressed by a pointer.
+    ///
+    /// This is used to determine whether to apply the :active pseudo-class.
+    pub fn is_active(&self, e: &Entity) -> bool {
+        self.pressed.iter().any(|pressed| {
+            let mut ha = *pressed;
+            loop {
+                if ha == *e {
+                    return true;
+                }
+                match self.parent_query.get(ha) {
+                    Ok(parent) => ha = parent.get(),
+                    _ => return false,
+                }
+            }
+        })
+    }
+
     /// True if the given entity has keyboard focus.
     ///
     /// This is used to determine whether to apply the :focus pseudo-class.
@@ -76,29 +112,56 @@ impl<'w, 's, 'h> SelectorMatcher<'w, 's, 'h> {
     ///
     /// This is used to determine whether to apply the :focus-visible pseudo-class.
     pub fn is_focus_visible(&self, e: &Entity) -> bool {
-        // TODO: Add configuration flag for whether focus should be visible.
-        Some(e) == self.focus.as_ref()
+        self.focus_visible && Some(e) == self.focus.as_ref()
+    }
+
+    /// The 0-based index of this entity among the children of its parent, and the number of
+    /// those children. Background layer nodes are not counted, since they are not part of the
+    /// element's content. Children which have been visually reordered are counted in their
+    /// logical order.
+    fn child_position(&self, entity: &Entity) -> Option<(usize, usize)> {
+        let parent = self.parent_query.get(*entity).ok()?;
+        let children: &[Entity] = self.children_query.get(parent.get()).ok()?;
+        let children = match self.logical_children_query.get(parent.get()) {
+            Ok(logical) => logical.resolve(children),
+            Err(_) => children,
+        };
+        let mut index = None;
+        let mut count = 0;
+        for child in children
+            .iter()
+            .filter(|child| !self.layers_query.contains(**child))
+        {
+            if child == entity {
+                index = Some(count);
+            }
+            count += 1;
+        }
+        index.map(|index| (index, count))
     }
 
     /// True if this entity is the first child of its parent.
     pub fn is_first_child(&self, entity: &Entity) -> bool {
-        match self.parent_query.get(*entity) {
-            Ok(parent) => match self.children_query.get(parent.get()) {
-                Ok(children) => children.first() == Some(entity),
-                _ => false,
-            },
-            _ => false,
-        }
+        self.child_position(entity)
+            .is_some_and(|(index, _)| index == 0)
     }
 
     /// True if this entity is the last child of its parent.
     pub fn is_last_child(&self, entity: &Entity) -> bool {
-        match self.parent_query.get(*entity) {
-            Ok(parent) => match self.children_query.get(parent.get()) {
-                Ok(children) => children.last() == Some(entity),
-                _ => false,
-            },
-            _ => false,
+        self.child_position(entity)
+            .is_some_and(|(index, count)| index + 1 == count)
+    }
+
+    /// True if this entity's 1-based index among the children of its parent is `a * n + b`
+    /// for some `n >= 0`.
+    pub fn is_nth_child(&self, entity: &Entity, a: i32, b: i32) -> bool {
+        let Some((index, _)) = self.child_position(entity) else {
+            return false;
+        };
+        let offset = index as i32 + 1 - b;
+        match a {
+            0 => offset == 0,
+            _ => offset % a == 0 && offset / a >= 0,
         }
     }
 
@@ -111,7 +174,18 @@ impl<'w, 's, 'h> SelectorMatcher<'w, 's, 'h> {
                 Ok(classes) => classes.0.contains(cls) && self.selector_match(next, entity),
                 _ => false,
             },
+            Selector::Attribute(name, value, next) => match self.attributes_query.get(*entity) {
+                Ok(attributes) => {
+                    let matched = match value {
+                        Some(value) => attributes.get(name) == Some(value.as_str()),
+                        None => attributes.contains(name),
+                    };
+                    matched && self.selector_match(next, entity)
+                }
+                _ => false,
+            },
             Selector::Hover(next) => self.is_hovering(entity) && self.selector_match(next, entity),
+            Selector::Active(next) => self.is_active(entity) && self.selector_match(next, entity),
             Selector::Focus(next) => self.is_focused(entity) && self.selector_match(next, entity),
             Selector::FocusWithin(next) => {
                 self.is_focus_within(entity) && self.selector_match(next, entity)
@@ -125,6 +199,9 @@ impl<'w, 's, 'h> SelectorMatcher<'w, 's, 'h> {
             Selector::LastChild(next) => {
                 self.is_last_child(entity) && self.selector_match(next, entity)
             }
+            Selector::NthChild(a, b, next) => {
+                self.is_nth_child(entity, *a, *b) && self.selector_match(next, entity)
+            }
             Selector::Current(next) => self.selector_match(next, entity),
             Selector::Parent(next) => match self.parent_query.get(*entity) {
                 Ok(parent) => self.selector_match(next, &parent.get()),
@@ -134,3 +211,232 @@ impl<'w, 's, 'h> SelectorMatcher<'w, 's, 'h> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::{RunSystemOnce, SystemState};
+
+    use super::*;
+    use crate::style::order::{update_visual_order, VisualOrder};
+
+    /// Run `f` with a matcher over the entities in `world`, with nothing hovered or focused.
+    fn with_matcher<R>(
+        world: &mut World,
+        pressed: &PressedEntities,
+        f: impl FnOnce(&SelectorMatcher) -> R,
+    ) -> R {
+        #[allow(clippy::type_complexity)]
+        let mut state: SystemState<(
+            Query<Ref<ElementClasses>>,
+            Query<Ref<ElementAttributes>>,
+            Query<&Parent, (With<Node>, With<Visibility>)>,
+            Query<&Children, (With<Node>, With<Visibility>)>,
+            Query<&LogicalChildren>,
+            Query<(), With<BackgroundLayerNode>>,
+        )> = SystemState::new(world);
+        let (classes, attributes, parents, children, logical, layers) = state.get(world);
+        let hover_map = HashMap::new();
+        let matcher = SelectorMatcher::new(
+            &classes,
+            &attributes,
+            &parents,
+            &children,
+            &logical,
+            &layers,
+            &hover_map,
+            None,
+            false,
+            pressed,
+        );
+        f(&matcher)
+    }
+
+    #[test]
+    fn test_active() {
+        let mut world = World::new();
+        let child = world
+            .spawn((NodeBundle::default(), ElementClasses::default()))
+            .id();
+        let parent = world
+            .spawn((NodeBundle::default(), ElementClasses::default()))
+            .add_child(child)
+            .id();
+        let other = world
+            .spawn((NodeBundle::default(), ElementClasses::default()))
+            .id();
+        let active = Selector::Active(Box::new(Selector::Accept));
+
+        let mut pressed = PressedEntities::default();
+        pressed.press(PointerId::Mouse, child);
+        with_matcher(&mut world, &pressed, |matcher| {
+            assert!(matcher.selector_match(&active, &child));
+            assert!(matcher.selector_match(&active, &parent));
+            assert!(!matcher.selector_match(&active, &other));
+        });
+
+        // Release over a different element.
+        pressed.release(PointerId::Mouse);
+        with_matcher(&mut world, &pressed, |matcher| {
+            assert!(!matcher.selector_match(&active, &child));
+            assert!(!matcher.selector_match(&active, &parent));
+        });
+    }
+
+    #[test]
+    fn test_first_last_child() {
+        let mut world = World::new();
+        let items: Vec<Entity> = (0..3)
+            .map(|_| {
+                world
+                    .spawn((NodeBundle::default(), ElementClasses::default()))
+                    .id()
+            })
+            .collect();
+        // Background layer nodes come before the content, but aren't counted.
+        let layer = world
+            .spawn((NodeBundle::default(), BackgroundLayerNode))
+            .id();
+        world
+            .spawn((NodeBundle::default(), ElementClasses::default()))
+            .add_child(layer)
+            .push_children(&items);
+
+        with_matcher(&mut world, &PressedEntities::default(), |matcher| {
+            assert!(matcher.is_first_child(&items[0]));
+            assert!(!matcher.is_first_child(&items[1]));
+            assert!(!matcher.is_first_child(&layer));
+            assert!(matcher.is_last_child(&items[2]));
+            assert!(!matcher.is_last_child(&items[1]));
+        });
+    }
+
+    #[test]
+    fn test_active_parent_combinator() {
+        let mut world = World::new();
+        let label = world
+            .spawn((NodeBundle::default(), ElementClasses::default()))
+            .id();
+        let mut button_classes = ElementClasses::default();
+        button_classes.add_class("button");
+        let button = world
+            .spawn((NodeBundle::default(), button_classes))
+            .add_child(label)
+            .id();
+        let panel = world
+            .spawn((NodeBundle::default(), ElementClasses::default()))
+            .add_child(button)
+            .id();
+        let selector = ".button:active > &".parse::<Selector>().unwrap();
+
+        let mut pressed = PressedEntities::default();
+        with_matcher(&mut world, &pressed, |matcher| {
+            assert!(!matcher.selector_match(&selector, &label));
+        });
+
+        // Pressing the label makes its ancestors active too.
+        pressed.press(PointerId::Mouse, label);
+        with_matcher(&mut world, &pressed, |matcher| {
+            assert!(matcher.selector_match(&selector, &label));
+            // The button's parent doesn't have the class.
+            assert!(!matcher.selector_match(&selector, &button));
+            assert!(matcher.is_active(&panel));
+        });
+    }
+
+    #[test]
+    fn test_nth_child() {
+        let mut world = World::new();
+        let rows: Vec<Entity> = (0..6)
+            .map(|_| {
+                world
+                    .spawn((NodeBundle::default(), ElementClasses::default()))
+                    .id()
+            })
+            .collect();
+        // Background layer nodes come before the content, but aren't counted.
+        let layer = world
+            .spawn((NodeBundle::default(), BackgroundLayerNode))
+            .id();
+        world
+            .spawn((NodeBundle::default(), ElementClasses::default()))
+            .add_child(layer)
+            .push_children(&rows);
+        let orphan = world
+            .spawn((NodeBundle::default(), ElementClasses::default()))
+            .id();
+
+        with_matcher(&mut world, &PressedEntities::default(), |matcher| {
+            let matching = |sel: &str| -> Vec<usize> {
+                let selector = sel.parse::<Selector>().unwrap();
+                (0..rows.len())
+                    .filter(|i| matcher.selector_match(&selector, &rows[*i]))
+                    .collect()
+            };
+
+            assert_eq!(matching("&:nth-child(odd)"), vec![0, 2, 4]);
+            assert_eq!(matching("&:nth-child(even)"), vec![1, 3, 5]);
+            assert_eq!(matching("&:nth-child(3n)"), vec![2, 5]);
+            assert_eq!(matching("&:nth-child(n+4)"), vec![3, 4, 5]);
+            assert_eq!(matching("&:nth-child(-n+2)"), vec![0, 1]);
+            assert_eq!(matching("&:nth-child(2)"), vec![1]);
+            assert_eq!(matching("&:first-child"), vec![0]);
+            assert_eq!(matching("&:last-child"), vec![5]);
+            assert!(!matcher.is_nth_child(&orphan, 1, 0));
+        });
+    }
+
+    #[test]
+    fn test_child_position_logical_order() {
+        let mut world = World::new();
+        let first = world
+            .spawn((NodeBundle::default(), ElementClasses::default()))
+            .id();
+        let last = world
+            .spawn((
+                NodeBundle::default(),
+                ElementClasses::default(),
+                VisualOrder(-1),
+            ))
+            .id();
+        let parent = world
+            .spawn(NodeBundle::default())
+            .push_children(&[first, last])
+            .id();
+        world.run_system_once(update_visual_order);
+        assert_eq!(
+            world.get::<Children>(parent).unwrap().to_vec(),
+            vec![last, first]
+        );
+
+        // Structural selectors match the logical order, not the visual one.
+        with_matcher(&mut world, &PressedEntities::default(), |matcher| {
+            assert!(matcher.is_first_child(&first));
+            assert!(matcher.is_last_child(&last));
+            assert!(matcher.is_nth_child(&last, 0, 2));
+        });
+    }
+
+    #[test]
+    fn test_attribute() {
+        let mut world = World::new();
+        let mut attributes = ElementAttributes::default();
+        attributes.set("variant", "primary");
+        attributes.set("disabled", "");
+        let button = world.spawn((NodeBundle::default(), attributes)).id();
+        let plain = world.spawn(NodeBundle::default()).id();
+
+        with_matcher(&mut world, &PressedEntities::default(), |matcher| {
+            let matches = |sel: &str, entity: Entity| {
+                matcher.selector_match(&sel.parse::<Selector>().unwrap(), &entity)
+            };
+
+            assert!(matches("&[disabled]", button));
+            assert!(matches("&[variant]", button));
+            assert!(matches("&[variant=primary]", button));
+            assert!(matches("&[variant=primary][disabled]", button));
+            assert!(!matches("&[variant=secondary]", button));
+            assert!(!matches("&[size]", button));
+            assert!(!matches("&[disabled]", plain));
+        });
+    }
+}
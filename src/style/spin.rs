@@ -0,0 +1,87 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+/// Component which continuously rotates an element about its center, at the given speed in
+/// radians per second. The rotation is time-based, so the speed is independent of frame rate.
+/// While spinning, the element's rotation replaces any rotation set via styles.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Spin {
+    /// Rotation speed, in radians per second. Negative values spin clockwise.
+    pub speed: f32,
+
+    /// Current rotation angle, in radians.
+    pub angle: f32,
+}
+
+impl Spin {
+    /// Construct a new spin animation, starting at an angle of zero.
+    pub fn new(speed: f32) -> Self {
+        Self { speed, angle: 0. }
+    }
+
+    /// Advance the rotation by `delta` seconds, keeping the angle within one revolution.
+    pub fn advance(&mut self, delta: f32) {
+        self.angle = (self.angle + self.speed * delta).rem_euclid(TAU);
+    }
+}
+
+/// System which advances spin animations.
+pub fn animate_spin(mut query: Query<(&mut Spin, &mut Transform)>, time: Res<Time>) {
+    let delta = time.delta_seconds();
+    for (mut spin, mut transform) in query.iter_mut() {
+        spin.advance(delta);
+        transform.rotation = Quat::from_rotation_z(spin.angle);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{f32::consts::PI, time::Duration};
+
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    #[test]
+    fn test_advance() {
+        let mut spin = Spin::new(PI);
+        spin.advance(0.5);
+        assert!((spin.angle - PI * 0.5).abs() < 1e-5);
+
+        // Wraps around after a full revolution.
+        spin.advance(2.);
+        assert!((spin.angle - PI * 0.5).abs() < 1e-5);
+
+        // Negative speeds wrap into the positive range.
+        let mut spin = Spin::new(-PI);
+        spin.advance(0.5);
+        assert!((spin.angle - PI * 1.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_animate_spin() {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        let entity = world.spawn((Spin::new(PI), Transform::default())).id();
+
+        let step = |world: &mut World, seconds: f32| {
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_secs_f32(seconds));
+            world.run_system_once(animate_spin);
+            let (axis, angle) = world
+                .get::<Transform>(entity)
+                .unwrap()
+                .rotation
+                .to_axis_angle();
+            angle * axis.z.signum()
+        };
+
+        // Rotation is proportional to elapsed time, regardless of the number of frames.
+        assert!((step(&mut world, 0.25) - PI * 0.25).abs() < 1e-4);
+        assert!((step(&mut world, 0.25) - PI * 0.5).abs() < 1e-4);
+        step(&mut world, 0.125);
+        assert!((step(&mut world, 0.125) - PI * 0.75).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,212 @@
+use std::{fmt, sync::Arc};
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+    utils::{BoxedFuture, HashSet},
+};
+
+use super::{
+    style_data::StyleData,
+    style_handle::{ElementStyles, StyleHandle},
+    style_props::StyleSet,
+};
+
+/// A style loaded from a `.style.ron` asset file. The file contains the data form of a
+/// [`StyleHandle`]: a list of style properties, and a list of selectors with their own
+/// properties.
+///
+/// Elements which use a style asset, via [`View::style_asset`], are restyled whenever the
+/// asset is reloaded, so if the [`AssetServer`] is watching for changes, edits to the file are
+/// visible without recompiling.
+///
+/// [`View::style_asset`]: crate::View::style_asset
+#[derive(Asset, TypePath, Clone)]
+pub struct StyleAsset(pub StyleHandle);
+
+/// Error which can occur when loading a [`StyleAsset`].
+#[derive(Debug)]
+pub enum StyleAssetError {
+    /// The file couldn't be read.
+    Io(std::io::Error),
+    /// The file isn't valid RON, or doesn't match the style format.
+    Ron(ron::error::SpannedError),
+    /// One of the selector expressions couldn't be parsed.
+    Selector(String),
+}
+
+impl fmt::Display for StyleAssetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StyleAssetError::Io(err) => write!(f, "could not read style asset: {}", err),
+            StyleAssetError::Ron(err) => write!(f, "could not parse style asset: {}", err),
+            StyleAssetError::Selector(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for StyleAssetError {}
+
+impl From<std::io::Error> for StyleAssetError {
+    fn from(err: std::io::Error) -> Self {
+        StyleAssetError::Io(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for StyleAssetError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        StyleAssetError::Ron(err)
+    }
+}
+
+/// Parse the contents of a style asset file.
+pub(crate) fn parse_style(bytes: &[u8]) -> Result<StyleHandle, StyleAssetError> {
+    let data: StyleData = ron::de::from_bytes(bytes)?;
+    let style = StyleSet::try_from(data).map_err(StyleAssetError::Selector)?;
+    Ok(StyleHandle(Arc::new(style)))
+}
+
+/// Loader for [`StyleAsset`]s.
+#[derive(Default)]
+pub struct StyleAssetLoader;
+
+impl AssetLoader for StyleAssetLoader {
+    type Asset = StyleAsset;
+    type Settings = ();
+    type Error = StyleAssetError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a Self::Settings,
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            Ok(StyleAsset(parse_style(&bytes)?))
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["style.ron"]
+    }
+}
+
+/// Component which records the style asset used by an element. Added by
+/// [`View::style_asset`](crate::View::style_asset).
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct ElementStyleAsset(pub Handle<StyleAsset>);
+
+/// System which copies the contents of style assets into the [`ElementStyles`] of the
+/// elements which use them, when the element is first given the asset, and whenever the
+/// asset is loaded or reloaded. Updating [`ElementStyles`] causes the element to be restyled.
+/// Until the asset is loaded, the element is styled as if it had no style asset.
+#[allow(clippy::type_complexity)]
+pub(crate) fn apply_style_assets(
+    mut commands: Commands,
+    mut events: EventReader<AssetEvent<StyleAsset>>,
+    assets: Res<Assets<StyleAsset>>,
+    mut query: Query<(Entity, Ref<ElementStyleAsset>, Option<&mut ElementStyles>)>,
+) {
+    let mut changed = HashSet::new();
+    for event in events.read() {
+        match event {
+            AssetEvent::LoadedWithDependencies { id }
+            | AssetEvent::Modified { id }
+            | AssetEvent::Removed { id } => {
+                changed.insert(*id);
+            }
+            _ => {}
+        }
+    }
+
+    for (entity, asset, styles) in query.iter_mut() {
+        if !asset.is_changed() && !changed.contains(&asset.0.id()) {
+            continue;
+        }
+        let style = assets.get(&asset.0).map(|asset| asset.0.clone());
+        match styles {
+            Some(mut styles) => styles.set_asset(style),
+            None => {
+                let mut styles = ElementStyles::default();
+                styles.set_asset(style);
+                commands.entity(entity).insert(styles);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StyleProp;
+
+    #[test]
+    fn test_parse_style() {
+        let style = parse_style(
+            br#"(
+                props: [
+                    BackgroundColor(Some((red: 1.0, green: 0.0, blue: 0.0, alpha: 1.0))),
+                    Width(Px(100.0)),
+                ],
+                selectors: [
+                    (":hover", [Width(Percent(50.0))]),
+                ],
+            )"#,
+        )
+        .unwrap();
+        assert_eq!(style.0.props.len(), 2);
+        assert!(matches!(
+            style.0.props[0],
+            StyleProp::BackgroundColor(Some(color)) if color == Color::rgba(1., 0., 0., 1.)
+        ));
+        assert!(matches!(style.0.props[1], StyleProp::Width(Val::Px(w)) if w == 100.));
+        assert_eq!(style.0.selectors.len(), 1);
+        assert_eq!(style.0.selectors[0].0.to_string(), ":hover");
+
+        assert!(matches!(
+            parse_style(br#"(selectors: [("&:unknown", [])])"#),
+            Err(StyleAssetError::Selector(_))
+        ));
+        assert!(matches!(
+            parse_style(b"(props: [NoSuchProp])"),
+            Err(StyleAssetError::Ron(_))
+        ));
+        assert!(matches!(
+            parse_style(br#"(props: [CursorImage("cursors/grab.png")])"#),
+            Err(StyleAssetError::Ron(_))
+        ));
+    }
+
+    #[test]
+    fn test_reload_style_asset() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<StyleAsset>()
+            .add_systems(Update, apply_style_assets);
+
+        let narrow = parse_style(b"(props: [Width(Px(10.0))])").unwrap();
+        let handle = app
+            .world
+            .resource_mut::<Assets<StyleAsset>>()
+            .add(StyleAsset(narrow.clone()));
+        let entity = app
+            .world
+            .spawn((NodeBundle::default(), ElementStyleAsset(handle.clone())))
+            .id();
+        app.update();
+        let styles = app.world.get::<ElementStyles>(entity).unwrap();
+        assert!(styles.iter().any(|s| *s == narrow));
+
+        // Replacing the asset, as happens on reload, updates the element.
+        let wide = parse_style(b"(props: [Width(Px(20.0))])").unwrap();
+        app.world
+            .resource_mut::<Assets<StyleAsset>>()
+            .insert(&handle, StyleAsset(wide.clone()));
+        app.update();
+        let styles = app.world.get::<ElementStyles>(entity).unwrap();
+        assert!(styles.iter().any(|s| *s == wide));
+        assert!(!styles.iter().any(|s| *s == narrow));
+    }
+}
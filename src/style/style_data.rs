@@ -0,0 +1,662 @@
+use bevy::{
+    asset::AssetPath,
+    math::Vec3,
+    prelude::*,
+    text::BreakLineOn,
+    ui::{self, ZIndex},
+};
+use bevy_color::SRgba;
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::Cursor;
+
+use super::{
+    background_layers::BackgroundLayer,
+    box_shadow::ShadowStyle,
+    content_visibility::ContentVisibility,
+    gradient::LinearGradient,
+    hit_test::HitTest,
+    image_fit::ImageFit,
+    selector::Selector,
+    style_props::{CurrentColorTarget, PointerEvents, StyleProp, StyleSet},
+    writing_mode::WritingMode,
+};
+
+/// Serializable form of a [`StyleSet`], which is the format of style asset files.
+///
+/// ```ron
+/// (
+///     props: [
+///         BackgroundColor(Some((red: 0.2, green: 0.2, blue: 0.2, alpha: 1.0))),
+///         Padding((left: Px(4.0), right: Px(4.0), top: Px(2.0), bottom: Px(2.0))),
+///     ],
+///     selectors: [
+///         (":hover", [BackgroundColor(Some((red: 0.3, green: 0.3, blue: 0.3, alpha: 1.0)))]),
+///     ],
+/// )
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct StyleData {
+    #[serde(default)]
+    pub(crate) props: Vec<StylePropData>,
+
+    /// Conditional styles, as pairs of selector expression and properties.
+    #[serde(default)]
+    pub(crate) selectors: Vec<(String, Vec<StylePropData>)>,
+}
+
+impl TryFrom<&StyleSet> for StyleData {
+    type Error = String;
+
+    /// Convert a [`StyleSet`] into its data form. Fails if any of the properties have no
+    /// serializable form.
+    fn try_from(style: &StyleSet) -> Result<Self, Self::Error> {
+        fn convert(props: &[StyleProp]) -> Result<Vec<StylePropData>, String> {
+            props
+                .iter()
+                .map(|prop| {
+                    StylePropData::from_prop(prop)
+                        .ok_or_else(|| format!("style property can't be serialized: {:?}", prop))
+                })
+                .collect()
+        }
+        Ok(StyleData {
+            props: convert(&style.props)?,
+            selectors: style
+                .selectors
+                .iter()
+                .map(|(selector, props)| Ok((selector.to_string(), convert(props)?)))
+                .collect::<Result<_, String>>()?,
+        })
+    }
+}
+
+impl TryFrom<StyleData> for StyleSet {
+    type Error = String;
+
+    /// Convert the data form of a style into a [`StyleSet`]. Fails if any of the selector
+    /// expressions can't be parsed.
+    fn try_from(data: StyleData) -> Result<Self, Self::Error> {
+        let mut selectors = Vec::with_capacity(data.selectors.len());
+        for (expr, props) in data.selectors {
+            let selector = expr
+                .parse::<Selector>()
+                .map_err(|err| format!("invalid selector '{}': {}", expr, err))?;
+            selectors.push((
+                Box::new(selector),
+                props.into_iter().map(StylePropData::into_prop).collect(),
+            ));
+        }
+        Ok(StyleSet {
+            props: data
+                .props
+                .into_iter()
+                .map(StylePropData::into_prop)
+                .collect(),
+            selectors,
+        })
+    }
+}
+
+impl Serialize for StyleSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        StyleData::try_from(self)
+            .map_err(ser::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for StyleSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        StyleSet::try_from(StyleData::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
+
+/// Serializable form of a [`BackgroundLayer`]. Layers which hold an image handle can't be
+/// serialized.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) enum BackgroundLayerData {
+    Color(SRgba),
+    Image(String),
+}
+
+/// Serializable form of a [`ShadowStyle`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ShadowData {
+    color: SRgba,
+    x_offset: ui::Val,
+    y_offset: ui::Val,
+    blur_radius: ui::Val,
+    spread_radius: ui::Val,
+}
+
+/// Serializable form of a [`ZIndex`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub(crate) enum ZIndexData {
+    Local(i32),
+    Global(i32),
+}
+
+/// Serializable form of a [`StyleProp`]. Variants have the same names as the style
+/// properties they represent. Colors are stored as sRGB, asset references as paths, and
+/// vectors as arrays. Properties which refer to runtime objects, such as image handles and
+/// transition timing functions, have no serializable form; neither do cursor images and
+/// offsets, which can't be applied yet, so a style file which uses them fails to load.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) enum StylePropData {
+    Reset,
+
+    BackgroundImage(Option<String>),
+    /// Angle in degrees, and color stops.
+    BackgroundGradient(f32, Vec<(ui::Val, SRgba)>),
+    BackgroundLayers(Vec<BackgroundLayerData>),
+    BackgroundColor(Option<SRgba>),
+    BorderColor(Option<SRgba>),
+    Color(Option<SRgba>),
+    CurrentColor(CurrentColorTarget),
+    AccentColor(Option<SRgba>),
+    UseAccentColor(CurrentColorTarget),
+
+    ImageFit(ImageFit),
+
+    HitSlop(Option<f32>),
+    HitTest(HitTest),
+
+    Spin(Option<f32>),
+    Opacity(f32),
+
+    ContentVisibility(ContentVisibility),
+    WritingMode(WritingMode),
+
+    ScrollbarWidth(Option<f32>),
+    ScrollbarThumbColor(Option<SRgba>),
+    ScrollbarTrackColor(Option<SRgba>),
+
+    CaretColor(Option<SRgba>),
+    SelectionColor(Option<SRgba>),
+
+    ZIndex(Option<ZIndexData>),
+    Order(i32),
+
+    Display(ui::Display),
+    Position(ui::PositionType),
+    Sticky,
+    Overflow(ui::OverflowAxis),
+    OverflowX(ui::OverflowAxis),
+    OverflowY(ui::OverflowAxis),
+    Direction(ui::Direction),
+
+    Left(ui::Val),
+    Right(ui::Val),
+    Top(ui::Val),
+    Bottom(ui::Val),
+
+    Width(ui::Val),
+    Height(ui::Val),
+    MinWidth(ui::Val),
+    MinHeight(ui::Val),
+    MaxWidth(ui::Val),
+    MaxHeight(ui::Val),
+
+    Margin(ui::UiRect),
+    MarginLeft(ui::Val),
+    MarginRight(ui::Val),
+    MarginTop(ui::Val),
+    MarginBottom(ui::Val),
+
+    Padding(ui::UiRect),
+    PaddingLeft(ui::Val),
+    PaddingRight(ui::Val),
+    PaddingTop(ui::Val),
+    PaddingBottom(ui::Val),
+
+    Border(ui::UiRect),
+    BorderLeft(ui::Val),
+    BorderRight(ui::Val),
+    BorderTop(ui::Val),
+    BorderBottom(ui::Val),
+
+    FlexDirection(ui::FlexDirection),
+    FlexWrap(ui::FlexWrap),
+    FlexGrow(f32),
+    FlexShrink(f32),
+    FlexBasis(ui::Val),
+    RowGap(ui::Val),
+    ColumnGap(ui::Val),
+    Gap(ui::Val),
+
+    AlignItems(ui::AlignItems),
+    AlignSelf(ui::AlignSelf),
+    AlignContent(ui::AlignContent),
+    JustifyItems(ui::JustifyItems),
+    JustifySelf(ui::JustifySelf),
+    JustifyContent(ui::JustifyContent),
+
+    GridAutoFlow(ui::GridAutoFlow),
+    GridTemplateRows(Vec<ui::RepeatedGridTrack>),
+    GridTemplateColumns(Vec<ui::RepeatedGridTrack>),
+    GridAutoRows(Vec<ui::GridTrack>),
+    GridAutoColumns(Vec<ui::GridTrack>),
+    GridRow(ui::GridPlacement),
+    GridRowStart(i16),
+    GridRowSpan(u16),
+    GridRowEnd(i16),
+    GridColumn(ui::GridPlacement),
+    GridColumnStart(i16),
+    GridColumnSpan(u16),
+    GridColumnEnd(i16),
+
+    PointerEvents(PointerEvents),
+
+    Font(Option<String>),
+    FontSize(f32),
+    TextAlign(JustifyText),
+    LineBreak(BreakLineOn),
+    LineHeight(f32),
+    LetterSpacing(f32),
+
+    OutlineColor(Option<SRgba>),
+    OutlineWidth(ui::Val),
+    OutlineOffset(ui::Val),
+
+    BorderRadius(ui::Val),
+    BorderRadiusTopLeft(ui::Val),
+    BorderRadiusTopRight(ui::Val),
+    BorderRadiusBottomLeft(ui::Val),
+    BorderRadiusBottomRight(ui::Val),
+
+    BoxShadow(Vec<ShadowData>),
+
+    Cursor(Cursor),
+
+    Scale(f32),
+    ScaleX(f32),
+    ScaleY(f32),
+    Rotation(f32),
+    Translation([f32; 3]),
+    TransformOrigin(ui::Val, ui::Val),
+}
+
+fn to_color(color: SRgba) -> Color {
+    Color::rgba(color.red, color.green, color.blue, color.alpha)
+}
+
+fn from_color(color: Color) -> SRgba {
+    let [red, green, blue, alpha] = color.as_rgba_f32();
+    SRgba::new(red, green, blue, alpha)
+}
+
+impl StylePropData {
+    /// Convert the data form of a property into a [`StyleProp`].
+    pub(crate) fn into_prop(self) -> StyleProp {
+        match self {
+            StylePropData::Reset => StyleProp::Reset,
+            StylePropData::BackgroundImage(path) => {
+                StyleProp::BackgroundImage(path.map(AssetPath::from))
+            }
+            StylePropData::BackgroundGradient(angle, stops) => {
+                StyleProp::BackgroundGradient(LinearGradient {
+                    angle,
+                    stops: stops
+                        .into_iter()
+                        .map(|(pos, color)| (pos, color.into()))
+                        .collect(),
+                })
+            }
+            StylePropData::BackgroundLayers(layers) => StyleProp::BackgroundLayers(
+                layers
+                    .into_iter()
+                    .map(|layer| match layer {
+                        BackgroundLayerData::Color(color) => {
+                            BackgroundLayer::Color(to_color(color))
+                        }
+                        BackgroundLayerData::Image(path) => BackgroundLayer::Image(path.into()),
+                    })
+                    .collect(),
+            ),
+            StylePropData::BackgroundColor(color) => {
+                StyleProp::BackgroundColor(color.map(to_color))
+            }
+            StylePropData::BorderColor(color) => StyleProp::BorderColor(color.map(to_color)),
+            StylePropData::Color(color) => StyleProp::Color(color.map(to_color)),
+            StylePropData::CurrentColor(target) => StyleProp::CurrentColor(target),
+            StylePropData::AccentColor(color) => StyleProp::AccentColor(color.map(to_color)),
+            StylePropData::UseAccentColor(target) => StyleProp::UseAccentColor(target),
+            StylePropData::ImageFit(fit) => StyleProp::ImageFit(fit),
+            StylePropData::HitSlop(slop) => StyleProp::HitSlop(slop),
+            StylePropData::HitTest(hit_test) => StyleProp::HitTest(hit_test),
+            StylePropData::Spin(speed) => StyleProp::Spin(speed),
+            StylePropData::Opacity(opacity) => StyleProp::Opacity(opacity),
+            StylePropData::ContentVisibility(cv) => StyleProp::ContentVisibility(cv),
+            StylePropData::WritingMode(mode) => StyleProp::WritingMode(mode),
+            StylePropData::ScrollbarWidth(width) => StyleProp::ScrollbarWidth(width),
+            StylePropData::ScrollbarThumbColor(color) => {
+                StyleProp::ScrollbarThumbColor(color.map(to_color))
+            }
+            StylePropData::ScrollbarTrackColor(color) => {
+                StyleProp::ScrollbarTrackColor(color.map(to_color))
+            }
+            StylePropData::CaretColor(color) => StyleProp::CaretColor(color.map(to_color)),
+            StylePropData::SelectionColor(color) => StyleProp::SelectionColor(color.map(to_color)),
+            StylePropData::ZIndex(z) => StyleProp::ZIndex(z.map(|z| match z {
+                ZIndexData::Local(z) => ZIndex::Local(z),
+                ZIndexData::Global(z) => ZIndex::Global(z),
+            })),
+            StylePropData::Order(order) => StyleProp::Order(order),
+            StylePropData::Display(d) => StyleProp::Display(d),
+            StylePropData::Position(p) => StyleProp::Position(p),
+            StylePropData::Sticky => StyleProp::Sticky,
+            StylePropData::Overflow(o) => StyleProp::Overflow(o),
+            StylePropData::OverflowX(o) => StyleProp::OverflowX(o),
+            StylePropData::OverflowY(o) => StyleProp::OverflowY(o),
+            StylePropData::Direction(d) => StyleProp::Direction(d),
+            StylePropData::Left(v) => StyleProp::Left(v),
+            StylePropData::Right(v) => StyleProp::Right(v),
+            StylePropData::Top(v) => StyleProp::Top(v),
+            StylePropData::Bottom(v) => StyleProp::Bottom(v),
+            StylePropData::Width(v) => StyleProp::Width(v),
+            StylePropData::Height(v) => StyleProp::Height(v),
+            StylePropData::MinWidth(v) => StyleProp::MinWidth(v),
+            StylePropData::MinHeight(v) => StyleProp::MinHeight(v),
+            StylePropData::MaxWidth(v) => StyleProp::MaxWidth(v),
+            StylePropData::MaxHeight(v) => StyleProp::MaxHeight(v),
+            StylePropData::Margin(r) => StyleProp::Margin(r),
+            StylePropData::MarginLeft(v) => StyleProp::MarginLeft(v),
+            StylePropData::MarginRight(v) => StyleProp::MarginRight(v),
+            StylePropData::MarginTop(v) => StyleProp::MarginTop(v),
+            StylePropData::MarginBottom(v) => StyleProp::MarginBottom(v),
+            StylePropData::Padding(r) => StyleProp::Padding(r),
+            StylePropData::PaddingLeft(v) => StyleProp::PaddingLeft(v),
+            StylePropData::PaddingRight(v) => StyleProp::PaddingRight(v),
+            StylePropData::PaddingTop(v) => StyleProp::PaddingTop(v),
+            StylePropData::PaddingBottom(v) => StyleProp::PaddingBottom(v),
+            StylePropData::Border(r) => StyleProp::Border(r),
+            StylePropData::BorderLeft(v) => StyleProp::BorderLeft(v),
+            StylePropData::BorderRight(v) => StyleProp::BorderRight(v),
+            StylePropData::BorderTop(v) => StyleProp::BorderTop(v),
+            StylePropData::BorderBottom(v) => StyleProp::BorderBottom(v),
+            StylePropData::FlexDirection(d) => StyleProp::FlexDirection(d),
+            StylePropData::FlexWrap(w) => StyleProp::FlexWrap(w),
+            StylePropData::FlexGrow(n) => StyleProp::FlexGrow(n),
+            StylePropData::FlexShrink(n) => StyleProp::FlexShrink(n),
+            StylePropData::FlexBasis(v) => StyleProp::FlexBasis(v),
+            StylePropData::RowGap(v) => StyleProp::RowGap(v),
+            StylePropData::ColumnGap(v) => StyleProp::ColumnGap(v),
+            StylePropData::Gap(v) => StyleProp::Gap(v),
+            StylePropData::AlignItems(a) => StyleProp::AlignItems(a),
+            StylePropData::AlignSelf(a) => StyleProp::AlignSelf(a),
+            StylePropData::AlignContent(a) => StyleProp::AlignContent(a),
+            StylePropData::JustifyItems(j) => StyleProp::JustifyItems(j),
+            StylePropData::JustifySelf(j) => StyleProp::JustifySelf(j),
+            StylePropData::JustifyContent(j) => StyleProp::JustifyContent(j),
+            StylePropData::GridAutoFlow(f) => StyleProp::GridAutoFlow(f),
+            StylePropData::GridTemplateRows(t) => StyleProp::GridTemplateRows(t),
+            StylePropData::GridTemplateColumns(t) => StyleProp::GridTemplateColumns(t),
+            StylePropData::GridAutoRows(t) => StyleProp::GridAutoRows(t),
+            StylePropData::GridAutoColumns(t) => StyleProp::GridAutoColumns(t),
+            StylePropData::GridRow(p) => StyleProp::GridRow(p),
+            StylePropData::GridRowStart(n) => StyleProp::GridRowStart(n),
+            StylePropData::GridRowSpan(n) => StyleProp::GridRowSpan(n),
+            StylePropData::GridRowEnd(n) => StyleProp::GridRowEnd(n),
+            StylePropData::GridColumn(p) => StyleProp::GridColumn(p),
+            StylePropData::GridColumnStart(n) => StyleProp::GridColumnStart(n),
+            StylePropData::GridColumnSpan(n) => StyleProp::GridColumnSpan(n),
+            StylePropData::GridColumnEnd(n) => StyleProp::GridColumnEnd(n),
+            StylePropData::PointerEvents(pe) => StyleProp::PointerEvents(pe),
+            StylePropData::Font(path) => StyleProp::Font(path.map(AssetPath::from)),
+            StylePropData::FontSize(size) => StyleProp::FontSize(size),
+            StylePropData::TextAlign(align) => StyleProp::TextAlign(align),
+            StylePropData::LineBreak(lb) => StyleProp::LineBreak(lb),
+            StylePropData::LineHeight(height) => StyleProp::LineHeight(height),
+            StylePropData::LetterSpacing(spacing) => StyleProp::LetterSpacing(spacing),
+            StylePropData::OutlineColor(color) => StyleProp::OutlineColor(color.map(to_color)),
+            StylePropData::OutlineWidth(v) => StyleProp::OutlineWidth(v),
+            StylePropData::OutlineOffset(v) => StyleProp::OutlineOffset(v),
+            StylePropData::BorderRadius(v) => StyleProp::BorderRadius(v),
+            StylePropData::BorderRadiusTopLeft(v) => StyleProp::BorderRadiusTopLeft(v),
+            StylePropData::BorderRadiusTopRight(v) => StyleProp::BorderRadiusTopRight(v),
+            StylePropData::BorderRadiusBottomLeft(v) => StyleProp::BorderRadiusBottomLeft(v),
+            StylePropData::BorderRadiusBottomRight(v) => StyleProp::BorderRadiusBottomRight(v),
+            StylePropData::BoxShadow(shadows) => StyleProp::BoxShadow(
+                shadows
+                    .into_iter()
+                    .map(|shadow| ShadowStyle {
+                        color: to_color(shadow.color),
+                        x_offset: shadow.x_offset,
+                        y_offset: shadow.y_offset,
+                        blur_radius: shadow.blur_radius,
+                        spread_radius: shadow.spread_radius,
+                    })
+                    .collect(),
+            ),
+            StylePropData::Cursor(cursor) => StyleProp::Cursor(cursor),
+            StylePropData::Scale(s) => StyleProp::Scale(s),
+            StylePropData::ScaleX(s) => StyleProp::ScaleX(s),
+            StylePropData::ScaleY(s) => StyleProp::ScaleY(s),
+            StylePropData::Rotation(r) => StyleProp::Rotation(r),
+            StylePropData::Translation(t) => StyleProp::Translation(Vec3::from(t)),
+            StylePropData::TransformOrigin(x, y) => StyleProp::TransformOrigin(x, y),
+        }
+    }
+
+    /// Convert a [`StyleProp`] into its data form, or `None` if it has no serializable form.
+    pub(crate) fn from_prop(prop: &StyleProp) -> Option<Self> {
+        Some(match prop {
+            StyleProp::Reset => StylePropData::Reset,
+            StyleProp::BackgroundImage(path) => {
+                StylePropData::BackgroundImage(path.as_ref().map(AssetPath::to_string))
+            }
+            StyleProp::BackgroundImageHandle(_) => return None,
+            StyleProp::BackgroundGradient(gradient) => StylePropData::BackgroundGradient(
+                gradient.angle,
+                gradient
+                    .stops
+                    .iter()
+                    .map(|(pos, color)| (*pos, (*color).into()))
+                    .collect(),
+            ),
+            StyleProp::BackgroundLayers(layers) => StylePropData::BackgroundLayers(
+                layers
+                    .iter()
+                    .map(|layer| match layer {
+                        BackgroundLayer::Color(color) => {
+                            Some(BackgroundLayerData::Color(from_color(*color)))
+                        }
+                        BackgroundLayer::Image(path) => {
+                            Some(BackgroundLayerData::Image(path.to_string()))
+                        }
+                        BackgroundLayer::ImageHandle(_) => None,
+                    })
+                    .collect::<Option<_>>()?,
+            ),
+            StyleProp::BackgroundColor(color) => {
+                StylePropData::BackgroundColor(color.map(from_color))
+            }
+            StyleProp::BorderColor(color) => StylePropData::BorderColor(color.map(from_color)),
+            StyleProp::Color(color) => StylePropData::Color(color.map(from_color)),
+            StyleProp::CurrentColor(target) => StylePropData::CurrentColor(*target),
+            StyleProp::AccentColor(color) => StylePropData::AccentColor(color.map(from_color)),
+            StyleProp::UseAccentColor(target) => StylePropData::UseAccentColor(*target),
+            StyleProp::ImageScale(_) => return None,
+            StyleProp::ImageFit(fit) => StylePropData::ImageFit(*fit),
+            StyleProp::HitSlop(slop) => StylePropData::HitSlop(*slop),
+            StyleProp::HitTest(hit_test) => StylePropData::HitTest(*hit_test),
+            StyleProp::Spin(speed) => StylePropData::Spin(*speed),
+            StyleProp::Opacity(opacity) => StylePropData::Opacity(*opacity),
+            StyleProp::ContentVisibility(cv) => StylePropData::ContentVisibility(*cv),
+            StyleProp::WritingMode(mode) => StylePropData::WritingMode(*mode),
+            StyleProp::ScrollbarWidth(width) => StylePropData::ScrollbarWidth(*width),
+            StyleProp::ScrollbarThumbColor(color) => {
+                StylePropData::ScrollbarThumbColor(color.map(from_color))
+            }
+            StyleProp::ScrollbarTrackColor(color) => {
+                StylePropData::ScrollbarTrackColor(color.map(from_color))
+            }
+            StyleProp::CaretColor(color) => StylePropData::CaretColor(color.map(from_color)),
+            StyleProp::SelectionColor(color) => {
+                StylePropData::SelectionColor(color.map(from_color))
+            }
+            StyleProp::ZIndex(z) => StylePropData::ZIndex(z.map(|z| match z {
+                ZIndex::Local(z) => ZIndexData::Local(z),
+                ZIndex::Global(z) => ZIndexData::Global(z),
+            })),
+            StyleProp::Order(order) => StylePropData::Order(*order),
+            StyleProp::Display(d) => StylePropData::Display(*d),
+            StyleProp::Position(p) => StylePropData::Position(*p),
+            StyleProp::Sticky => StylePropData::Sticky,
+            StyleProp::Overflow(o) => StylePropData::Overflow(*o),
+            StyleProp::OverflowX(o) => StylePropData::OverflowX(*o),
+            StyleProp::OverflowY(o) => StylePropData::OverflowY(*o),
+            StyleProp::Direction(d) => StylePropData::Direction(*d),
+            StyleProp::Left(v) => StylePropData::Left(*v),
+            StyleProp::Right(v) => StylePropData::Right(*v),
+            StyleProp::Top(v) => StylePropData::Top(*v),
+            StyleProp::Bottom(v) => StylePropData::Bottom(*v),
+            StyleProp::Width(v) => StylePropData::Width(*v),
+            StyleProp::Height(v) => StylePropData::Height(*v),
+            StyleProp::MinWidth(v) => StylePropData::MinWidth(*v),
+            StyleProp::MinHeight(v) => StylePropData::MinHeight(*v),
+            StyleProp::MaxWidth(v) => StylePropData::MaxWidth(*v),
+            StyleProp::MaxHeight(v) => StylePropData::MaxHeight(*v),
+            StyleProp::Margin(r) => StylePropData::Margin(*r),
+            StyleProp::MarginLeft(v) => StylePropData::MarginLeft(*v),
+            StyleProp::MarginRight(v) => StylePropData::MarginRight(*v),
+            StyleProp::MarginTop(v) => StylePropData::MarginTop(*v),
+            StyleProp::MarginBottom(v) => StylePropData::MarginBottom(*v),
+            StyleProp::Padding(r) => StylePropData::Padding(*r),
+            StyleProp::PaddingLeft(v) => StylePropData::PaddingLeft(*v),
+            StyleProp::PaddingRight(v) => StylePropData::PaddingRight(*v),
+            StyleProp::PaddingTop(v) => StylePropData::PaddingTop(*v),
+            StyleProp::PaddingBottom(v) => StylePropData::PaddingBottom(*v),
+            StyleProp::Border(r) => StylePropData::Border(*r),
+            StyleProp::BorderLeft(v) => StylePropData::BorderLeft(*v),
+            StyleProp::BorderRight(v) => StylePropData::BorderRight(*v),
+            StyleProp::BorderTop(v) => StylePropData::BorderTop(*v),
+            StyleProp::BorderBottom(v) => StylePropData::BorderBottom(*v),
+            StyleProp::FlexDirection(d) => StylePropData::FlexDirection(*d),
+            StyleProp::FlexWrap(w) => StylePropData::FlexWrap(*w),
+            StyleProp::FlexGrow(n) => StylePropData::FlexGrow(*n),
+            StyleProp::FlexShrink(n) => StylePropData::FlexShrink(*n),
+            StyleProp::FlexBasis(v) => StylePropData::FlexBasis(*v),
+            StyleProp::RowGap(v) => StylePropData::RowGap(*v),
+            StyleProp::ColumnGap(v) => StylePropData::ColumnGap(*v),
+            StyleProp::Gap(v) => StylePropData::Gap(*v),
+            StyleProp::AlignItems(a) => StylePropData::AlignItems(*a),
+            StyleProp::AlignSelf(a) => StylePropData::AlignSelf(*a),
+            StyleProp::AlignContent(a) => StylePropData::AlignContent(*a),
+            StyleProp::JustifyItems(j) => StylePropData::JustifyItems(*j),
+            StyleProp::JustifySelf(j) => StylePropData::JustifySelf(*j),
+            StyleProp::JustifyContent(j) => StylePropData::JustifyContent(*j),
+            StyleProp::GridAutoFlow(f) => StylePropData::GridAutoFlow(*f),
+            StyleProp::GridTemplateRows(t) => StylePropData::GridTemplateRows(t.clone()),
+            StyleProp::GridTemplateColumns(t) => StylePropData::GridTemplateColumns(t.clone()),
+            StyleProp::GridAutoRows(t) => StylePropData::GridAutoRows(t.clone()),
+            StyleProp::GridAutoColumns(t) => StylePropData::GridAutoColumns(t.clone()),
+            StyleProp::GridRow(p) => StylePropData::GridRow(*p),
+            StyleProp::GridRowStart(n) => StylePropData::GridRowStart(*n),
+            StyleProp::GridRowSpan(n) => StylePropData::GridRowSpan(*n),
+            StyleProp::GridRowEnd(n) => StylePropData::GridRowEnd(*n),
+            StyleProp::GridColumn(p) => StylePropData::GridColumn(*p),
+            StyleProp::GridColumnStart(n) => StylePropData::GridColumnStart(*n),
+            StyleProp::GridColumnSpan(n) => StylePropData::GridColumnSpan(*n),
+            StyleProp::GridColumnEnd(n) => StylePropData::GridColumnEnd(*n),
+            StyleProp::PointerEvents(pe) => StylePropData::PointerEvents(*pe),
+            StyleProp::Font(path) => StylePropData::Font(path.as_ref().map(AssetPath::to_string)),
+            StyleProp::FontSize(size) => StylePropData::FontSize(*size),
+            StyleProp::TextAlign(align) => StylePropData::TextAlign(*align),
+            StyleProp::LineBreak(lb) => StylePropData::LineBreak(*lb),
+            StyleProp::LineHeight(height) => StylePropData::LineHeight(*height),
+            StyleProp::LetterSpacing(spacing) => StylePropData::LetterSpacing(*spacing),
+            StyleProp::OutlineColor(color) => StylePropData::OutlineColor(color.map(from_color)),
+            StyleProp::OutlineWidth(v) => StylePropData::OutlineWidth(*v),
+            StyleProp::OutlineOffset(v) => StylePropData::OutlineOffset(*v),
+            StyleProp::BorderRadius(v) => StylePropData::BorderRadius(*v),
+            StyleProp::BorderRadiusTopLeft(v) => StylePropData::BorderRadiusTopLeft(*v),
+            StyleProp::BorderRadiusTopRight(v) => StylePropData::BorderRadiusTopRight(*v),
+            StyleProp::BorderRadiusBottomLeft(v) => StylePropData::BorderRadiusBottomLeft(*v),
+            StyleProp::BorderRadiusBottomRight(v) => StylePropData::BorderRadiusBottomRight(*v),
+            StyleProp::BoxShadow(shadows) => StylePropData::BoxShadow(
+                shadows
+                    .iter()
+                    .map(|shadow| ShadowData {
+                        color: from_color(shadow.color),
+                        x_offset: shadow.x_offset,
+                        y_offset: shadow.y_offset,
+                        blur_radius: shadow.blur_radius,
+                        spread_radius: shadow.spread_radius,
+                    })
+                    .collect(),
+            ),
+            StyleProp::Cursor(cursor) => StylePropData::Cursor(*cursor),
+            StyleProp::CursorImage(_) | StyleProp::CursorOffset(_) => return None,
+            StyleProp::Scale(s) => StylePropData::Scale(*s),
+            StyleProp::ScaleX(s) => StylePropData::ScaleX(*s),
+            StyleProp::ScaleY(s) => StylePropData::ScaleY(*s),
+            StyleProp::Rotation(r) => StylePropData::Rotation(*r),
+            StyleProp::Translation(t) => StylePropData::Translation(t.to_array()),
+            StyleProp::TransformOrigin(x, y) => StylePropData::TransformOrigin(*x, *y),
+            StyleProp::Transition(_) => return None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use bevy::ui::{GridTrack, RepeatedGridTrack};
+    use bevy_color::LinearRgba;
+
+    use super::*;
+    use crate::{StyleHandle, Transition};
+
+    #[test]
+    fn test_ron_round_trip() {
+        let style = StyleHandle::build(|ss| {
+            ss.background_color(Color::rgba(0.1, 0.2, 0.3, 1.))
+                .border_color("#8080ff")
+                .border(1)
+                .border_radius(4)
+                .padding(ui::UiRect::axes(Val::Px(8.), Val::Px(4.)))
+                .z_index(ZIndex::Global(10))
+                .font(Some("fonts/Inter.ttf".into()))
+                .box_shadow(Color::BLACK, 0, 2, 4, 0)
+                .background_gradient(90., [(Val::Auto, LinearRgba::BLACK)])
+                .grid_template_columns(vec![RepeatedGridTrack::flex(3, 1.)])
+                .grid_auto_rows(vec![GridTrack::px(20.)])
+                .translation(Vec3::new(1., 2., 0.))
+                .selector(":hover", |ss| ss.background_color(Color::WHITE))
+                .selector(".dark > :nth-child(odd)", |ss| {
+                    ss.color(Color::WHITE).width(Val::Percent(50.))
+                })
+                .selector("[variant=primary]", |ss| ss.border_color(Color::BLUE))
+        });
+
+        let ron = style.to_ron().unwrap();
+        let parsed = StyleHandle::from_ron(&ron).unwrap();
+        assert_eq!(
+            format!("{:?}", parsed.0.props),
+            format!("{:?}", style.0.props)
+        );
+        assert_eq!(
+            format!("{:?}", parsed.0.selectors),
+            format!("{:?}", style.0.selectors)
+        );
+        assert_eq!(parsed.to_ron().unwrap(), ron);
+    }
+
+    #[test]
+    fn test_unserializable() {
+        let style = StyleHandle::build(|ss| ss.background_image_handle(Some(Handle::default())));
+        assert!(style.to_ron().is_err());
+        let style = StyleHandle::build(|ss| {
+            ss.selector(":hover", |ss| ss.transition(&[Transition::default()]))
+        });
+        assert!(style.to_ron().is_err());
+        let style = StyleHandle(Arc::new(StyleSet {
+            props: vec![StyleProp::CursorImage("cursors/grab.png".into())],
+            selectors: Vec::new(),
+        }));
+        assert!(style.to_ron().is_err());
+    }
+}
@@ -1,10 +1,15 @@
 #![allow(missing_docs)]
 
 use super::{
-    builder::StyleBuilder, computed::ComputedStyle, selector_matcher::SelectorMatcher,
+    builder::StyleBuilder,
+    computed::ComputedStyle,
+    selector_matcher::SelectorMatcher,
+    style_asset::{parse_style, StyleAssetError},
     style_props::StyleSet,
 };
 use bevy::prelude::*;
+use bevy::text::BreakLineOn;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::sync::Arc;
 
 /// A sharable reference to a collection of UI style properties.
@@ -23,6 +28,22 @@ impl StyleHandle {
         }))
     }
 
+    /// Serialize this style to RON, in the format used by [`StyleAsset`] files. Fails if the
+    /// style contains properties which have no serializable form, such as image handles or
+    /// transitions.
+    ///
+    /// [`StyleAsset`]: crate::StyleAsset
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+    }
+
+    /// Parse a style from RON, in the format used by [`StyleAsset`] files.
+    ///
+    /// [`StyleAsset`]: crate::StyleAsset
+    pub fn from_ron(ron: &str) -> Result<Self, StyleAssetError> {
+        parse_style(ron.as_bytes())
+    }
+
     /// Merge the style properties into a computed `Style` object.
     pub fn apply_to(
         &self,
@@ -47,6 +68,23 @@ impl StyleHandle {
     pub fn uses_focus_within(&self) -> bool {
         self.0.as_ref().uses_focus_within()
     }
+
+    /// Return whether any of the selectors depend on the element's position among its siblings.
+    pub fn uses_child_position(&self) -> bool {
+        self.0.as_ref().uses_child_position()
+    }
+}
+
+impl Serialize for StyleHandle {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for StyleHandle {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Self(Arc::new(StyleSet::deserialize(deserializer)?)))
+    }
 }
 
 impl PartialEq for StyleHandle {
@@ -70,26 +108,60 @@ pub struct ElementStyles {
 
     /// Whether any selectors use the :focus-within pseudo-class
     pub(crate) uses_focus_within: bool,
+
+    /// Whether any selectors use :first-child, :last-child or :nth-child
+    pub(crate) uses_child_position: bool,
+
+    /// Style loaded from a [`StyleAsset`], which is applied after `styles`.
+    ///
+    /// [`StyleAsset`]: crate::StyleAsset
+    pub(crate) asset: Option<StyleHandle>,
+
+    /// Style computed by a [`ReactiveStyle`], which is applied after `styles`. This is kept
+    /// separately so that updating the element's styles doesn't discard it.
+    ///
+    /// [`ReactiveStyle`]: crate::ReactiveStyle
+    pub(crate) reactive: Option<StyleHandle>,
 }
 
 impl ElementStyles {
     pub fn new(styles: &[StyleHandle]) -> Self {
-        let selector_depth = styles.iter().map(|s| s.depth()).max().unwrap_or(0);
-        let uses_hover = styles.iter().any(|s| s.uses_hover());
-        let uses_focus_within = styles.iter().any(|s| s.uses_focus_within());
-        Self {
+        let mut result = Self {
             styles: styles.to_vec(),
-            selector_depth,
-            uses_hover,
-            uses_focus_within,
-        }
+            ..default()
+        };
+        result.update_flags();
+        result
     }
 
     pub fn update(&mut self, styles: &[StyleHandle]) {
         self.styles = styles.to_vec();
-        self.selector_depth = self.styles.iter().map(|s| s.depth()).max().unwrap_or(0);
-        self.uses_hover = self.styles.iter().any(|s| s.uses_hover());
-        self.uses_focus_within = self.styles.iter().any(|s| s.uses_focus_within());
+        self.update_flags();
+    }
+
+    pub(crate) fn set_asset(&mut self, style: Option<StyleHandle>) {
+        self.asset = style;
+        self.update_flags();
+    }
+
+    pub(crate) fn set_reactive(&mut self, style: Option<StyleHandle>) {
+        self.reactive = style;
+        self.update_flags();
+    }
+
+    /// Iterate over all of the styles of the element, in the order they are applied.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &StyleHandle> {
+        self.styles
+            .iter()
+            .chain(self.asset.iter())
+            .chain(self.reactive.iter())
+    }
+
+    fn update_flags(&mut self) {
+        self.selector_depth = self.iter().map(|s| s.depth()).max().unwrap_or(0);
+        self.uses_hover = self.iter().any(|s| s.uses_hover());
+        self.uses_focus_within = self.iter().any(|s| s.uses_focus_within());
+        self.uses_child_position = self.iter().any(|s| s.uses_child_position());
     }
 }
 
@@ -107,4 +179,26 @@ pub struct TextStyles {
 
     /// Text color
     pub color: Option<Color>,
+
+    /// Horizontal alignment of lines of text.
+    pub alignment: Option<JustifyText>,
+
+    /// How text is wrapped.
+    pub line_break: Option<BreakLineOn>,
+
+    /// Height of a line of text, as a multiple of the font size.
+    pub line_height: Option<f32>,
+
+    /// Extra space between letters, in logical pixels.
+    pub letter_spacing: Option<f32>,
+
+    /// Combined opacity of this element and its ancestors. Although not a text style, it is
+    /// inherited the same way. `None` means fully opaque.
+    pub opacity: Option<f32>,
+
+    /// Accent color, used by [`ACCENT_COLOR`]. Although not a text style, it is inherited
+    /// the same way.
+    ///
+    /// [`ACCENT_COLOR`]: crate::ACCENT_COLOR
+    pub accent_color: Option<Color>,
 }
@@ -5,18 +5,32 @@ use bevy::{
     // ecs::entity::Entity,
     // math::{IVec2, Vec3},
     prelude::*,
+    text::BreakLineOn,
     ui,
 };
 
+use serde::{Deserialize, Serialize};
+
 use crate::Cursor;
 
 use super::{
-    builder::StyleBuilder, computed::{ComputedImage, ComputedStyle}, selector::Selector,
-    selector_matcher::SelectorMatcher, transition::Transition,
+    background_layers::BackgroundLayer,
+    border_radius::BorderRadius,
+    box_shadow::ShadowStyle,
+    builder::StyleBuilder,
+    computed::{ComputedImage, ComputedStyle},
+    content_visibility::ContentVisibility,
+    gradient::LinearGradient,
+    hit_test::HitTest,
+    image_fit::ImageFit,
+    selector::Selector,
+    selector_matcher::SelectorMatcher,
+    transition::Transition,
+    writing_mode::WritingMode,
 };
 
 /// Controls behavior of bevy_mod_picking
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum PointerEvents {
     /// No pointer events for this entity, or its children
     None,
@@ -24,22 +38,59 @@ pub enum PointerEvents {
     All,
 }
 
+/// Color properties which can be assigned the [`CURRENT_COLOR`] or [`ACCENT_COLOR`] value.
+///
+/// [`CURRENT_COLOR`]: crate::CURRENT_COLOR
+/// [`ACCENT_COLOR`]: crate::ACCENT_COLOR
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum CurrentColorTarget {
+    Background,
+    Border,
+    Outline,
+}
+
 /// The set of all style attributes. This is represented as a list of enums rather than
 /// a map so that attributes can be both strongly typed and represented sparsely.
 #[derive(Debug, Clone)]
 pub enum StyleProp {
+    Reset,
+
     BackgroundImage(Option<AssetPath<'static>>),
     BackgroundImageHandle(Option<Handle<Image>>),
+    BackgroundGradient(LinearGradient),
+    BackgroundLayers(Vec<BackgroundLayer>),
     BackgroundColor(Option<Color>),
     BorderColor(Option<Color>),
     Color(Option<Color>),
+    CurrentColor(CurrentColorTarget),
+    AccentColor(Option<Color>),
+    UseAccentColor(CurrentColorTarget),
 
     ImageScale(Option<bevy::prelude::ImageScaleMode>),
+    ImageFit(ImageFit),
+
+    HitSlop(Option<f32>),
+    HitTest(HitTest),
+
+    Spin(Option<f32>),
+    Opacity(f32),
+
+    ContentVisibility(ContentVisibility),
+    WritingMode(WritingMode),
+
+    ScrollbarWidth(Option<f32>),
+    ScrollbarThumbColor(Option<Color>),
+    ScrollbarTrackColor(Option<Color>),
+
+    CaretColor(Option<Color>),
+    SelectionColor(Option<Color>),
 
     ZIndex(Option<ui::ZIndex>),
+    Order(i32),
 
     Display(ui::Display),
     Position(ui::PositionType),
+    Sticky,
     Overflow(ui::OverflowAxis),
     OverflowX(ui::OverflowAxis),
     OverflowY(ui::OverflowAxis),
@@ -108,21 +159,35 @@ pub enum StyleProp {
     GridColumnSpan(u16),
     GridColumnEnd(i16),
 
-    // TODO:
-    // LineBreak(BreakLineOn),
     PointerEvents(PointerEvents),
 
     // Text
     Font(Option<AssetPath<'static>>),
     FontSize(f32),
+    TextAlign(JustifyText),
+    LineBreak(BreakLineOn),
+    LineHeight(f32),
+    LetterSpacing(f32),
 
     // Outlines
     OutlineColor(Option<Color>),
     OutlineWidth(ui::Val),
     OutlineOffset(ui::Val),
 
-    // TODO: Future planned features
+    // Corner radii
+    BorderRadius(ui::Val),
+    BorderRadiusTopLeft(ui::Val),
+    BorderRadiusTopRight(ui::Val),
+    BorderRadiusBottomLeft(ui::Val),
+    BorderRadiusBottomRight(ui::Val),
+
+    // Shadows
+    BoxShadow(Vec<ShadowStyle>),
+
+    // Cursor
     Cursor(Cursor),
+
+    // TODO: Future planned features
     CursorImage(AssetPath<'static>),
     CursorOffset(IVec2),
 
@@ -132,6 +197,7 @@ pub enum StyleProp {
     ScaleY(f32),
     Rotation(f32),
     Translation(Vec3),
+    TransformOrigin(ui::Val, ui::Val),
 
     // Transitions
     Transition(Vec<Transition>),
@@ -189,6 +255,11 @@ impl StyleSet {
         self.selectors.iter().any(|s| s.0.uses_focus_within())
     }
 
+    /// Return whether any of the selectors depend on the element's position among its siblings.
+    pub fn uses_child_position(&self) -> bool {
+        self.selectors.iter().any(|s| s.0.uses_child_position())
+    }
+
     /// Merge the style properties into a computed `Style` object.
     pub fn apply_to(
         &self,
@@ -207,35 +278,101 @@ impl StyleSet {
         }
     }
 
+    /// Apply the properties in the order they were added to the builder. Order matters, since
+    /// shorthand properties like `padding` overwrite the longhand properties set before them.
     fn apply_attrs_to(&self, attrs: &[StyleProp], computed: &mut ComputedStyle) {
         for attr in attrs.iter() {
             match attr {
+                StyleProp::Reset => {
+                    computed.reset();
+                }
                 StyleProp::BackgroundImage(image) => {
                     computed.image = image.as_ref().map(|i| ComputedImage::Path(i.clone()));
                 }
+                StyleProp::BackgroundLayers(layers) => {
+                    computed.background_layers.clone_from(layers);
+                }
                 StyleProp::BackgroundImageHandle(image) => {
                     computed.image = image.as_ref().map(|h| ComputedImage::Handle(h.clone()));
                 }
+                StyleProp::BackgroundGradient(gradient) => {
+                    computed.image = Some(ComputedImage::Gradient(gradient.clone()));
+                }
                 StyleProp::ImageScale(scale) => {
                     computed.image_scale = scale.clone();
                 },
+                StyleProp::ImageFit(fit) => {
+                    computed.image_fit = *fit;
+                }
+                StyleProp::HitSlop(slop) => {
+                    computed.hit_slop = *slop;
+                }
+                StyleProp::HitTest(hit_test) => {
+                    computed.hit_test = *hit_test;
+                }
+                StyleProp::Opacity(opacity) => {
+                    computed.opacity = Some(*opacity);
+                }
+                StyleProp::Spin(speed) => {
+                    computed.spin = *speed;
+                }
+                StyleProp::ContentVisibility(visibility) => {
+                    computed.content_visibility = *visibility;
+                }
+                StyleProp::WritingMode(mode) => {
+                    computed.writing_mode = *mode;
+                }
+                StyleProp::ScrollbarWidth(width) => {
+                    computed.scrollbar.width = *width;
+                }
+                StyleProp::ScrollbarThumbColor(color) => {
+                    computed.scrollbar.thumb_color = *color;
+                }
+                StyleProp::ScrollbarTrackColor(color) => {
+                    computed.scrollbar.track_color = *color;
+                }
+                StyleProp::CaretColor(color) => {
+                    computed.text_input.caret_color = *color;
+                }
+                StyleProp::SelectionColor(color) => {
+                    computed.text_input.selection_color = *color;
+                }
                 StyleProp::BackgroundColor(expr) => {
+                    computed.set_current_color(CurrentColorTarget::Background, false);
                     computed.background_color = *expr;
                 }
                 StyleProp::BorderColor(expr) => {
+                    computed.set_current_color(CurrentColorTarget::Border, false);
                     computed.border_color = *expr;
                 }
+                StyleProp::CurrentColor(target) => {
+                    computed.set_current_color(*target, true);
+                }
+                StyleProp::AccentColor(expr) => {
+                    computed.accent_color = *expr;
+                }
+                StyleProp::UseAccentColor(target) => {
+                    computed.set_accent_color(*target, true);
+                }
                 StyleProp::Color(expr) => {
                     computed.color = *expr;
                 }
                 StyleProp::ZIndex(expr) => {
                     computed.z_index = *expr;
                 }
+                StyleProp::Order(order) => {
+                    computed.order = *order;
+                }
                 StyleProp::Display(expr) => {
                     computed.style.display = *expr;
                 }
                 StyleProp::Position(expr) => {
                     computed.style.position_type = *expr;
+                    computed.sticky = false;
+                }
+                StyleProp::Sticky => {
+                    computed.style.position_type = ui::PositionType::Relative;
+                    computed.sticky = true;
                 }
                 StyleProp::OverflowX(expr) => {
                     computed.style.overflow.x = *expr;
@@ -422,6 +559,7 @@ impl StyleSet {
                 }
 
                 StyleProp::OutlineColor(expr) => {
+                    computed.set_current_color(CurrentColorTarget::Outline, false);
                     computed.outline_color = *expr;
                 }
 
@@ -433,6 +571,30 @@ impl StyleSet {
                     computed.outline_offset = *expr;
                 }
 
+                StyleProp::BorderRadius(expr) => {
+                    computed.border_radius = BorderRadius::all(*expr);
+                }
+
+                StyleProp::BorderRadiusTopLeft(expr) => {
+                    computed.border_radius.top_left = *expr;
+                }
+
+                StyleProp::BorderRadiusTopRight(expr) => {
+                    computed.border_radius.top_right = *expr;
+                }
+
+                StyleProp::BorderRadiusBottomLeft(expr) => {
+                    computed.border_radius.bottom_left = *expr;
+                }
+
+                StyleProp::BorderRadiusBottomRight(expr) => {
+                    computed.border_radius.bottom_right = *expr;
+                }
+
+                StyleProp::BoxShadow(shadows) => {
+                    computed.box_shadow.clone_from(shadows);
+                }
+
                 StyleProp::PointerEvents(expr) => {
                     computed.pickable = Some(*expr);
                 }
@@ -445,7 +607,25 @@ impl StyleSet {
                     computed.font_size = Some(*expr);
                 }
 
-                StyleProp::Cursor(_) => todo!(),
+                StyleProp::TextAlign(expr) => {
+                    computed.alignment = Some(*expr);
+                }
+
+                StyleProp::LineBreak(expr) => {
+                    computed.line_break = Some(*expr);
+                }
+
+                StyleProp::LineHeight(expr) => {
+                    computed.line_height = Some(*expr);
+                }
+
+                StyleProp::LetterSpacing(expr) => {
+                    computed.letter_spacing = Some(*expr);
+                }
+
+                StyleProp::Cursor(expr) => {
+                    computed.cursor = Some(*expr);
+                }
                 StyleProp::CursorImage(_) => todo!(),
                 StyleProp::CursorOffset(_) => todo!(),
 
@@ -465,9 +645,198 @@ impl StyleSet {
                 StyleProp::Translation(expr) => {
                     computed.translation = Some(*expr);
                 }
+                StyleProp::TransformOrigin(x, y) => {
+                    computed.transform_origin = Some((*x, *y));
+                }
 
                 StyleProp::Transition(trans) => computed.transitions.clone_from(&trans),
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{StyleHandle, ACCENT_COLOR, CURRENT_COLOR};
+
+    fn compute(handle: &StyleHandle, inherited_color: Option<Color>) -> ComputedStyle {
+        let mut computed = ComputedStyle::new();
+        computed.color = inherited_color;
+        handle.0.apply_attrs_to(&handle.0.props, &mut computed);
+        computed.resolve_current_color();
+        computed
+    }
+
+    #[test]
+    fn test_current_color() {
+        let style = StyleHandle::build(|ss| ss.color(Color::RED).border_color(CURRENT_COLOR));
+        let computed = compute(&style, None);
+        assert_eq!(computed.border_color, Some(Color::RED));
+        assert_eq!(computed.background_color, None);
+    }
+
+    #[test]
+    fn test_current_color_inherited() {
+        let style = StyleHandle::build(|ss| ss.border_color(CURRENT_COLOR));
+        let computed = compute(&style, Some(Color::GREEN));
+        assert_eq!(computed.border_color, Some(Color::GREEN));
+
+        // Inherited color changes, border follows.
+        let computed = compute(&style, Some(Color::BLUE));
+        assert_eq!(computed.border_color, Some(Color::BLUE));
+    }
+
+    #[test]
+    fn test_current_color_overridden() {
+        let style = StyleHandle::build(|ss| {
+            ss.color(Color::RED)
+                .border_color(CURRENT_COLOR)
+                .border_color(Color::BLUE)
+        });
+        let computed = compute(&style, None);
+        assert_eq!(computed.border_color, Some(Color::BLUE));
+    }
+
+    #[test]
+    fn test_accent_color() {
+        let style = StyleHandle::build(|ss| ss.background_color(ACCENT_COLOR));
+        let mut computed = ComputedStyle::new();
+        computed.accent_color = Some(Color::GREEN);
+        style.0.apply_attrs_to(&style.0.props, &mut computed);
+        computed.resolve_current_color();
+        assert_eq!(computed.background_color, Some(Color::GREEN));
+
+        // An explicit color takes precedence over the accent color.
+        let style = StyleHandle::build(|ss| {
+            ss.accent_color(Color::GREEN)
+                .background_color(ACCENT_COLOR)
+                .background_color(Color::RED)
+                .border_color(ACCENT_COLOR)
+        });
+        let computed = compute(&style, None);
+        assert_eq!(computed.background_color, Some(Color::RED));
+        assert_eq!(computed.border_color, Some(Color::GREEN));
+    }
+
+    #[test]
+    fn test_flex_shorthand() {
+        let style = StyleHandle::build(|ss| ss.flex(1));
+        let computed = compute(&style, None);
+        assert_eq!(computed.style.flex_grow, 1.);
+        assert_eq!(computed.style.flex_shrink, 1.);
+        assert_eq!(computed.style.flex_basis, Val::Px(0.));
+
+        // Longhand after shorthand overrides it.
+        let style = StyleHandle::build(|ss| ss.flex(2).flex_shrink(0.));
+        let computed = compute(&style, None);
+        assert_eq!(computed.style.flex_grow, 2.);
+        assert_eq!(computed.style.flex_shrink, 0.);
+    }
+
+    #[test]
+    fn test_chain_order_padding() {
+        let style = StyleHandle::build(|ss| ss.padding(8).padding_left(16));
+        let computed = compute(&style, None);
+        assert_eq!(computed.style.padding.left, Val::Px(16.));
+        assert_eq!(computed.style.padding.right, Val::Px(8.));
+        assert_eq!(computed.style.padding.top, Val::Px(8.));
+
+        // Shorthand after longhand overrides it.
+        let style = StyleHandle::build(|ss| ss.padding_left(16).padding(8));
+        let computed = compute(&style, None);
+        assert_eq!(computed.style.padding.left, Val::Px(8.));
+    }
+
+    #[test]
+    fn test_chain_order_margin() {
+        let style = StyleHandle::build(|ss| ss.margin(4).margin_top(10).margin_bottom(12));
+        let computed = compute(&style, None);
+        assert_eq!(computed.style.margin.top, Val::Px(10.));
+        assert_eq!(computed.style.margin.bottom, Val::Px(12.));
+        assert_eq!(computed.style.margin.left, Val::Px(4.));
+
+        let style = StyleHandle::build(|ss| ss.margin_top(10).margin(4));
+        let computed = compute(&style, None);
+        assert_eq!(computed.style.margin.top, Val::Px(4.));
+    }
+
+    #[test]
+    fn test_chain_order_border() {
+        let style = StyleHandle::build(|ss| ss.border(1).border_left(3).border(2));
+        let computed = compute(&style, None);
+        assert_eq!(computed.style.border.left, Val::Px(2.));
+
+        let style = StyleHandle::build(|ss| ss.border(1).border_top(3));
+        let computed = compute(&style, None);
+        assert_eq!(computed.style.border.top, Val::Px(3.));
+        assert_eq!(computed.style.border.bottom, Val::Px(1.));
+    }
+
+    #[test]
+    fn test_chain_order_repeated() {
+        let style = StyleHandle::build(|ss| {
+            ss.background_color(Color::RED)
+                .width(10)
+                .background_color(Color::BLUE)
+                .width(20)
+        });
+        let computed = compute(&style, None);
+        assert_eq!(computed.background_color, Some(Color::BLUE));
+        assert_eq!(computed.style.width, Val::Px(20.));
+    }
+
+    #[test]
+    fn test_reset() {
+        let style = StyleHandle::build(|ss| {
+            ss.reset()
+                .padding_left(4)
+                .border_bottom(2)
+                .background_color(Color::RED)
+        });
+        let mut computed = ComputedStyle::new();
+        computed.style.margin = UiRect::all(Val::Px(10.));
+        computed.style.padding = UiRect::all(Val::Auto);
+        computed.border_color = Some(Color::BLUE);
+        style.0.apply_attrs_to(&style.0.props, &mut computed);
+
+        // Unset properties are reset to the baseline.
+        assert_eq!(computed.style.margin, UiRect::ZERO);
+        assert_eq!(computed.style.padding.right, Val::ZERO);
+        assert_eq!(computed.style.border.top, Val::ZERO);
+        assert_eq!(computed.border_color, None);
+
+        // Properties set after the reset are preserved.
+        assert_eq!(computed.style.padding.left, Val::Px(4.));
+        assert_eq!(computed.style.border.bottom, Val::Px(2.));
+        assert_eq!(computed.background_color, Some(Color::RED));
+    }
+
+    #[test]
+    fn test_overflow_clip() {
+        let style = StyleHandle::build(|ss| ss.overflow(OverflowAxis::Clip));
+        let computed = compute(&style, None);
+        assert_eq!(computed.style.overflow, Overflow::clip());
+
+        let style = StyleHandle::build(|ss| ss.overflow(Overflow::clip_y()));
+        let computed = compute(&style, None);
+        assert_eq!(computed.style.overflow.x, OverflowAxis::Visible);
+        assert_eq!(computed.style.overflow.y, OverflowAxis::Clip);
+    }
+
+    #[test]
+    fn test_text_input_colors() {
+        let style = StyleHandle::build(|ss| {
+            ss.caret_color(Color::RED)
+                .selection_color(Color::rgba(0., 0., 1., 0.5))
+        });
+        let computed = compute(&style, None);
+        assert_eq!(computed.text_input.caret_color, Some(Color::RED));
+        assert_eq!(
+            computed.text_input.selection_color,
+            Some(Color::rgba(0., 0., 1., 0.5))
+        );
+        let computed = compute(&StyleHandle::build(|ss| ss), None);
+        assert!(computed.text_input.is_empty());
+    }
+}
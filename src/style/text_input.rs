@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+
+/// Component which holds the caret and selection styles for a text input element. Text input
+/// widgets read this component to color the caret and the selection highlight. Selections
+/// spanning several lines use the same color for each line's highlight.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq)]
+pub struct TextInputStyle {
+    /// Color of the text caret. If not set, the caret should use the text color.
+    pub caret_color: Option<Color>,
+
+    /// Color of the selection highlight.
+    pub selection_color: Option<Color>,
+}
+
+impl TextInputStyle {
+    /// True if none of the text input properties are set.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
@@ -0,0 +1,22 @@
+use bevy::prelude::*;
+
+/// Component which holds the computed line height and letter spacing of a text node, as set
+/// by the `line_height` and `letter_spacing` style properties. Like the font and color, these
+/// are inherited, so they are resolved from the nearest ancestor which sets them.
+///
+/// Bevy 0.13's text pipeline has no support for either, so this component has no visual
+/// effect on its own; it exists so that custom text rendering can read the resolved values.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq)]
+pub struct TextSpacing {
+    /// Height of a line of text, as a multiple of the font size.
+    pub line_height: Option<f32>,
+    /// Extra space between letters, in logical pixels.
+    pub letter_spacing: Option<f32>,
+}
+
+impl TextSpacing {
+    /// True if neither property is set.
+    pub fn is_empty(&self) -> bool {
+        self.line_height.is_none() && self.letter_spacing.is_none()
+    }
+}
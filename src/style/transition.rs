@@ -1,4 +1,5 @@
 use bevy::{prelude::*, ui, utils::HashMap};
+use bevy_color::{Mix, Oklaba, SRgba};
 use std::fmt::Debug;
 
 /// Represents an animation timing function such as 'ease-in'.
@@ -75,6 +76,90 @@ pub mod timing {
         }
     }
 
+    /// Easing function defined by a cubic bezier curve from (0, 0) to (1, 1), with control
+    /// points (x1, y1) and (x2, y2), the same as the CSS `cubic-bezier()` function. The x
+    /// coordinates must be in the range [0, 1]. Since transitions hold a `'static` reference,
+    /// custom curves are typically declared as constants:
+    ///
+    /// ```ignore
+    /// const SNAPPY: &CubicBezier = &CubicBezier::new(0.2, 0.9, 0.3, 1.2);
+    /// ```
+    pub struct CubicBezier {
+        x1: f32,
+        y1: f32,
+        x2: f32,
+        y2: f32,
+    }
+
+    impl CubicBezier {
+        /// Construct a new cubic bezier easing function from the two control points.
+        pub const fn new(x1: f32, y1: f32, x2: f32, y2: f32) -> Self {
+            Self { x1, y1, x2, y2 }
+        }
+
+        fn sample(a: f32, b: f32, s: f32) -> f32 {
+            // Bernstein form with fixed endpoints at 0 and 1.
+            let r = 1. - s;
+            3. * r * r * s * a + 3. * r * s * s * b + s * s * s
+        }
+
+        fn slope(a: f32, b: f32, s: f32) -> f32 {
+            let r = 1. - s;
+            3. * r * r * a + 6. * r * s * (b - a) + 3. * s * s * (1. - b)
+        }
+    }
+
+    impl TimingFunction for CubicBezier {
+        fn eval(&self, t: f32) -> f32 {
+            if t <= 0. {
+                return 0.;
+            } else if t >= 1. {
+                return 1.;
+            }
+
+            // Find the curve parameter whose x coordinate is t. Newton's method converges
+            // quickly for most curves; fall back to bisection where the slope is too flat.
+            let mut s = t;
+            for _ in 0..8 {
+                let err = Self::sample(self.x1, self.x2, s) - t;
+                if err.abs() < 1e-6 {
+                    return Self::sample(self.y1, self.y2, s);
+                }
+                let slope = Self::slope(self.x1, self.x2, s);
+                if slope.abs() < 1e-6 {
+                    break;
+                }
+                s -= err / slope;
+            }
+
+            let (mut lo, mut hi) = (0., 1.);
+            s = t;
+            for _ in 0..32 {
+                let x = Self::sample(self.x1, self.x2, s);
+                if (x - t).abs() < 1e-6 {
+                    break;
+                }
+                if x < t {
+                    lo = s;
+                } else {
+                    hi = s;
+                }
+                s = (lo + hi) / 2.;
+            }
+            Self::sample(self.y1, self.y2, s)
+        }
+    }
+
+    impl Debug for CubicBezier {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(
+                f,
+                "cubic-bezier({}, {}, {}, {})",
+                self.x1, self.y1, self.x2, self.y2
+            )
+        }
+    }
+
     /// Linear easing function
     pub const LINEAR: &Linear = &Linear {};
 
@@ -86,6 +171,9 @@ pub mod timing {
 
     /// "ease-in-out" animation function
     pub const EASE_IN_OUT: &EaseInOut = &EaseInOut {};
+
+    /// The CSS "ease" animation function, `cubic-bezier(0.25, 0.1, 0.25, 1)`
+    pub const EASE: &CubicBezier = &CubicBezier::new(0.25, 0.1, 0.25, 1.);
 }
 
 /// Specifies which property is being animated.
@@ -129,6 +217,9 @@ pub enum TransitionProperty {
 
     /// Animate border bottom
     BorderBottom,
+
+    /// Animate the element's opacity, which also fades its descendants
+    Opacity,
 }
 
 /// Defines a CSS-like animated transition
@@ -162,10 +253,37 @@ pub struct TransitionState {
     pub(crate) transition: Transition,
     // pub(crate) direction: f32,
     pub(crate) clock: f32,
+    /// Time remaining, in seconds, before the animation starts.
+    pub(crate) delay: f32,
 }
 
 impl TransitionState {
-    pub fn advance(&mut self, delta: f32) {
+    pub fn new(transition: Transition) -> Self {
+        let delay = transition.delay;
+        Self {
+            transition,
+            clock: 0.,
+            delay,
+        }
+    }
+
+    /// Restart the animation from the beginning, including the delay. If the animation is
+    /// interrupted during the delay, the delay starts over.
+    pub fn restart(&mut self) {
+        self.clock = 0.;
+        self.delay = self.transition.delay;
+    }
+
+    pub fn advance(&mut self, mut delta: f32) {
+        // Hold at the start value until the delay has elapsed.
+        if self.delay > 0. {
+            if delta <= self.delay {
+                self.delay -= delta;
+                return;
+            }
+            delta -= self.delay;
+            self.delay = 0.;
+        }
         if self.transition.duration > 0. {
             self.clock = (self.clock + delta / self.transition.duration).clamp(0., 1.);
         } else {
@@ -203,6 +321,33 @@ pub struct AnimatedBorderColor {
     pub(crate) target: Color,
 }
 
+#[derive(Component)]
+#[doc(hidden)]
+pub struct AnimatedOpacity {
+    pub(crate) state: TransitionState,
+    pub(crate) origin: f32,
+    pub(crate) target: f32,
+}
+
+impl AnimatedOpacity {
+    /// The current opacity of the element.
+    pub(crate) fn value(&self) -> f32 {
+        let t = self.state.t();
+        self.target * t + self.origin * (1. - t)
+    }
+}
+
+/// Interpolate between two colors. This is done in Oklab space, the same as gradients, so
+/// that the intermediate colors don't get muddy.
+fn mix_colors(origin: Color, target: Color, t: f32) -> Color {
+    let [r0, g0, b0, a0] = origin.as_rgba_f32();
+    let [r1, g1, b1, a1] = target.as_rgba_f32();
+    let origin = Oklaba::from(SRgba::new(r0, g0, b0, a0));
+    let target = Oklaba::from(SRgba::new(r1, g1, b1, a1));
+    let color = SRgba::from(origin.mix(&target, t));
+    Color::rgba(color.red, color.green, color.blue, color.alpha)
+}
+
 pub struct AnimatedLayoutProp {
     pub(crate) state: TransitionState,
     pub(crate) origin: f32,
@@ -238,7 +383,8 @@ impl AnimatedLayoutProp {
                 TransitionProperty::BorderBottom => style.border.bottom = ui::Val::Px(value),
                 TransitionProperty::Transform
                 | TransitionProperty::BackgroundColor
-                | TransitionProperty::BorderColor => panic!("Invalid style transition prop"),
+                | TransitionProperty::BorderColor
+                | TransitionProperty::Opacity => panic!("Invalid style transition prop"),
             }
         }
     }
@@ -265,7 +411,8 @@ impl AnimatedLayoutProp {
             }
             TransitionProperty::Transform
             | TransitionProperty::BackgroundColor
-            | TransitionProperty::BorderColor => panic!("Invalid style transition prop"),
+            | TransitionProperty::BorderColor
+            | TransitionProperty::Opacity => panic!("Invalid style transition prop"),
         };
 
         // Assume that all values are in pixels, we don't try and animate in other units.
@@ -273,7 +420,7 @@ impl AnimatedLayoutProp {
             if self.target != next_value {
                 self.origin = prev_value;
                 self.target = next_value;
-                self.state.clock = 0.;
+                self.state.restart();
             }
         }
     }
@@ -302,37 +449,45 @@ pub fn animate_transforms(
 
 #[doc(hidden)]
 pub fn animate_bg_colors(
-    mut query: Query<(
-        Entity,
-        Option<&mut BackgroundColor>,
-        &mut AnimatedBackgroundColor,
-    )>,
+    mut query: Query<(&mut BackgroundColor, &mut AnimatedBackgroundColor)>,
     time: Res<Time>,
 ) {
-    #![allow(unused)]
-    for (e, mut bg, mut at) in query.iter_mut() {
+    for (mut bg, mut at) in query.iter_mut() {
         let t_old = at.state.clock;
         at.state.advance(time.delta_seconds());
-        let t = at.state.transition.timing.eval(at.state.clock);
-        let origin = at.origin.as_rgba_linear();
-        let target = at.target.as_rgba_linear();
-        todo!("Finish color space interpolation!");
+        if at.state.clock != t_old {
+            bg.0 = mix_colors(at.origin, at.target, at.state.t());
+        }
     }
 }
 
 #[doc(hidden)]
 pub fn animate_border_colors(
-    mut query: Query<(Entity, Option<&mut BorderColor>, &mut AnimatedBorderColor)>,
+    mut query: Query<(&mut BorderColor, &mut AnimatedBorderColor)>,
     time: Res<Time>,
 ) {
-    #![allow(unused)]
-    for (e, mut bg, mut at) in query.iter_mut() {
+    for (mut bc, mut at) in query.iter_mut() {
         let t_old = at.state.clock;
         at.state.advance(time.delta_seconds());
-        let t = at.state.transition.timing.eval(at.state.clock);
-        let origin = at.origin.as_rgba_linear();
-        let target = at.target.as_rgba_linear();
-        todo!("Finish color space interpolation!");
+        if at.state.clock != t_old {
+            bc.0 = mix_colors(at.origin, at.target, at.state.t());
+        }
+    }
+}
+
+/// Advance opacity transitions. The faded colors are applied by the style system, which
+/// restyles the element and its descendants whenever the [`AnimatedOpacity`] changes, so it's
+/// only marked as changed while the value is moving.
+#[doc(hidden)]
+pub fn animate_opacity(mut query: Query<&mut AnimatedOpacity>, time: Res<Time>) {
+    for mut anim in query.iter_mut() {
+        let t_old = anim.state.clock;
+        anim.bypass_change_detection()
+            .state
+            .advance(time.delta_seconds());
+        if anim.state.clock != t_old {
+            anim.set_changed();
+        }
     }
 }
 
@@ -345,3 +500,216 @@ pub fn animate_layout(mut query: Query<(&mut Style, &mut AnimatedLayout)>, time:
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+    use crate::{StyleHandle, StyleProp};
+
+    fn assert_color(actual: Color, expected: [f32; 4]) {
+        let actual = actual.as_rgba_f32();
+        assert!(
+            actual
+                .iter()
+                .zip(expected)
+                .all(|(a, e)| (a - e).abs() < 1e-3),
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+
+    fn step(world: &mut World, secs: f32) {
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(secs));
+    }
+
+    fn delayed() -> TransitionState {
+        TransitionState::new(Transition {
+            property: TransitionProperty::Width,
+            delay: 0.5,
+            duration: 1.,
+            timing: timing::LINEAR,
+        })
+    }
+
+    #[test]
+    fn test_cubic_bezier() {
+        let linear = timing::CubicBezier::new(0., 0., 1., 1.);
+        for t in [0., 0.1, 0.5, 0.75, 1.] {
+            assert!((linear.eval(t) - t).abs() < 1e-4);
+        }
+        // Reference value for CSS `ease` at the midpoint.
+        assert!((timing::EASE.eval(0.5) - 0.8024).abs() < 1e-3);
+        assert_eq!(timing::EASE.eval(-1.), 0.);
+        assert_eq!(timing::EASE.eval(2.), 1.);
+        // Overshooting curves may leave the [0, 1] range.
+        let back = timing::CubicBezier::new(0.3, 0., 0.7, 1.5);
+        assert!(back.eval(0.8) > 1.);
+        assert_eq!(
+            format!("{:?}", timing::EASE),
+            "cubic-bezier(0.25, 0.1, 0.25, 1)"
+        );
+    }
+
+    #[test]
+    fn test_delay() {
+        let mut state = delayed();
+        state.advance(0.25);
+        assert_eq!(state.t(), 0.);
+        state.advance(0.2);
+        assert_eq!(state.t(), 0.);
+        // Remainder of the step past the delay is applied to the animation.
+        state.advance(0.3);
+        assert!((state.t() - 0.25).abs() < 1e-5);
+        state.advance(1.);
+        assert_eq!(state.t(), 1.);
+    }
+
+    #[test]
+    fn test_delay_interrupted() {
+        let mut state = delayed();
+        state.advance(0.4);
+        state.restart();
+        state.advance(0.4);
+        assert_eq!(state.t(), 0.);
+        state.advance(0.2);
+        assert!((state.t() - 0.1).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_layout_held_during_delay() {
+        let mut prop = AnimatedLayoutProp::new(delayed());
+        prop.origin = 10.;
+        prop.target = 20.;
+        let mut style = Style::default();
+        prop.update(TransitionProperty::Width, &mut style, 0.25, true);
+        assert_eq!(style.width, ui::Val::Px(10.));
+        prop.update(TransitionProperty::Width, &mut style, 0.75, false);
+        assert_eq!(style.width, ui::Val::Px(15.));
+    }
+
+    #[test]
+    fn test_transition_delay_builder() {
+        let style = StyleHandle::build(|ss| {
+            ss.transition(&[Transition {
+                property: TransitionProperty::Width,
+                duration: 0.3,
+                ..default()
+            }])
+            .transition_delay(TransitionProperty::Width, 0.1)
+        });
+        match &style.0.props[0] {
+            StyleProp::Transition(transitions) => assert_eq!(transitions[0].delay, 0.1),
+            _ => panic!("Expected transition"),
+        }
+    }
+
+    #[test]
+    fn test_animate_bg_color() {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        let entity = world
+            .spawn((
+                BackgroundColor(Color::BLACK),
+                AnimatedBackgroundColor {
+                    state: TransitionState::new(Transition {
+                        property: TransitionProperty::BackgroundColor,
+                        duration: 1.,
+                        ..default()
+                    }),
+                    origin: Color::BLACK,
+                    target: Color::WHITE,
+                },
+            ))
+            .id();
+
+        // Halfway through, the color is the Oklab midpoint, which is darker than the sRGB one.
+        step(&mut world, 0.5);
+        world.run_system_once(animate_bg_colors);
+        let bg = world.get::<BackgroundColor>(entity).unwrap().0;
+        assert_color(bg, [0.3886, 0.3886, 0.3886, 1.]);
+
+        step(&mut world, 0.25);
+        world.run_system_once(animate_bg_colors);
+        let bg = world.get::<BackgroundColor>(entity).unwrap().0;
+        assert!(bg.r() > 0.3886 && bg.r() < 1.);
+
+        step(&mut world, 1.);
+        world.run_system_once(animate_bg_colors);
+        let bg = world.get::<BackgroundColor>(entity).unwrap().0;
+        assert_color(bg, [1., 1., 1., 1.]);
+    }
+
+    #[test]
+    fn test_animate_border_color() {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        let entity = world
+            .spawn((
+                BorderColor(Color::NONE),
+                AnimatedBorderColor {
+                    state: TransitionState::new(Transition {
+                        property: TransitionProperty::BorderColor,
+                        delay: 0.5,
+                        duration: 1.,
+                        timing: timing::LINEAR,
+                    }),
+                    origin: Color::NONE,
+                    target: Color::BLACK,
+                },
+            ))
+            .id();
+
+        // Held at the origin during the delay.
+        step(&mut world, 0.25);
+        world.run_system_once(animate_border_colors);
+        let bc = world.get::<BorderColor>(entity).unwrap().0;
+        assert_color(bc, [0., 0., 0., 0.]);
+
+        // Alpha is interpolated along with the color.
+        step(&mut world, 0.5);
+        world.run_system_once(animate_border_colors);
+        let bc = world.get::<BorderColor>(entity).unwrap().0;
+        assert_color(bc, [0., 0., 0., 0.25]);
+    }
+
+    #[test]
+    fn test_animate_opacity() {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        let entity = world
+            .spawn(AnimatedOpacity {
+                state: TransitionState::new(Transition {
+                    property: TransitionProperty::Opacity,
+                    duration: 1.,
+                    ..default()
+                }),
+                origin: 1.,
+                target: 0.,
+            })
+            .id();
+        world.clear_trackers();
+
+        step(&mut world, 0.25);
+        world.run_system_once(animate_opacity);
+        let anim = world.entity(entity).get_ref::<AnimatedOpacity>().unwrap();
+        assert!(anim.is_changed());
+        assert!((anim.value() - 0.75).abs() < 1e-5);
+
+        // Once finished, the value no longer changes.
+        step(&mut world, 1.);
+        world.run_system_once(animate_opacity);
+        world.clear_trackers();
+        step(&mut world, 0.25);
+        world.run_system_once(animate_opacity);
+        let anim = world.entity(entity).get_ref::<AnimatedOpacity>().unwrap();
+        assert!(!anim.is_changed());
+        assert_eq!(anim.value(), 0.);
+    }
+}
@@ -1,18 +1,32 @@
 use bevy::{
-    a11y::Focus,
-    prelude::*,
-    render::texture::ImageLoaderSettings,
+    a11y::Focus, ecs::system::SystemState, prelude::*, render::texture::ImageLoaderSettings,
+    window::PrimaryWindow,
+};
+use bevy_mod_picking::{
+    focus::{HoverMap, PreviousHoverMap},
+    pointer::PointerId,
 };
-use bevy_mod_picking::focus::{HoverMap, PreviousHoverMap};
 
 use crate::{
-    style::{ComputedStyle, UpdateComputedStyle}, ElementClasses, ElementStyles, QuillPlugin, SelectorMatcher
+    style::{ComputedStyle, UpdateComputedStyle},
+    Cursor, ElementAttributes, ElementClasses, ElementStyles, FocusRing, FocusVisible,
+    PressedEntities, QuillPlugin, QuillSettings, SelectorMatcher,
+};
+
+use super::{
+    background_layers::{BackgroundLayer, BackgroundLayerNode},
+    computed::{ComputedImage, UpdateComputedStyles},
+    content_visibility::ContentVisibilityState,
+    order::LogicalChildren,
+    style_handle::TextStyles,
+    transition::AnimatedOpacity,
 };
 
-use super::{computed::ComputedImage, style_handle::TextStyles};
+#[derive(Resource, Default)]
+pub(crate) struct PreviousFocus(Option<Entity>, bool);
 
 #[derive(Resource, Default)]
-pub(crate) struct PreviousFocus(Option<Entity>);
+pub(crate) struct PreviousPressed(PressedEntities);
 
 #[allow(clippy::too_many_arguments)]
 #[allow(clippy::type_complexity)]
@@ -25,41 +39,68 @@ pub(crate) fn update_styles(
             Option<Ref<ElementStyles>>,
             Option<&TextStyles>,
             Option<Ref<Text>>,
+            Option<Ref<ContentVisibilityState>>,
+            Option<Ref<Parent>>,
+            Option<Ref<AnimatedOpacity>>,
         ),
-        With<Node>,
+        (With<Node>, Without<BackgroundLayerNode>),
     >,
     query_element_classes: Query<Ref<'static, ElementClasses>>,
+    query_element_attributes: Query<Ref<'static, ElementAttributes>>,
     query_parents: Query<&'static Parent, (With<Node>, With<Visibility>)>,
     query_children: Query<&'static Children, (With<Node>, With<Visibility>)>,
+    query_logical_children: Query<&'static LogicalChildren>,
+    query_layers: Query<(), With<BackgroundLayerNode>>,
+    query_children_changed: Query<(), (With<Node>, Changed<Children>)>,
     hover_map: Res<HoverMap>,
     hover_map_prev: Res<PreviousHoverMap>,
     assets: Res<AssetServer>,
     focus: Res<Focus>,
+    focus_visible: Res<FocusVisible>,
     plugin: Res<QuillPlugin>,
+    settings: Res<QuillSettings>,
     mut focus_prev: ResMut<PreviousFocus>,
+    pressed: Res<PressedEntities>,
+    mut pressed_prev: ResMut<PreviousPressed>,
 ) {
     let matcher = SelectorMatcher::new(
         &query_element_classes,
+        &query_element_attributes,
         &query_parents,
         &query_children,
+        &query_logical_children,
+        &query_layers,
         &hover_map.0,
         focus.0,
+        focus_visible.0,
+        &pressed,
     );
     let matcher_prev = SelectorMatcher::new(
         &query_element_classes,
+        &query_element_attributes,
         &query_parents,
         &query_children,
+        &query_logical_children,
+        &query_layers,
         &hover_map_prev.0,
         focus_prev.0,
+        focus_prev.1,
+        &pressed_prev.0,
     );
 
+    // Changing the settings may invalidate previously computed styles, so restyle everything.
+    let restyle = !settings.style_cache_enabled || settings.is_changed();
+    let mut updates = Vec::new();
     for root_node in &query_root {
         update_element_styles(
             &mut commands,
+            &mut updates,
             &query_styles,
             &query_element_classes,
+            &query_element_attributes,
             &query_parents,
             &query_children,
+            &query_children_changed,
             &matcher,
             &matcher_prev,
             &assets,
@@ -67,28 +108,87 @@ pub(crate) fn update_styles(
             &TextStyles::default(),
             &plugin,
             false,
+            restyle,
         )
     }
 
+    // Queue the computed styles as a single command, which applies them in order.
+    if !updates.is_empty() {
+        commands.add(UpdateComputedStyles(updates));
+    }
+
     focus_prev.0 = focus.0;
+    focus_prev.1 = focus_visible.0;
+    if pressed_prev.0 != *pressed {
+        pressed_prev.0.clone_from(&pressed);
+    }
+}
+
+/// Set the window cursor to the [`Cursor`] of the topmost hovered element, or of its nearest
+/// ancestor which has one. When several elements are hovered, the one with the least hover
+/// depth wins. The default cursor is restored when the pointer leaves such elements; until
+/// then, the window cursor is left alone, so apps can still set it themselves.
+pub(crate) fn update_cursor_icon(
+    hover_map: Res<HoverMap>,
+    query_cursors: Query<&Cursor>,
+    query_parents: Query<&Parent>,
+    mut query_window: Query<&mut Window, With<PrimaryWindow>>,
+    mut current: Local<Option<Cursor>>,
+) {
+    let cursor = hover_map
+        .get(&PointerId::Mouse)
+        .and_then(|hovered| {
+            hovered
+                .iter()
+                .min_by(|(_, a), (_, b)| a.depth.total_cmp(&b.depth))
+                .map(|(entity, _)| *entity)
+        })
+        .and_then(|entity| {
+            std::iter::once(entity)
+                .chain(query_parents.iter_ancestors(entity))
+                .find_map(|e| query_cursors.get(e).ok().copied())
+        });
+    if cursor == *current {
+        return;
+    }
+    *current = cursor;
+
+    let Ok(mut window) = query_window.get_single_mut() else {
+        return;
+    };
+    match cursor.unwrap_or(Cursor::Default).icon() {
+        Some(icon) => {
+            window.cursor.icon = icon;
+            window.cursor.visible = true;
+        }
+        None => {
+            window.cursor.visible = false;
+        }
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
 #[allow(clippy::type_complexity)]
 fn update_element_styles(
     commands: &mut Commands,
+    updates: &mut Vec<UpdateComputedStyle>,
     query_styles: &Query<
         (
             Ref<Style>,
             Option<Ref<ElementStyles>>,
             Option<&TextStyles>,
             Option<Ref<Text>>,
+            Option<Ref<ContentVisibilityState>>,
+            Option<Ref<Parent>>,
+            Option<Ref<AnimatedOpacity>>,
         ),
-        With<Node>,
+        (With<Node>, Without<BackgroundLayerNode>),
     >,
     classes_query: &Query<Ref<'static, ElementClasses>>,
+    attributes_query: &Query<Ref<'static, ElementAttributes>>,
     parent_query: &Query<'_, '_, &Parent, (With<Node>, With<Visibility>)>,
     children_query: &Query<'_, '_, &Children, (With<Node>, With<Visibility>)>,
+    children_changed_query: &Query<'_, '_, (), (With<Node>, Changed<Children>)>,
     matcher: &SelectorMatcher<'_, '_, '_>,
     matcher_prev: &SelectorMatcher<'_, '_, '_>,
     assets: &Res<AssetServer>,
@@ -96,19 +196,41 @@ fn update_element_styles(
     inherited_styles: &TextStyles,
     plugin: &QuillPlugin,
     mut inherited_styles_changed: bool,
+    mut restyle: bool,
 ) {
     let mut text_styles = inherited_styles.clone();
+    let mut skip_children = false;
+
+    if let Ok((style, elt_styles, prev_text_styles, txt, content_visibility, parent, opacity)) =
+        query_styles.get(entity)
+    {
+        if parent.is_some_and(|parent| parent.is_changed()) {
+            // Moved to a different parent (for example by a `Portal`), so the inherited styles
+            // may be different. Children are restyled if the styles they inherit change.
+            inherited_styles_changed = true;
+        }
+
+        if let Some(content_visibility) = content_visibility {
+            if content_visibility.offscreen {
+                skip_children = true;
+            } else if content_visibility.is_changed() && !content_visibility.is_added() {
+                // Just came back on-screen: descendants may have missed changes while they
+                // were skipped, so restyle the entire subtree.
+                restyle = true;
+            }
+        }
 
-    if let Ok((style, elt_styles, prev_text_styles, txt)) = query_styles.get(entity) {
         // Check if the element styles or ancestor classes have changed.
         let mut changed = match elt_styles {
             Some(ref element_style) => is_changed(
                 element_style,
                 entity,
                 classes_query,
+                attributes_query,
                 matcher,
                 matcher_prev,
                 parent_query,
+                children_changed_query,
             ),
             None => false,
         };
@@ -119,40 +241,56 @@ fn update_element_styles(
             }
         }
 
-        if changed || inherited_styles_changed {
-            // Compute computed style. Initialize to the current state.
-            let mut computed = ComputedStyle::new();
-            computed.style = style.clone();
+        if plugin.focus_ring.is_some()
+            && matcher.is_focus_visible(&entity) != matcher_prev.is_focus_visible(&entity)
+        {
+            changed = true;
+        }
 
-            // Inherited properties
-            computed.font_handle = inherited_styles.font.clone();
-            computed.font_size = inherited_styles.font_size;
-            computed.color = inherited_styles.color;
+        // An opacity transition fades the element and its descendants on every frame.
+        if opacity.as_ref().is_some_and(|opacity| opacity.is_changed()) {
+            changed = true;
+        }
 
-            // Apply element styles to computed
-            if let Some(ref element_styles) = elt_styles {
-                for ss in element_styles.styles.iter() {
-                    ss.apply_to(&mut computed, matcher, &entity);
-                }
-                // Load font asset if non-null.
-                if let Some(ref font_path) = computed.font {
-                    computed.font_handle = Some(assets.load(font_path));
-                }
-            }
+        if changed || inherited_styles_changed || restyle {
+            let mut computed = compute_element_style(
+                &style,
+                elt_styles.as_deref(),
+                inherited_styles,
+                matcher,
+                entity,
+                Some(assets.as_ref()),
+                plugin.focus_ring.as_ref(),
+            );
 
             // Update inherited text styles
             text_styles.font = computed.font_handle.clone();
             text_styles.font_size = computed.font_size;
             text_styles.color = computed.color;
+            text_styles.alignment = computed.alignment;
+            text_styles.line_break = computed.line_break;
+            text_styles.line_height = computed.line_height;
+            text_styles.letter_spacing = computed.letter_spacing;
+            let own_opacity = match opacity {
+                Some(ref opacity) => opacity.value(),
+                None => computed.opacity.unwrap_or(1.),
+            };
+            text_styles.opacity =
+                Some(computed.inherited_opacity.unwrap_or(1.) * own_opacity).filter(|o| *o < 1.);
+            text_styles.accent_color = computed.accent_color;
 
             if text_styles == *inherited_styles && txt.is_none() {
                 // No change from parent, so we can remove the cached styles and rely on inherited
                 // styles only. Note that for text nodes, we always want to store the inherited
-                // styles, even if they are the same as the parent.
-                inherited_styles_changed = prev_text_styles.is_some();
+                // styles, even if they are the same as the parent. Children inherit the same
+                // styles as this element, so they need restyling if the inherited styles
+                // changed, or if they were previously given the cached copy.
+                if prev_text_styles.is_some() {
+                    inherited_styles_changed = true;
+                    commands.entity(entity).remove::<TextStyles>();
+                }
                 if inherited_styles_changed {
                     changed = true;
-                    commands.entity(entity).remove::<TextStyles>();
                 }
             } else {
                 // Text styles are different from parent, so we need to store a cached copy.
@@ -163,21 +301,33 @@ fn update_element_styles(
                 }
             }
 
-            if changed {
+            if changed || restyle {
                 computed.image_handle = match computed.image.as_ref() {
                     None => None,
                     Some(ComputedImage::Handle(h)) => Some(h.clone()),
+                    // Resolved by UpdateComputedStyle, since it needs to generate a texture.
+                    Some(ComputedImage::Gradient(_)) => None,
                     Some(ComputedImage::Path(p)) => {
                         let sampler = plugin.default_sampler.clone();
                         Some(
                             assets.load_with_settings(p, move |s: &mut ImageLoaderSettings| {
                                 s.sampler = sampler.clone()
-                            })
+                            }),
                         )
                     }
                 };
-                
-                commands.add(UpdateComputedStyle { entity, computed });
+
+                for layer in computed.background_layers.iter_mut() {
+                    if let BackgroundLayer::Image(path) = layer {
+                        let sampler = plugin.default_sampler.clone();
+                        *layer = BackgroundLayer::ImageHandle(assets.load_with_settings(
+                            path.clone(),
+                            move |s: &mut ImageLoaderSettings| s.sampler = sampler.clone(),
+                        ));
+                    }
+                }
+
+                updates.push(UpdateComputedStyle { entity, computed });
             }
         } else if let Some(prev) = prev_text_styles {
             // Styles didn't change, but we need to pass inherited text styles to children.
@@ -185,14 +335,21 @@ fn update_element_styles(
         }
     }
 
+    if skip_children {
+        return;
+    }
+
     if let Ok(children) = children_query.get(entity) {
         for child in children.iter() {
             update_element_styles(
                 commands,
+                updates,
                 query_styles,
                 classes_query,
+                attributes_query,
                 parent_query,
                 children_query,
+                children_changed_query,
                 matcher,
                 matcher_prev,
                 assets,
@@ -200,21 +357,174 @@ fn update_element_styles(
                 &text_styles,
                 plugin,
                 inherited_styles_changed,
+                restyle,
+            );
+        }
+    }
+}
+
+/// Compute the style of a single element, given the current [`Style`], the inherited text
+/// styles, and the selector state.
+pub(crate) fn compute_element_style(
+    style: &Style,
+    element_styles: Option<&ElementStyles>,
+    inherited_styles: &TextStyles,
+    matcher: &SelectorMatcher<'_, '_, '_>,
+    entity: Entity,
+    assets: Option<&AssetServer>,
+    focus_ring: Option<&FocusRing>,
+) -> ComputedStyle {
+    // Compute computed style. Initialize to the current state.
+    let mut computed = ComputedStyle::new();
+    computed.style = style.clone();
+
+    // Inherited properties
+    computed.font_handle = inherited_styles.font.clone();
+    computed.font_size = inherited_styles.font_size;
+    computed.color = inherited_styles.color;
+    computed.alignment = inherited_styles.alignment;
+    computed.line_break = inherited_styles.line_break;
+    computed.line_height = inherited_styles.line_height;
+    computed.letter_spacing = inherited_styles.letter_spacing;
+    computed.inherited_opacity = inherited_styles.opacity;
+    computed.accent_color = inherited_styles.accent_color;
+
+    // Apply element styles to computed
+    if let Some(element_styles) = element_styles {
+        for ss in element_styles.iter() {
+            ss.apply_to(&mut computed, matcher, &entity);
+        }
+        computed.resolve_current_color();
+        // Load font asset if non-null.
+        if let (Some(ref font_path), Some(assets)) = (&computed.font, assets) {
+            computed.font_handle = Some(assets.load(font_path));
+        }
+    }
+
+    if let Some(ring) = focus_ring {
+        if matcher.is_focus_visible(&entity) {
+            ring.apply_to(&mut computed);
+        }
+    }
+
+    computed
+}
+
+/// Compute the style of an element from the current state of the world, without applying it.
+/// This evaluates the element's styles and those of its ancestors (for inherited text
+/// properties) against the current class, hover, focus and pressed state. Useful for tests and
+/// inspection tools.
+#[allow(clippy::type_complexity)]
+pub fn compute_style(world: &mut World, entity: Entity) -> ComputedStyle {
+    let mut state: SystemState<(
+        Query<(&Style, Option<&ElementStyles>)>,
+        Query<Ref<ElementClasses>>,
+        Query<Ref<ElementAttributes>>,
+        Query<&Parent, (With<Node>, With<Visibility>)>,
+        Query<&Children, (With<Node>, With<Visibility>)>,
+        Query<&LogicalChildren>,
+        Query<(), With<BackgroundLayerNode>>,
+    )> = SystemState::new(world);
+    let world: &World = world;
+    let (
+        query_styles,
+        query_element_classes,
+        query_element_attributes,
+        query_parents,
+        query_children,
+        query_logical_children,
+        query_layers,
+    ) = state.get(world);
+    let hover_map = world
+        .get_resource::<HoverMap>()
+        .map(|hover_map| hover_map.0.clone())
+        .unwrap_or_default();
+    let pressed = world
+        .get_resource::<PressedEntities>()
+        .cloned()
+        .unwrap_or_default();
+    let matcher = SelectorMatcher::new(
+        &query_element_classes,
+        &query_element_attributes,
+        &query_parents,
+        &query_children,
+        &query_logical_children,
+        &query_layers,
+        &hover_map,
+        world.get_resource::<Focus>().and_then(|focus| focus.0),
+        world
+            .get_resource::<FocusVisible>()
+            .is_some_and(|focus_visible| focus_visible.0),
+        &pressed,
+    );
+    let focus_ring = world
+        .get_resource::<QuillPlugin>()
+        .and_then(|plugin| plugin.focus_ring.as_ref());
+    let assets = world.get_resource::<AssetServer>();
+
+    // Text styles are inherited, so compute the ancestors first, starting from the root.
+    let mut ancestors = Vec::new();
+    let mut e = entity;
+    while let Some(parent) = world.get::<Parent>(e) {
+        e = parent.get();
+        ancestors.push(e);
+    }
+
+    let mut inherited_styles = TextStyles::default();
+    for e in ancestors.into_iter().rev() {
+        if let Ok((style, element_styles)) = query_styles.get(e) {
+            let computed = compute_element_style(
+                style,
+                element_styles,
+                &inherited_styles,
+                &matcher,
+                e,
+                assets,
+                focus_ring,
             );
+            let opacity = computed.effective_opacity();
+            inherited_styles = TextStyles {
+                font: computed.font_handle,
+                font_size: computed.font_size,
+                color: computed.color,
+                alignment: computed.alignment,
+                line_break: computed.line_break,
+                line_height: computed.line_height,
+                letter_spacing: computed.letter_spacing,
+                opacity: Some(opacity).filter(|o| *o < 1.),
+                accent_color: computed.accent_color,
+            };
         }
     }
+
+    match query_styles.get(entity) {
+        Ok((style, element_styles)) => compute_element_style(
+            style,
+            element_styles,
+            &inherited_styles,
+            &matcher,
+            entity,
+            assets,
+            focus_ring,
+        ),
+        Err(_) => ComputedStyle::new(),
+    }
 }
 
 /// Detects whether the given entity's styles have changed, or whether any of its ancestors
 /// have changed in a way that would affect the computation of styles (either because
-/// of class list changes or hovering).
+/// of class list changes or hovering), or whether the position of the entity or an ancestor
+/// among its siblings has changed.
+#[allow(clippy::too_many_arguments)]
 fn is_changed(
     element_styles: &Ref<'_, ElementStyles>,
     entity: Entity,
     classes_query: &Query<Ref<'static, ElementClasses>>,
+    attributes_query: &Query<Ref<'static, ElementAttributes>>,
     matcher: &SelectorMatcher<'_, '_, '_>,
     matcher_prev: &SelectorMatcher<'_, '_, '_>,
     parent_query: &Query<'_, '_, &Parent, (With<Node>, With<Visibility>)>,
+    children_changed_query: &Query<'_, '_, (), (With<Node>, Changed<Children>)>,
 ) -> bool {
     // Style changes only affect current element, not children.
     let mut changed = element_styles.is_changed();
@@ -232,6 +542,11 @@ fn is_changed(
                     break;
                 }
 
+                if matcher.is_active(&e) != matcher_prev.is_active(&e) {
+                    changed = true;
+                    break;
+                }
+
                 if matcher.is_focused(&e) != matcher_prev.is_focused(&e) {
                     changed = true;
                     break;
@@ -255,11 +570,766 @@ fn is_changed(
                 }
             }
 
+            if attributes_query
+                .get(e)
+                .is_ok_and(|attributes| attributes.is_changed())
+            {
+                changed = true;
+                break;
+            }
+
             match parent_query.get(e) {
-                Ok(parent) => e = **parent,
+                Ok(parent) => {
+                    // A change to the parent's child list may have moved this entity.
+                    if element_styles.uses_child_position
+                        && children_changed_query.contains(parent.get())
+                    {
+                        changed = true;
+                        break;
+                    }
+                    e = **parent
+                }
                 _ => break,
             }
         }
     }
     changed
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::{
+        ecs::system::{Command, RunSystemOnce},
+        text::BreakLineOn,
+    };
+    use bevy_mod_picking::backend::HitData;
+
+    use super::*;
+    use crate::{
+        animate_opacity, BorderRadius, BoxShadow, ContentVisibility, ElementState, StyleHandle,
+        TextSpacing, Transition, TransitionProperty, ACCENT_COLOR,
+    };
+
+    /// App which runs [`update_styles`], with the resources it reads.
+    fn test_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_resource::<Focus>()
+            .init_resource::<HoverMap>()
+            .init_resource::<PreviousHoverMap>()
+            .init_resource::<FocusVisible>()
+            .init_resource::<QuillPlugin>()
+            .init_resource::<QuillSettings>()
+            .init_resource::<PreviousFocus>()
+            .init_resource::<PressedEntities>()
+            .init_resource::<PreviousPressed>()
+            .add_systems(Update, update_styles);
+        app
+    }
+
+    /// Hover map in which the mouse pointer hits the given entities, at the given depths.
+    fn hover_map(hits: &[(Entity, f32)]) -> HoverMap {
+        let mut hover_map = HoverMap::default();
+        hover_map.0.insert(
+            PointerId::Mouse,
+            hits.iter()
+                .map(|(e, depth)| (*e, HitData::new(Entity::PLACEHOLDER, *depth, None, None)))
+                .collect(),
+        );
+        hover_map
+    }
+
+    #[test]
+    fn test_compute_style() {
+        let mut world = World::new();
+        let parent_style = StyleHandle::build(|ss| ss.color(Color::RED).font_size(20.));
+        let child_style = StyleHandle::build(|ss| {
+            ss.width(10)
+                .selector(".dark > &", |ss| ss.background_color(Color::BLACK))
+                .selector(".dark > &:hover", |ss| ss.background_color(Color::GRAY))
+        });
+
+        let child = world
+            .spawn((
+                NodeBundle::default(),
+                ElementClasses::default(),
+                ElementStyles::new(&[child_style]),
+            ))
+            .id();
+        let mut parent_classes = ElementClasses::default();
+        parent_classes.add_class("dark");
+        let parent = world
+            .spawn((
+                NodeBundle::default(),
+                parent_classes,
+                ElementStyles::new(&[parent_style]),
+            ))
+            .add_child(child)
+            .id();
+
+        let computed = compute_style(&mut world, child);
+        assert_eq!(computed.style.width, Val::Px(10.));
+        assert_eq!(computed.background_color, Some(Color::BLACK));
+        // Text styles are inherited from the parent.
+        assert_eq!(computed.color, Some(Color::RED));
+        assert_eq!(computed.font_size, Some(20.));
+
+        // Hovering the child applies the hover selector.
+        world.insert_resource(hover_map(&[(child, 0.)]));
+        let computed = compute_style(&mut world, child);
+        assert_eq!(computed.background_color, Some(Color::GRAY));
+
+        // Removing the class from the parent disables both selectors.
+        world.get_mut::<ElementClasses>(parent).unwrap().0.clear();
+        let computed = compute_style(&mut world, child);
+        assert_eq!(computed.background_color, None);
+        assert_eq!(computed.color, Some(Color::RED));
+    }
+
+    #[test]
+    fn test_state_scoped_styles() {
+        let mut world = World::new();
+        let entity = world
+            .spawn((NodeBundle::default(), ElementClasses::default()))
+            .id();
+
+        // Compute the background color of the element with each of the two styles, which
+        // should always agree.
+        let background = |world: &mut World, sugar: &StyleHandle, selector: &StyleHandle| {
+            world
+                .entity_mut(entity)
+                .insert(ElementStyles::new(&[sugar.clone()]));
+            let expected = compute_style(world, entity).background_color;
+            world
+                .entity_mut(entity)
+                .insert(ElementStyles::new(&[selector.clone()]));
+            assert_eq!(compute_style(world, entity).background_color, expected);
+            expected
+        };
+
+        let cases = [
+            (
+                StyleHandle::build(|ss| ss.when_hovered(|ss| ss.background_color(Color::RED))),
+                StyleHandle::build(|ss| {
+                    ss.selector("&:hover", |ss| ss.background_color(Color::RED))
+                }),
+            ),
+            (
+                StyleHandle::build(|ss| ss.when_pressed(|ss| ss.background_color(Color::RED))),
+                StyleHandle::build(|ss| {
+                    ss.selector("&:active", |ss| ss.background_color(Color::RED))
+                }),
+            ),
+            (
+                StyleHandle::build(|ss| ss.when_focused(|ss| ss.background_color(Color::RED))),
+                StyleHandle::build(|ss| {
+                    ss.selector("&:focus", |ss| ss.background_color(Color::RED))
+                }),
+            ),
+            (
+                StyleHandle::build(|ss| ss.when_disabled(|ss| ss.background_color(Color::RED))),
+                StyleHandle::build(|ss| {
+                    ss.selector("&.disabled", |ss| ss.background_color(Color::RED))
+                }),
+            ),
+        ];
+        let combined = (
+            StyleHandle::build(|ss| {
+                ss.when(&[ElementState::Hovered, ElementState::Pressed], |ss| {
+                    ss.background_color(Color::BLUE)
+                })
+            }),
+            StyleHandle::build(|ss| {
+                ss.selector("&:hover:active", |ss| ss.background_color(Color::BLUE))
+            }),
+        );
+
+        for (sugar, selector) in cases.iter() {
+            assert_eq!(background(&mut world, sugar, selector), None);
+        }
+        assert_eq!(background(&mut world, &combined.0, &combined.1), None);
+
+        // Enter each state in turn; each sugar applies once its state is entered.
+        world.insert_resource(hover_map(&[(entity, 0.)]));
+        let (sugar, selector) = &cases[0];
+        assert_eq!(background(&mut world, sugar, selector), Some(Color::RED));
+        // Hovered alone doesn't satisfy the combined state.
+        assert_eq!(background(&mut world, &combined.0, &combined.1), None);
+
+        let mut pressed = PressedEntities::default();
+        pressed.press(PointerId::Mouse, entity);
+        world.insert_resource(pressed);
+        let (sugar, selector) = &cases[1];
+        assert_eq!(background(&mut world, sugar, selector), Some(Color::RED));
+        assert_eq!(
+            background(&mut world, &combined.0, &combined.1),
+            Some(Color::BLUE)
+        );
+
+        world.insert_resource(Focus(Some(entity)));
+        let (sugar, selector) = &cases[2];
+        assert_eq!(background(&mut world, sugar, selector), Some(Color::RED));
+
+        world
+            .get_mut::<ElementClasses>(entity)
+            .unwrap()
+            .add_class("disabled");
+        let (sugar, selector) = &cases[3];
+        assert_eq!(background(&mut world, sugar, selector), Some(Color::RED));
+    }
+
+    #[test]
+    fn test_accent_color_inherited() {
+        let mut world = World::new();
+        let theme = StyleHandle::build(|ss| ss.accent_color(Color::BLUE));
+        // Checked indicator of a checkbox.
+        let indicator = StyleHandle::build(|ss| ss.background_color(ACCENT_COLOR));
+        let checkbox = world
+            .spawn((NodeBundle::default(), ElementStyles::new(&[indicator])))
+            .id();
+        world
+            .spawn((NodeBundle::default(), ElementStyles::new(&[theme])))
+            .add_child(checkbox);
+        let computed = compute_style(&mut world, checkbox);
+        assert_eq!(computed.background_color, Some(Color::BLUE));
+    }
+
+    #[test]
+    fn test_text_spacing_inherited() {
+        let mut world = World::new();
+        let paragraph = StyleHandle::build(|ss| ss.line_height(1.5).letter_spacing(1.));
+        let label = world
+            .spawn((
+                TextBundle::from_section("label", TextStyle::default()),
+                ElementStyles::new(&[StyleHandle::build(|ss| ss.letter_spacing(2.))]),
+            ))
+            .id();
+        let parent = world
+            .spawn((NodeBundle::default(), ElementStyles::new(&[paragraph])))
+            .add_child(label)
+            .id();
+        let computed = compute_style(&mut world, label);
+        assert_eq!(computed.line_height, Some(1.5));
+        assert_eq!(computed.letter_spacing, Some(2.));
+        UpdateComputedStyle {
+            entity: label,
+            computed,
+        }
+        .apply(&mut world);
+        assert_eq!(
+            world.get::<TextSpacing>(label),
+            Some(&TextSpacing {
+                line_height: Some(1.5),
+                letter_spacing: Some(2.),
+            })
+        );
+
+        // Removing the property from the child reverts to the inherited value.
+        world
+            .entity_mut(label)
+            .insert(ElementStyles::new(&[StyleHandle::default()]));
+        let computed = compute_style(&mut world, label);
+        assert_eq!(computed.letter_spacing, Some(1.));
+
+        // With nothing to inherit, the text node has no spacing.
+        world
+            .entity_mut(parent)
+            .insert(ElementStyles::new(&[StyleHandle::default()]));
+        let computed = compute_style(&mut world, label);
+        assert_eq!(computed.line_height, None);
+        UpdateComputedStyle {
+            entity: label,
+            computed,
+        }
+        .apply(&mut world);
+        assert!(world.get::<TextSpacing>(label).is_none());
+    }
+
+    #[test]
+    fn test_text_align_inherited() {
+        let mut world = World::new();
+        let centered = StyleHandle::build(|ss| {
+            ss.text_align(JustifyText::Center)
+                .line_break(BreakLineOn::AnyCharacter)
+        });
+        let label = world
+            .spawn(TextBundle::from_section("label", TextStyle::default()))
+            .id();
+        world
+            .spawn((NodeBundle::default(), ElementStyles::new(&[centered])))
+            .add_child(label);
+        let computed = compute_style(&mut world, label);
+        assert_eq!(computed.alignment, Some(JustifyText::Center));
+        UpdateComputedStyle {
+            entity: label,
+            computed,
+        }
+        .apply(&mut world);
+        let text = world.get::<Text>(label).unwrap();
+        assert_eq!(text.justify, JustifyText::Center);
+        assert_eq!(text.linebreak_behavior, BreakLineOn::AnyCharacter);
+    }
+
+    #[test]
+    fn test_viewport_units() {
+        let mut world = World::new();
+        let panel = StyleHandle::build(|ss| {
+            ss.width_vw(50.)
+                .height_vh(25.)
+                .min_width(Val::VMin(10.))
+                .padding(Val::VMax(2.))
+                .selector(":hover", |ss| ss.width(Val::Vw(60.)))
+        });
+        let entity = world
+            .spawn((
+                NodeBundle::default(),
+                ElementClasses::default(),
+                ElementStyles::new(&[panel]),
+            ))
+            .id();
+        let computed = compute_style(&mut world, entity);
+        assert_eq!(computed.style.width, Val::Vw(50.));
+        UpdateComputedStyle { entity, computed }.apply(&mut world);
+        let style = world.get::<Style>(entity).unwrap();
+        assert_eq!(style.width, Val::Vw(50.));
+        assert_eq!(style.height, Val::Vh(25.));
+        assert_eq!(style.min_width, Val::VMin(10.));
+        assert_eq!(style.padding.left, Val::VMax(2.));
+
+        world.insert_resource(hover_map(&[(entity, 0.)]));
+        let computed = compute_style(&mut world, entity);
+        assert_eq!(computed.style.width, Val::Vw(60.));
+    }
+
+    #[test]
+    fn test_opacity() {
+        let mut world = World::new();
+        let container = StyleHandle::build(|ss| {
+            ss.opacity(0.5)
+                .background_color(Color::RED)
+                .color(Color::WHITE)
+        });
+        let faded = StyleHandle::build(|ss| ss.opacity(0.5).background_color(Color::BLUE));
+        let label = world
+            .spawn(TextBundle::from_section("label", TextStyle::default()))
+            .id();
+        let child = world
+            .spawn((NodeBundle::default(), ElementStyles::new(&[faded])))
+            .id();
+        let parent = world
+            .spawn((NodeBundle::default(), ElementStyles::new(&[container])))
+            .push_children(&[label, child])
+            .id();
+
+        for entity in [parent, label, child] {
+            let computed = compute_style(&mut world, entity);
+            UpdateComputedStyle { entity, computed }.apply(&mut world);
+        }
+
+        // The container and its text are faded.
+        let bg = world.get::<BackgroundColor>(parent).unwrap().0;
+        assert_eq!(bg, Color::RED.with_a(0.5));
+        let text = world.get::<Text>(label).unwrap();
+        assert_eq!(text.sections[0].style.color.a(), 0.5);
+
+        // Opacity multiplies down the hierarchy.
+        let bg = world.get::<BackgroundColor>(child).unwrap().0;
+        assert_eq!(bg.a(), 0.25);
+    }
+
+    #[test]
+    fn test_opacity_transition() {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        let transition = [Transition {
+            property: TransitionProperty::Opacity,
+            duration: 1.,
+            ..default()
+        }];
+        let hidden = StyleHandle::build(|ss| {
+            ss.opacity(0.)
+                .background_color(Color::RED)
+                .transition(&transition)
+        });
+        let shown = StyleHandle::build(|ss| {
+            ss.opacity(1.)
+                .background_color(Color::RED)
+                .transition(&transition)
+        });
+        let entity = world
+            .spawn((NodeBundle::default(), ElementStyles::new(&[hidden.clone()])))
+            .id();
+        let restyle = |world: &mut World| {
+            let computed = compute_style(world, entity);
+            UpdateComputedStyle { entity, computed }.apply(world);
+            world.get::<BackgroundColor>(entity).unwrap().0.a()
+        };
+
+        // Nothing to animate from when first styled.
+        assert_eq!(restyle(&mut world), 0.);
+
+        // Fades in from the current opacity.
+        world
+            .entity_mut(entity)
+            .insert(ElementStyles::new(&[shown]));
+        assert_eq!(restyle(&mut world), 0.);
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(250));
+        world.run_system_once(animate_opacity);
+        assert!((restyle(&mut world) - 0.25).abs() < 1e-5);
+
+        // Interrupting the transition reverses it from where it got to.
+        world
+            .entity_mut(entity)
+            .insert(ElementStyles::new(&[hidden]));
+        assert!((restyle(&mut world) - 0.25).abs() < 1e-5);
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(500));
+        world.run_system_once(animate_opacity);
+        assert!((restyle(&mut world) - 0.125).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_border_radius() {
+        let mut world = World::new();
+        let style = StyleHandle::build(|ss| {
+            ss.border_radius(8)
+                .border_radius_top_left(0)
+                .when_hovered(|ss| ss.border_radius(12))
+        });
+        let entity = world
+            .spawn((
+                NodeBundle::default(),
+                ElementClasses::default(),
+                ElementStyles::new(&[style]),
+            ))
+            .id();
+        let computed = compute_style(&mut world, entity);
+        assert_eq!(computed.border_radius.top_left, Val::Px(0.));
+        assert_eq!(computed.border_radius.bottom_right, Val::Px(8.));
+        UpdateComputedStyle { entity, computed }.apply(&mut world);
+        assert_eq!(
+            world.get::<BorderRadius>(entity).unwrap().top_right,
+            Val::Px(8.)
+        );
+
+        // Hover overrides every corner.
+        world.insert_resource(hover_map(&[(entity, 0.)]));
+        let computed = compute_style(&mut world, entity);
+        assert_eq!(computed.border_radius, BorderRadius::all(Val::Px(12.)));
+
+        // Square corners remove the component.
+        let computed = ComputedStyle::new();
+        UpdateComputedStyle { entity, computed }.apply(&mut world);
+        assert!(world.get::<BorderRadius>(entity).is_none());
+    }
+
+    #[test]
+    fn test_z_index() {
+        let mut world = World::new();
+        let style = StyleHandle::build(|ss| ss.z_index(1).when_hovered(|ss| ss.z_index_global(10)));
+        let entity = world
+            .spawn((
+                NodeBundle::default(),
+                ElementClasses::default(),
+                ElementStyles::new(&[style]),
+            ))
+            .id();
+        let computed = compute_style(&mut world, entity);
+        UpdateComputedStyle { entity, computed }.apply(&mut world);
+        assert!(matches!(
+            world.get::<ZIndex>(entity),
+            Some(ZIndex::Local(1))
+        ));
+
+        // Hovering pops the element above everything else.
+        world.insert_resource(hover_map(&[(entity, 0.)]));
+        let computed = compute_style(&mut world, entity);
+        UpdateComputedStyle { entity, computed }.apply(&mut world);
+        assert!(matches!(
+            world.get::<ZIndex>(entity),
+            Some(ZIndex::Global(10))
+        ));
+
+        // No z-index removes the component.
+        let computed = ComputedStyle::new();
+        UpdateComputedStyle { entity, computed }.apply(&mut world);
+        assert!(world.get::<ZIndex>(entity).is_none());
+    }
+
+    #[test]
+    fn test_box_shadow() {
+        let mut world = World::new();
+        let card = StyleHandle::build(|ss| {
+            ss.box_shadow(Color::rgba(0., 0., 0., 0.5), 0, 2, 4, 0)
+                .box_shadow(Color::rgba(0., 0., 0., 0.2), 0, 0, 1, 1)
+                .when_hovered(|ss| ss.box_shadow(Color::rgba(0., 0., 0., 0.5), 0, 8, 16, 0))
+        });
+        let entity = world
+            .spawn((
+                NodeBundle::default(),
+                ElementClasses::default(),
+                ElementStyles::new(&[card]),
+            ))
+            .id();
+        let computed = compute_style(&mut world, entity);
+        assert_eq!(computed.box_shadow.len(), 2);
+        assert_eq!(computed.box_shadow[0].y_offset, Val::Px(2.));
+        assert_eq!(computed.box_shadow[1].color.a(), 0.2);
+        UpdateComputedStyle { entity, computed }.apply(&mut world);
+        assert_eq!(world.get::<BoxShadow>(entity).unwrap().0.len(), 2);
+
+        // Hovering replaces the whole stack.
+        world.insert_resource(hover_map(&[(entity, 0.)]));
+        let computed = compute_style(&mut world, entity);
+        assert_eq!(computed.box_shadow.len(), 1);
+        assert_eq!(computed.box_shadow[0].blur_radius, Val::Px(16.));
+
+        // No shadows removes the component.
+        let computed = ComputedStyle::new();
+        UpdateComputedStyle { entity, computed }.apply(&mut world);
+        assert!(world.get::<BoxShadow>(entity).is_none());
+    }
+
+    #[test]
+    fn test_cursor_icon() {
+        let mut app = App::new();
+        app.init_resource::<HoverMap>()
+            .add_systems(Update, update_cursor_icon);
+        let window = app.world.spawn((Window::default(), PrimaryWindow)).id();
+        let button = app
+            .world
+            .spawn((NodeBundle::default(), Cursor::Pointer))
+            .id();
+        let label = app
+            .world
+            .spawn(NodeBundle::default())
+            .set_parent(button)
+            .id();
+        let input = app.world.spawn((NodeBundle::default(), Cursor::Text)).id();
+        let icon = |app: &App| app.world.get::<Window>(window).unwrap().cursor.icon;
+
+        let hover = |app: &mut App, hits: &[(Entity, f32)]| {
+            app.insert_resource(hover_map(hits));
+            app.update();
+        };
+
+        // Descendants use the cursor of their ancestor.
+        hover(&mut app, &[(label, 0.)]);
+        assert_eq!(icon(&app), CursorIcon::Pointer);
+
+        // The nearest hovered element wins.
+        hover(&mut app, &[(label, 1.), (input, 0.)]);
+        assert_eq!(icon(&app), CursorIcon::Text);
+
+        // Hovering nothing restores the default.
+        hover(&mut app, &[]);
+        assert_eq!(icon(&app), CursorIcon::Default);
+    }
+
+    #[test]
+    fn test_content_visibility_auto() {
+        let mut app = test_app();
+
+        let item_style =
+            StyleHandle::build(|ss| ss.content_visibility(ContentVisibility::Auto).height(20));
+        let red = StyleHandle::build(|ss| ss.background_color(Color::RED));
+        let blue = StyleHandle::build(|ss| ss.background_color(Color::BLUE));
+
+        let mut items = Vec::new();
+        let mut labels = Vec::new();
+        let list = app.world.spawn(NodeBundle::default()).id();
+        for _ in 0..100 {
+            let label = app
+                .world
+                .spawn((NodeBundle::default(), ElementStyles::new(&[red.clone()])))
+                .id();
+            let item = app
+                .world
+                .spawn((
+                    NodeBundle::default(),
+                    ElementStyles::new(&[item_style.clone()]),
+                ))
+                .add_child(label)
+                .id();
+            app.world.entity_mut(list).add_child(item);
+            items.push(item);
+            labels.push(label);
+        }
+        app.update();
+
+        let background =
+            |world: &World, label: Entity| world.get::<BackgroundColor>(label).unwrap().0;
+        assert_eq!(background(&app.world, labels[50]), Color::RED);
+
+        // Only the first ten items are on-screen.
+        for item in &items[10..] {
+            app.world
+                .get_mut::<ContentVisibilityState>(*item)
+                .unwrap()
+                .offscreen = true;
+        }
+        for label in &labels {
+            app.world
+                .entity_mut(*label)
+                .insert(ElementStyles::new(&[blue.clone()]));
+        }
+        app.update();
+        assert_eq!(background(&app.world, labels[5]), Color::BLUE);
+        assert_eq!(background(&app.world, labels[50]), Color::RED);
+
+        // Scrolling an item into view restyles its contents.
+        app.world
+            .get_mut::<ContentVisibilityState>(items[50])
+            .unwrap()
+            .offscreen = false;
+        app.update();
+        assert_eq!(background(&app.world, labels[50]), Color::BLUE);
+        assert_eq!(background(&app.world, labels[60]), Color::RED);
+    }
+
+    #[test]
+    fn test_reparent_restyle() {
+        let mut app = test_app();
+
+        let red = StyleHandle::build(|ss| ss.color(Color::RED));
+        let blue = StyleHandle::build(|ss| ss.color(Color::BLUE));
+        let label = app
+            .world
+            .spawn(TextBundle::from_section("label", TextStyle::default()))
+            .id();
+        app.world
+            .spawn((NodeBundle::default(), ElementStyles::new(&[red])))
+            .add_child(label);
+        let overlay = app
+            .world
+            .spawn((NodeBundle::default(), ElementStyles::new(&[blue])))
+            .id();
+        app.update();
+
+        let color = |world: &World| world.get::<Text>(label).unwrap().sections[0].style.color;
+        assert_eq!(color(&app.world), Color::RED);
+
+        // Moving the node to a different parent, as a portal does, restyles it.
+        app.world.entity_mut(overlay).add_child(label);
+        app.update();
+        assert_eq!(color(&app.world), Color::BLUE);
+    }
+
+    #[test]
+    fn test_inherit_through_unstyled_node() {
+        let mut app = test_app();
+
+        let label = app
+            .world
+            .spawn(TextBundle::from_section("label", TextStyle::default()))
+            .id();
+        let wrapper = app.world.spawn(NodeBundle::default()).add_child(label).id();
+        let root = app
+            .world
+            .spawn((
+                NodeBundle::default(),
+                ElementStyles::new(&[StyleHandle::build(|ss| ss.color(Color::RED))]),
+            ))
+            .add_child(wrapper)
+            .id();
+        app.update();
+
+        let color = |world: &World| world.get::<Text>(label).unwrap().sections[0].style.color;
+        assert_eq!(color(&app.world), Color::RED);
+
+        // A change to the inherited styles passes through the wrapper, which has no styles.
+        app.world
+            .entity_mut(root)
+            .insert(ElementStyles::new(&[StyleHandle::build(|ss| {
+                ss.color(Color::BLUE)
+            })]));
+        app.update();
+        assert_eq!(color(&app.world), Color::BLUE);
+    }
+
+    #[test]
+    fn test_nth_child_restyle() {
+        let mut app = test_app();
+
+        let row_style = StyleHandle::build(|ss| {
+            ss.background_color(Color::BLUE)
+                .selector("&:nth-child(odd)", |ss| ss.background_color(Color::RED))
+        });
+        let rows: Vec<Entity> = (0..4)
+            .map(|_| {
+                app.world
+                    .spawn((
+                        NodeBundle::default(),
+                        ElementStyles::new(&[row_style.clone()]),
+                    ))
+                    .id()
+            })
+            .collect();
+        let list = app
+            .world
+            .spawn(NodeBundle::default())
+            .push_children(&rows)
+            .id();
+        app.update();
+
+        let background = |world: &World, row: Entity| world.get::<BackgroundColor>(row).unwrap().0;
+        assert_eq!(background(&app.world, rows[0]), Color::RED);
+        assert_eq!(background(&app.world, rows[1]), Color::BLUE);
+        assert_eq!(background(&app.world, rows[2]), Color::RED);
+
+        // Removing a row shifts the stripes of the rows after it.
+        app.world.entity_mut(list).remove_children(&rows[..1]);
+        app.update();
+        assert_eq!(background(&app.world, rows[1]), Color::RED);
+        assert_eq!(background(&app.world, rows[2]), Color::BLUE);
+        assert_eq!(background(&app.world, rows[3]), Color::RED);
+    }
+
+    #[test]
+    fn test_cascade_large_tree() {
+        let mut app = test_app();
+        let theme = StyleHandle::build(|ss| {
+            ss.color(Color::WHITE)
+                .selector("&.dark", |ss| ss.color(Color::BLACK))
+        });
+        let root = app
+            .world
+            .spawn((
+                NodeBundle::default(),
+                ElementClasses::default(),
+                ElementStyles::new(&[theme]),
+            ))
+            .id();
+        let mut labels = Vec::new();
+        for _ in 0..10 {
+            let section = app.world.spawn(NodeBundle::default()).set_parent(root).id();
+            for _ in 0..100 {
+                let label = app
+                    .world
+                    .spawn(TextBundle::from_section("label", TextStyle::default()))
+                    .set_parent(section)
+                    .id();
+                labels.push(label);
+            }
+        }
+        app.update();
+
+        let color = |world: &World, label: Entity| {
+            world.get::<Text>(label).unwrap().sections[0].style.color
+        };
+        assert!(labels.iter().all(|l| color(&app.world, *l) == Color::WHITE));
+
+        // Toggling a class on the root restyles every node beneath it.
+        app.world
+            .get_mut::<ElementClasses>(root)
+            .unwrap()
+            .add_class("dark");
+        app.update();
+        assert!(labels.iter().all(|l| color(&app.world, *l) == Color::BLACK));
+    }
+}
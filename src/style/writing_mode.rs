@@ -0,0 +1,89 @@
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Controls the direction in which the content of an element is laid out, a subset of the
+/// CSS `writing-mode` property. Bevy has no native support for vertical text, so the
+/// vertical modes are implemented by rotating the element by 90 degrees, and adjusting its
+/// margins so that its layout footprint is the size of the rotated element. This works
+/// best for single-line labels, such as axis titles; because the margins are used to
+/// adjust the footprint, any margins set on a vertical element are ignored.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WritingMode {
+    /// Content flows horizontally, from left to right.
+    #[default]
+    HorizontalTb,
+
+    /// Content is rotated clockwise, so that text reads from top to bottom.
+    VerticalRl,
+
+    /// Content is rotated counter-clockwise, so that text reads from bottom to top.
+    SidewaysLr,
+}
+
+impl WritingMode {
+    /// Rotation which is applied to the element's transform.
+    pub(crate) fn rotation(self) -> f32 {
+        match self {
+            WritingMode::HorizontalTb => 0.,
+            // UI coordinates are y-down, so a positive rotation is clockwise.
+            WritingMode::VerticalRl => FRAC_PI_2,
+            WritingMode::SidewaysLr => -FRAC_PI_2,
+        }
+    }
+
+    /// True if this is one of the rotated modes.
+    pub(crate) fn is_vertical(self) -> bool {
+        self != WritingMode::HorizontalTb
+    }
+}
+
+/// Component added to elements with a vertical [`WritingMode`], which records the size of the
+/// element before rotation, as of the most recent layout.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct VerticalFootprint {
+    pub(crate) size: Vec2,
+}
+
+/// Compute the margins which give an element of the given (unrotated) size a layout footprint
+/// with the width and height swapped. Since rotation is about the center of the element, the
+/// difference is split evenly between both sides.
+pub(crate) fn footprint_margin(size: Vec2) -> UiRect {
+    let dx = (size.y - size.x) * 0.5;
+    let dy = (size.x - size.y) * 0.5;
+    UiRect::new(Val::Px(dx), Val::Px(dx), Val::Px(dy), Val::Px(dy))
+}
+
+/// Update the margins of vertical elements whose size has changed since the last layout.
+/// Margins don't affect the size of the element's own content, so this converges after a
+/// single frame.
+pub(crate) fn update_vertical_footprints(
+    mut query: Query<(&Node, &mut VerticalFootprint, &mut Style)>,
+) {
+    for (node, mut footprint, mut style) in query.iter_mut() {
+        let size = node.size();
+        if footprint.size != size {
+            footprint.size = size;
+            style.margin = footprint_margin(size);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_footprint_margin() {
+        let size = Vec2::new(80., 20.);
+        let margin = footprint_margin(size);
+        let resolve = |val: Val| match val {
+            Val::Px(px) => px,
+            _ => panic!("expected pixels"),
+        };
+        let width = size.x + resolve(margin.left) + resolve(margin.right);
+        let height = size.y + resolve(margin.top) + resolve(margin.bottom);
+        assert_eq!(Vec2::new(width, height), Vec2::new(20., 80.));
+    }
+}
@@ -0,0 +1,77 @@
+use bevy::{a11y::Focus, prelude::*};
+
+/// Marker component which requests that an element be given [`Focus`]. Added when an
+/// [`Element`](crate::Element) with [`autofocus`](crate::Element::autofocus) is first built,
+/// and removed once the request has been applied.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct AutoFocus;
+
+/// System which gives focus to newly built autofocus elements.
+///
+/// This runs after views are rendered, and before styles are updated, so an element which is
+/// built with autofocus matches `:focus` selectors on the same frame: the style system sees
+/// that [`Focus`] differs from its record of the previous focus, and restyles both the old and
+/// new focus targets. Elements which are razed before this system runs have already been
+/// despawned, so their request is dropped and focus is left unchanged. If several elements
+/// request focus in the same frame, an arbitrary one wins.
+pub(crate) fn apply_autofocus(
+    mut commands: Commands,
+    query: Query<Entity, Added<AutoFocus>>,
+    focus: Option<ResMut<Focus>>,
+) {
+    let Some(mut focus) = focus else {
+        return;
+    };
+    for entity in query.iter() {
+        focus.0 = Some(entity);
+        commands.entity(entity).remove::<AutoFocus>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+    use crate::{
+        presenter_state::{AnyPresenterState, PresenterState},
+        BuildContext, Cx, Element, If, View,
+    };
+
+    #[derive(Resource)]
+    struct DialogOpen(bool);
+
+    fn dialog(cx: Cx) -> impl View {
+        let open = cx.use_resource::<DialogOpen>().0;
+        If::new(open, Element::new().autofocus(), ())
+    }
+
+    #[test]
+    fn test_autofocus() {
+        let mut world = World::new();
+        world.init_resource::<Focus>();
+        world.insert_resource(DialogOpen(true));
+        let root = world.spawn_empty().id();
+        let mut state = PresenterState::new(dialog, ());
+        state.build(&mut BuildContext::new(&mut world, root), root);
+        world.run_system_once(apply_autofocus);
+        let focused = world.resource::<Focus>().0.unwrap();
+        assert!(!world.entity(focused).contains::<AutoFocus>());
+
+        // Focus is only requested on the first build.
+        world.resource_mut::<Focus>().0 = None;
+        state.build(&mut BuildContext::new(&mut world, root), root);
+        world.run_system_once(apply_autofocus);
+        assert_eq!(world.resource::<Focus>().0, None);
+
+        // Razing the element before the request is applied leaves focus unchanged.
+        world.insert_resource(DialogOpen(false));
+        state.build(&mut BuildContext::new(&mut world, root), root);
+        world.insert_resource(DialogOpen(true));
+        state.build(&mut BuildContext::new(&mut world, root), root);
+        world.insert_resource(DialogOpen(false));
+        state.build(&mut BuildContext::new(&mut world, root), root);
+        world.run_system_once(apply_autofocus);
+        assert_eq!(world.resource::<Focus>().0, None);
+    }
+}
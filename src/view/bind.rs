@@ -110,7 +110,8 @@ impl View for Bind {
         };
         // Update child view properties. This transfers the props from the 'new' presenter
         // that is a member of the Bind, to the 'old' presenter state which is stored in the
-        // view handle. The old state is the one that will persist.
+        // view handle. The old state is the one that will persist. If the props compare
+        // equal, the presenter is not marked as changed and will not be re-run.
         if self.binding.update_handle_props(&mut handle) {
             entt.insert(PresenterStateChanged);
         }
@@ -147,3 +148,97 @@ impl PartialEq for Bind {
         self.binding.eq(&*other.binding)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::{plugin::render_frame, Callback, Cx, For, Fragment};
+
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Clone, PartialEq)]
+    struct Props {
+        value: i32,
+        on_click: Callback,
+    }
+
+    fn child(_cx: Cx<Props>) -> impl View {
+        CALLS.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[test]
+    fn test_unchanged_props() {
+        let mut world = World::new();
+        let root = world.spawn_empty().id();
+        let on_click = Callback::new(|_| {});
+        let props = Props {
+            value: 1,
+            on_click: on_click.clone(),
+        };
+
+        let view = child.bind(props.clone());
+        let mut state = view.build(&mut BuildContext::new(&mut world, root));
+        render_frame(&mut world);
+        assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+
+        // Equal props, including a clone of the same callback: presenter is not re-run.
+        let view = child.bind(props.clone());
+        view.update(&mut BuildContext::new(&mut world, root), &mut state);
+        render_frame(&mut world);
+        assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+
+        // A new callback compares unequal, even though the closure is the same.
+        let view = child.bind(Props {
+            value: 1,
+            on_click: Callback::new(|_| {}),
+        });
+        view.update(&mut BuildContext::new(&mut world, root), &mut state);
+        render_frame(&mut world);
+        assert_eq!(CALLS.load(Ordering::Relaxed), 2);
+
+        // Changed props: presenter is re-run.
+        let view = child.bind(Props {
+            value: 2,
+            on_click,
+        });
+        view.update(&mut BuildContext::new(&mut world, root), &mut state);
+        render_frame(&mut world);
+        assert_eq!(CALLS.load(Ordering::Relaxed), 3);
+    }
+
+    static BUTTON_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Resource)]
+    struct Title(&'static str);
+
+    fn button(cx: Cx<usize>) -> impl View {
+        BUTTON_CALLS.fetch_add(1, Ordering::Relaxed);
+        format!("Button {}", cx.props)
+    }
+
+    fn button_list(cx: Cx) -> impl View {
+        let buttons: Vec<usize> = (0..100).collect();
+        Fragment::new((
+            cx.use_resource::<Title>().0,
+            For::index(&buttons, |i, _| button.bind(*i)),
+        ))
+    }
+
+    #[test]
+    fn test_unchanged_props_list() {
+        let mut world = World::new();
+        world.insert_resource(Title("Buttons"));
+        world.spawn(ViewHandle::new(button_list, ()));
+        render_frame(&mut world);
+        assert_eq!(BUTTON_CALLS.load(Ordering::Relaxed), 100);
+
+        // Rebuilding the list re-runs none of the 100 buttons, since their props are equal.
+        for title in ["More buttons", "Even more buttons"] {
+            world.resource_mut::<Title>().0 = title;
+            render_frame(&mut world);
+        }
+        assert_eq!(BUTTON_CALLS.load(Ordering::Relaxed), 100);
+    }
+}
@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+/// A shareable closure which can be passed as part of a presenter's props.
+///
+/// Presenter props must implement `PartialEq` so that [`crate::Bind`] can skip re-running a
+/// child presenter when its props are unchanged. Closures don't implement `PartialEq`, so
+/// `Callback` compares by pointer instead: clones of the same callback are equal, while two
+/// separately-constructed callbacks are not, even if the closures are identical. This means
+/// that a parent which creates a new callback each time it renders will always re-run the
+/// child; to avoid that, construct the callback once (for example with `use_memo`) and clone it.
+pub struct Callback<P = ()> {
+    inner: Arc<dyn Fn(P) + Send + Sync>,
+}
+
+impl<P> Callback<P> {
+    /// Construct a new callback from a closure.
+    pub fn new(f: impl Fn(P) + Send + Sync + 'static) -> Self {
+        Self { inner: Arc::new(f) }
+    }
+
+    /// Invoke the callback.
+    pub fn call(&self, arg: P) {
+        (self.inner)(arg)
+    }
+}
+
+impl<P> Clone for Callback<P> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<P> PartialEq for Callback<P> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[test]
+    fn test_callback_eq() {
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        let a = Callback::new(|n: usize| {
+            CALLS.fetch_add(n, Ordering::Relaxed);
+        });
+        let b = Callback::new(|n: usize| {
+            CALLS.fetch_add(n, Ordering::Relaxed);
+        });
+        assert!(a == a.clone());
+        assert!(a != b);
+        a.call(2);
+        b.call(3);
+        assert_eq!(CALLS.load(Ordering::Relaxed), 5);
+    }
+}
@@ -0,0 +1,38 @@
+use std::any::TypeId;
+
+use bevy::{prelude::*, utils::HashMap};
+
+type PendingWrite = Box<dyn FnOnce(&mut World) + Send + Sync>;
+
+/// Resource writes requested by [`Cx::write_resource_coalesced`], keyed by resource type.
+/// Only the most recent write for each type is kept, and all pending writes are flushed
+/// once per frame.
+///
+/// [`Cx::write_resource_coalesced`]: crate::Cx::write_resource_coalesced
+#[derive(Resource, Default)]
+pub(crate) struct CoalescedWrites(HashMap<TypeId, PendingWrite>);
+
+impl CoalescedWrites {
+    /// Replace any pending write of resource `T` with `value`.
+    pub(crate) fn write<T: Resource>(&mut self, value: T) {
+        self.0.insert(
+            TypeId::of::<T>(),
+            Box::new(move |world: &mut World| world.insert_resource(value)),
+        );
+    }
+}
+
+/// System which applies all pending coalesced writes, so that each written resource is
+/// changed at most once per frame.
+pub(crate) fn flush_coalesced_writes(world: &mut World) {
+    let Some(mut writes) = world.get_resource_mut::<CoalescedWrites>() else {
+        return;
+    };
+    if writes.0.is_empty() {
+        return;
+    }
+    let pending = std::mem::take(&mut writes.0);
+    for (_, write) in pending {
+        write(world);
+    }
+}
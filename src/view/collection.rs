@@ -0,0 +1,76 @@
+use std::hash::Hash;
+
+use bevy::{ecs::component::Tick, utils::HashMap};
+
+/// Result of [`Cx::use_collection`]: the current items of a keyed collection, along with the
+/// keys of the items which were added, removed or changed since the previous render.
+///
+/// [`Cx::use_collection`]: crate::Cx::use_collection
+#[derive(Clone, Debug, PartialEq)]
+pub struct Collection<K, V> {
+    /// The current items of the collection, in order.
+    pub items: Vec<(K, V)>,
+    /// Keys of items which were not present on the previous render.
+    pub added: Vec<K>,
+    /// Keys of items which were present on the previous render, but are no longer.
+    pub removed: Vec<K>,
+    /// Keys of items whose value differs from the previous render.
+    pub changed: Vec<K>,
+}
+
+impl<K, V> Collection<K, V> {
+    /// True if no items were added, removed or changed since the previous render.
+    pub fn is_unchanged(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Snapshot of a collection as of the previous render, along with the change tick of the
+/// resource it was taken from.
+pub(crate) struct CollectionSnapshot<K, V> {
+    pub(crate) tick: Tick,
+    pub(crate) items: Vec<(K, V)>,
+}
+
+/// Compare two snapshots of a collection by key, returning the keys of added, removed and
+/// changed items, in collection order.
+pub(crate) fn diff_collection<K: Clone + Eq + Hash, V: PartialEq>(
+    prev: &[(K, V)],
+    next: &[(K, V)],
+) -> (Vec<K>, Vec<K>, Vec<K>) {
+    let prev_index: HashMap<&K, &V> = prev.iter().map(|(k, v)| (k, v)).collect();
+    let next_index: HashMap<&K, &V> = next.iter().map(|(k, v)| (k, v)).collect();
+    let mut added = Vec::new();
+    let mut changed = Vec::new();
+    for (key, value) in next {
+        match prev_index.get(key) {
+            None => added.push(key.clone()),
+            Some(prev_value) if *prev_value != value => changed.push(key.clone()),
+            Some(_) => {}
+        }
+    }
+    let removed = prev
+        .iter()
+        .filter(|(key, _)| !next_index.contains_key(key))
+        .map(|(key, _)| key.clone())
+        .collect();
+    (added, removed, changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_collection() {
+        let prev = vec![(1, "a"), (2, "b"), (3, "c")];
+        let next = vec![(1, "a"), (3, "C"), (4, "d")];
+        let (added, removed, changed) = diff_collection(&prev, &next);
+        assert_eq!(added, vec![4]);
+        assert_eq!(removed, vec![2]);
+        assert_eq!(changed, vec![3]);
+
+        let (added, removed, changed) = diff_collection(&next, &next);
+        assert!(added.is_empty() && removed.is_empty() && changed.is_empty());
+    }
+}
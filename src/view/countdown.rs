@@ -0,0 +1,166 @@
+use bevy::prelude::*;
+
+/// Component which drives a countdown created by [`Cx::use_countdown`]. The countdown is
+/// advanced by a system each frame, but the component is only marked as changed (causing
+/// the presenter to rebuild) at the sample rate, or when the countdown finishes.
+///
+/// [`Cx::use_countdown`]: crate::Cx::use_countdown
+#[derive(Component, Clone, Debug)]
+pub struct CountdownTimer {
+    duration: f32,
+    elapsed: f32,
+    interval: f32,
+    since_sample: f32,
+    sampled_remaining: f32,
+    paused: bool,
+    pub(crate) notified: bool,
+}
+
+impl CountdownTimer {
+    pub(crate) fn new(duration: f32, hz: f32) -> Self {
+        Self {
+            duration,
+            elapsed: 0.,
+            interval: if hz > 0. { 1. / hz } else { 0. },
+            since_sample: 0.,
+            sampled_remaining: duration,
+            paused: false,
+            notified: false,
+        }
+    }
+
+    /// Remaining time in seconds, as of the most recent sample.
+    pub fn remaining(&self) -> f32 {
+        self.sampled_remaining
+    }
+
+    /// True if the countdown has reached zero.
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    /// True if the countdown is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Stop the countdown from advancing.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Continue a paused countdown.
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Restart the countdown from the full duration.
+    pub fn reset(&mut self) {
+        self.elapsed = 0.;
+        self.since_sample = 0.;
+        self.sampled_remaining = self.duration;
+        self.notified = false;
+    }
+
+    /// Advance the countdown by `delta` seconds. Returns true if a new sample was taken,
+    /// meaning that observers should be notified.
+    pub(crate) fn tick(&mut self, delta: f32) -> bool {
+        if self.paused || self.is_finished() {
+            return false;
+        }
+        self.elapsed = (self.elapsed + delta).min(self.duration);
+        self.since_sample += delta;
+        if self.is_finished() || self.since_sample >= self.interval {
+            self.since_sample = 0.;
+            self.sampled_remaining = self.duration - self.elapsed;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Handle which can be used to control a countdown from outside of the presenter, for
+/// example from an event handler.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CountdownHandle(pub(crate) Entity);
+
+impl CountdownHandle {
+    /// Stop the countdown from advancing.
+    pub fn pause(&self, commands: &mut Commands) {
+        self.modify(commands, CountdownTimer::pause);
+    }
+
+    /// Continue a paused countdown.
+    pub fn resume(&self, commands: &mut Commands) {
+        self.modify(commands, CountdownTimer::resume);
+    }
+
+    /// Restart the countdown from the full duration.
+    pub fn reset(&self, commands: &mut Commands) {
+        self.modify(commands, CountdownTimer::reset);
+    }
+
+    fn modify(&self, commands: &mut Commands, f: fn(&mut CountdownTimer)) {
+        let id = self.0;
+        commands.add(move |world: &mut World| {
+            if let Some(mut timer) = world.get_mut::<CountdownTimer>(id) {
+                f(&mut timer);
+            }
+        });
+    }
+}
+
+/// Result of [`Cx::use_countdown`].
+///
+/// [`Cx::use_countdown`]: crate::Cx::use_countdown
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Countdown {
+    /// Remaining time in seconds.
+    pub remaining: f32,
+    /// True if the countdown has reached zero.
+    pub finished: bool,
+    /// True only for the first render after the countdown reached zero.
+    pub just_finished: bool,
+    /// Handle used to pause, resume or reset the countdown.
+    pub handle: CountdownHandle,
+}
+
+/// System which advances all countdowns.
+pub(crate) fn update_countdowns(mut query: Query<&mut CountdownTimer>, time: Res<Time>) {
+    let delta = time.delta_seconds();
+    for mut timer in query.iter_mut() {
+        if timer.bypass_change_detection().tick(delta) {
+            timer.set_changed();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_countdown_sampling() {
+        let mut timer = CountdownTimer::new(1., 4.);
+        // Not enough time for a sample
+        assert!(!timer.tick(0.1));
+        assert_eq!(timer.remaining(), 1.);
+        assert!(timer.tick(0.2));
+        assert!((timer.remaining() - 0.7).abs() < 1e-5);
+
+        timer.pause();
+        assert!(!timer.tick(0.5));
+        timer.resume();
+
+        // Finishing always takes a sample, exactly once.
+        assert!(timer.tick(0.9));
+        assert!(timer.is_finished());
+        assert_eq!(timer.remaining(), 0.);
+        assert!(!timer.tick(0.1));
+
+        timer.reset();
+        assert!(!timer.is_finished());
+        assert_eq!(timer.remaining(), 1.);
+    }
+}
@@ -1,17 +1,56 @@
-use std::{cell::RefCell, cmp::Ordering, marker::PhantomData};
+use std::{any::TypeId, cell::RefCell, cmp::Ordering, hash::Hash, marker::PhantomData};
 
-use bevy::prelude::*;
+use bevy::{
+    a11y::Focus,
+    ecs::{
+        component::{ComponentId, Tick},
+        query::{
+            FilteredAccess, QueryFilter, QueryState, ROQueryItem, ReadOnlyQueryData, WorldQuery,
+        },
+    },
+    prelude::*,
+};
 
-use crate::{tracked_resources::TrackedResource, BuildContext, ScopedValueKey, TrackingContext};
+use crate::{
+    tracked_resources::{
+        TrackedQuery, TrackedResource, TrackedResourcePredicate, TrackedResourcePresence,
+        TrackedResourceProjection, TrackedResourceSelector,
+    },
+    BuildContext, QuillSettings, ScopedValueKey, TrackingContext,
+};
 
 use super::{
     atom::{AtomCell, AtomHandle, AtomMethods},
+    coalesced::CoalescedWrites,
+    collection::{diff_collection, Collection, CollectionSnapshot},
+    countdown::{Countdown, CountdownHandle, CountdownTimer},
+    drag::{DragState, DragStates},
+    effect::{run_cleanup, Cleanup, EffectCleanup},
+    element_ref::{ElementRef, ElementRefNodes},
+    history::HistorySampler,
+    lerp::LerpedValue,
+    memo::{CleanupMemo, Memo},
+    rate_limit::{interval, RateLimit},
+    resource_tuple::ResourceTuple,
     scoped_values::ScopedValueMap,
+    tracking::validate_hook,
 };
 
+/// Cached result of [`Cx::use_resource_map`], along with the resource change tick it was
+/// derived from.
+struct MappedResource<R> {
+    tick: Tick,
+    value: R,
+}
+
 /// Cx is a context parameter that is passed to presenters. It contains the presenter's
 /// properties (passed from the parent presenter), plus other context information needed
 /// in building the view state graph.
+///
+/// Hooks such as [`Cx::create_atom`] and [`Cx::use_effect`] store their state by call
+/// position, so they must be called in the same order on every render: calling a hook
+/// conditionally, or in a loop whose length varies between renders, is not allowed. Setting
+/// [`crate::QuillSettings::strict_hooks`] validates this in debug builds.
 pub struct Cx<'w, 'p, Props = ()> {
     /// The properties that were passed to the presenter from it's parent.
     pub props: &'p Props,
@@ -33,6 +72,12 @@ impl<'w, 'p, Props> Cx<'w, 'p, Props> {
         }
     }
 
+    /// Returns true if this is the first time the presenter has been built, and false on
+    /// every subsequent rebuild.
+    pub fn is_first_render(&self) -> bool {
+        self.tracking.borrow().first_render
+    }
+
     /// Return a reference to the resource of the given type. Calling this function
     /// adds the resource as a dependency of the current presenter invocation.
     pub fn use_resource<T: Resource>(&self) -> &T {
@@ -40,6 +85,525 @@ impl<'w, 'p, Props> Cx<'w, 'p, Props> {
         self.bc.world.resource::<T>()
     }
 
+    /// Return a clone of the resource `T` if it exists, or `default` if it doesn't. Calling
+    /// this function adds the resource as a dependency of the current presenter invocation,
+    /// so the presenter will rebuild when the resource changes, and also when it is inserted
+    /// or removed.
+    pub fn use_resource_or<T: Resource + Clone>(&self, default: T) -> T {
+        let value = self.bc.world.get_resource::<T>();
+        self.tracking
+            .borrow_mut()
+            .resources
+            .push(Box::new(TrackedResourcePresence::<T>::new(value.is_some())));
+        value.cloned().unwrap_or(default)
+    }
+
+    /// Return the result of `predicate` applied to the resource `T`. Unlike
+    /// [`Cx::use_resource`], the presenter is only re-run when a change to the resource causes
+    /// the result of the predicate to change, rather than on every change to the resource.
+    pub fn use_resource_when<T: Resource>(
+        &self,
+        predicate: impl Fn(&T) -> bool + Send + Sync + 'static,
+    ) -> bool {
+        let result = predicate(self.bc.world.resource::<T>());
+        self.tracking
+            .borrow_mut()
+            .resources
+            .push(Box::new(TrackedResourcePredicate::<T, _>::new(
+                predicate, result,
+            )));
+        result
+    }
+
+    /// Return a reference to a part of the resource `T`, picked out by `selector`. Unlike
+    /// [`Cx::use_resource`], the presenter is only re-run when a change to the resource
+    /// changes the selected value, rather than on every change to the resource. The
+    /// [`use_field!`] macro generates the selector for a field path.
+    ///
+    /// The value is borrowed from the resource, and checking for changes compares it in
+    /// place, so this suits large fields. To select a value which isn't stored in the
+    /// resource, such as a length or a tuple of fields, use [`Cx::use_resource_selector`].
+    ///
+    /// [`use_field!`]: crate::use_field
+    pub fn use_resource_select<T: Resource, R: PartialEq + Clone + Send + Sync + 'static>(
+        &self,
+        selector: impl Fn(&T) -> &R + Send + Sync + 'static,
+    ) -> &R {
+        let value = selector(self.bc.world.resource::<T>());
+        self.tracking.borrow_mut().resources.push(Box::new(
+            TrackedResourceSelector::<T, R, _>::new(selector, value.clone()),
+        ));
+        value
+    }
+
+    /// Return a value computed from the resource `T` by `select`, such as the length of a
+    /// list or a tuple of several fields. Like [`Cx::use_resource_select`], the presenter is
+    /// only re-run when a change to the resource changes the selected value, but since the
+    /// value is computed rather than borrowed from the resource, it is returned and stored
+    /// by value. The selector is run again on each change to the resource, so it should be
+    /// cheap; to track a large field without copying it, use [`Cx::use_resource_select`].
+    pub fn use_resource_selector<T: Resource, U: PartialEq + Clone + Send + Sync + 'static>(
+        &self,
+        select: impl Fn(&T) -> U + Send + Sync + 'static,
+    ) -> U {
+        let value = select(self.bc.world.resource::<T>());
+        self.tracking.borrow_mut().resources.push(Box::new(
+            TrackedResourceProjection::<T, U, _>::new(select, value.clone()),
+        ));
+        value
+    }
+
+    /// Return the results of a read-only query over the world, such as `Q = &Name` with
+    /// `F = With<Selected>`. The presenter is re-run when the set of matching entities
+    /// changes, or when any of the components read by `Q` are changed on a matching entity.
+    /// Note that changes are detected using change ticks, not by comparing values, so writing
+    /// an identical value to a component still causes a rebuild. Checking for changes visits
+    /// every matching entity, so queries should be narrow.
+    pub fn use_query<Q: ReadOnlyQueryData + 'static, F: QueryFilter + 'static>(
+        &mut self,
+    ) -> Vec<ROQueryItem<'_, Q>> {
+        let world = &mut *self.bc.world;
+        let mut access = FilteredAccess::<ComponentId>::default();
+        let query_state = Q::init_state(world);
+        Q::update_component_access(&query_state, &mut access);
+        let components: Vec<ComponentId> = access.access().reads().collect();
+        let tracked = QueryState::<(Entity, Q), F>::new(world);
+        let entities: Vec<Entity> = tracked.iter_manual(world).map(|(e, _)| e).collect();
+        self.tracking
+            .borrow_mut()
+            .resources
+            .push(Box::new(TrackedQuery::new(tracked, components, entities)));
+
+        let state = QueryState::<Q, F>::new(self.bc.world);
+        let world: &World = self.bc.world;
+        state.iter_manual(world).collect()
+    }
+
+    /// Returns true if the resource `T` was just added: that is, on the first render after the
+    /// resource is inserted into the world, but not on renders caused by later changes to the
+    /// resource. If the resource is removed and inserted again, this will return true again.
+    /// Calling this function adds the resource as a dependency of the current presenter
+    /// invocation, so the presenter will rebuild when the resource is inserted.
+    pub fn use_resource_added<T: Resource>(&mut self) -> bool {
+        self.add_tracked_resource::<T>();
+        let is_added = self
+            .bc
+            .world
+            .get_resource_ref::<T>()
+            .map(|res| res.is_added());
+        let handle = self.create_atom_handle::<bool>("use_resource_added");
+        let mut entt = self.bc.world.entity_mut(handle.id);
+        let present = is_added.is_some();
+        match entt.get_mut::<AtomCell>() {
+            Some(mut cell) => {
+                let was_present = cell
+                    .0
+                    .downcast_mut::<bool>()
+                    .expect("Atom is incorrect type");
+                let result = present && !*was_present;
+                *was_present = present;
+                result
+            }
+            None => {
+                // First render: the resource may have been added earlier, so rely on Bevy's
+                // change detection to tell whether it was added this frame.
+                entt.insert(AtomCell(Box::new(present)));
+                is_added.unwrap_or(false)
+            }
+        }
+    }
+
+    /// Return a clone of the resource `T`, as of the first time it is read during this render.
+    /// Every call within the same render returns the same value, even if the resource is
+    /// modified in between, so that all parts of the presenter see a consistent view of it.
+    /// Calling this function adds the resource as a dependency of the current presenter
+    /// invocation.
+    ///
+    /// The resource is cloned once per render, and again for each call, so for large
+    /// resources, prefer [`Cx::use_resource`] or [`Cx::use_resource_map`].
+    pub fn snapshot_resource<T: Resource + Clone>(&self) -> T {
+        self.add_tracked_resource::<T>();
+        self.tracking
+            .borrow_mut()
+            .snapshots
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(self.bc.world.resource::<T>().clone()))
+            .downcast_ref::<T>()
+            .expect("Snapshot is incorrect type")
+            .clone()
+    }
+
+    /// Return a `(prev, current)` pair of values of the resource `T`, where `prev` is the value
+    /// of the resource as of the previous render of this presenter. This is useful for
+    /// reactions which need both values, such as animating from the old value to the new.
+    /// On the first render, `prev` is the same as `current`. Calling this function adds the
+    /// resource as a dependency of the current presenter invocation.
+    pub fn use_resource_transition<T: Resource + Clone>(&mut self) -> (T, T) {
+        let current = self.use_resource::<T>().clone();
+        let handle = self.create_atom_handle::<T>("use_resource_transition");
+        let mut entt = self.bc.world.entity_mut(handle.id);
+        match entt.get_mut::<AtomCell>() {
+            Some(mut cell) => {
+                let prev = cell.0.downcast_mut::<T>().expect("Atom is incorrect type");
+                (std::mem::replace(prev, current.clone()), current)
+            }
+            None => {
+                entt.insert(AtomCell(Box::new(current.clone())));
+                (current.clone(), current)
+            }
+        }
+    }
+
+    /// Return references to each of the resources in the tuple `T`, for example
+    /// `cx.use_resources::<(A, B, C)>()`. Calling this function adds all of the resources as
+    /// dependencies of the current presenter invocation.
+    ///
+    /// Panics if any of the resources does not exist, naming the missing resource.
+    pub fn use_resources<T: ResourceTuple>(&self) -> T::Item<'_> {
+        T::track(self);
+        T::fetch(self.bc.world)
+    }
+
+    /// Return a value derived from the resource `T`. The result of `derive` is cached, and
+    /// is only recomputed when the resource changes. Calling this function adds the resource
+    /// as a dependency of the current presenter invocation.
+    pub fn use_resource_map<T: Resource, R: Send + Sync + 'static>(
+        &mut self,
+        derive: impl FnOnce(&T) -> R,
+    ) -> &R {
+        self.add_tracked_resource::<T>();
+        let id = self.create_hook_entity("use_resource_map", std::any::type_name::<R>());
+        let changed = self.bc.world.resource_ref::<T>().last_changed();
+        let stale = match self.bc.world.get::<AtomCell>(id) {
+            Some(cell) => {
+                cell.0
+                    .downcast_ref::<MappedResource<R>>()
+                    .expect("Atom is incorrect type")
+                    .tick
+                    != changed
+            }
+            None => true,
+        };
+        if stale {
+            let value = derive(self.bc.world.resource::<T>());
+            self.bc
+                .world
+                .entity_mut(id)
+                .insert(AtomCell(Box::new(MappedResource {
+                    tick: changed,
+                    value,
+                })));
+        }
+        &self
+            .bc
+            .world
+            .get::<AtomCell>(id)
+            .unwrap()
+            .0
+            .downcast_ref::<MappedResource<R>>()
+            .unwrap()
+            .value
+    }
+
+    /// Return the items of a keyed collection held by the resource `T`, as extracted by
+    /// `accessor`, along with the keys of items which were added, removed or changed since the
+    /// previous render. The diff can be used to only update the rows which are affected. If
+    /// the resource hasn't changed since the previous render, the accessor isn't called and
+    /// the comparison is skipped. Calling this function adds the resource as a dependency of
+    /// the current presenter invocation.
+    pub fn use_collection<
+        T: Resource,
+        K: Clone + Eq + Hash + Send + Sync + 'static,
+        V: Clone + PartialEq + Send + Sync + 'static,
+    >(
+        &mut self,
+        accessor: impl FnOnce(&T) -> Vec<(K, V)>,
+    ) -> Collection<K, V> {
+        self.add_tracked_resource::<T>();
+        let id = self.create_hook_entity("use_collection", std::any::type_name::<(K, V)>());
+        let tick = self.bc.world.resource_ref::<T>().last_changed();
+        if let Some(cell) = self.bc.world.get::<AtomCell>(id) {
+            let snapshot = cell
+                .0
+                .downcast_ref::<CollectionSnapshot<K, V>>()
+                .expect("Atom is incorrect type");
+            // Fast path: the resource hasn't changed, so neither has the collection.
+            if snapshot.tick == tick {
+                return Collection {
+                    items: snapshot.items.clone(),
+                    added: Vec::new(),
+                    removed: Vec::new(),
+                    changed: Vec::new(),
+                };
+            }
+        }
+
+        let items = accessor(self.bc.world.resource::<T>());
+        let mut entt = self.bc.world.entity_mut(id);
+        match entt.get_mut::<AtomCell>() {
+            Some(mut cell) => {
+                let snapshot = cell
+                    .0
+                    .downcast_mut::<CollectionSnapshot<K, V>>()
+                    .expect("Atom is incorrect type");
+                let (added, removed, changed) = diff_collection(&snapshot.items, &items);
+                snapshot.tick = tick;
+                snapshot.items.clone_from(&items);
+                Collection {
+                    items,
+                    added,
+                    removed,
+                    changed,
+                }
+            }
+            None => {
+                entt.insert(AtomCell(Box::new(CollectionSnapshot {
+                    tick,
+                    items: items.clone(),
+                })));
+                Collection {
+                    added: items.iter().map(|(k, _)| k.clone()).collect(),
+                    items,
+                    removed: Vec::new(),
+                    changed: Vec::new(),
+                }
+            }
+        }
+    }
+
+    /// Record a value derived from the resource `T` into a ring buffer, and return the most
+    /// recent `capacity` samples, oldest first. The resource is sampled `hz` times per second
+    /// (a rate of zero means every frame), whether or not it has changed, and the presenter is
+    /// rebuilt after each sample. If `capacity` changes between builds, the buffer is resized
+    /// preserving the most recent values.
+    pub fn use_history<T: Resource, V: Clone + Send + Sync + 'static>(
+        &mut self,
+        selector: impl Fn(&T) -> V + Send + Sync + 'static,
+        capacity: usize,
+        hz: f32,
+    ) -> Vec<V> {
+        let id = self.create_hook_entity("use_history", "HistorySampler");
+        if !self.bc.world.entity(id).contains::<HistorySampler>() {
+            let sampler = HistorySampler::new::<T, V>(self.bc.world, selector, capacity, hz);
+            self.bc.world.entity_mut(id).insert(sampler);
+        } else {
+            let mut entt = self.bc.world.entity_mut(id);
+            let mut sampler = entt.get_mut::<HistorySampler>().unwrap();
+            // Reconfiguring the sampler doesn't require a rebuild by itself.
+            let sampler = sampler.bypass_change_detection();
+            sampler.interval = interval(hz);
+            sampler.set_selector::<T, V>(selector);
+            sampler.history_mut::<V>().set_capacity(capacity);
+        }
+        let mut entt = self.bc.world.entity_mut(id);
+        let mut sampler = entt.get_mut::<HistorySampler>().unwrap();
+        let result = sampler
+            .bypass_change_detection()
+            .history_mut::<V>()
+            .to_vec();
+        self.add_tracked_component::<HistorySampler>(id);
+        result
+    }
+
+    /// Start a countdown of `duration` seconds, returning the remaining time. The presenter
+    /// is rebuilt at most `hz` times per second while the countdown is running (a rate of
+    /// zero means every frame), and once more when it finishes; [`Countdown::just_finished`]
+    /// is true for exactly one render. The duration is only read when the countdown is first
+    /// created; use [`CountdownHandle::reset`] to start over.
+    pub fn use_countdown(&mut self, duration: f32, hz: f32) -> Countdown {
+        let id = self.create_hook_entity("use_countdown", "CountdownTimer");
+        let mut entt = self.bc.world.entity_mut(id);
+        if !entt.contains::<CountdownTimer>() {
+            entt.insert(CountdownTimer::new(duration, hz));
+        }
+        let mut timer = entt.get_mut::<CountdownTimer>().unwrap();
+        // Don't trigger another rebuild by acknowledging completion.
+        let timer = timer.bypass_change_detection();
+        let just_finished = timer.is_finished() && !timer.notified;
+        timer.notified = timer.is_finished();
+        let result = Countdown {
+            remaining: timer.remaining(),
+            finished: timer.is_finished(),
+            just_finished,
+            handle: CountdownHandle(id),
+        };
+        self.add_tracked_component::<CountdownTimer>(id);
+        result
+    }
+
+    /// Return a value which eases toward `selector` applied to the resource `T`, rather than
+    /// jumping when the resource changes. The value approaches the target exponentially at
+    /// the given `speed` (in units of 1/seconds); the presenter is rebuilt each frame while
+    /// the value is moving, and stops rebuilding once it has converged on the target. On the
+    /// first render, the value starts at the target.
+    pub fn use_lerped_resource<T: Resource>(
+        &mut self,
+        selector: impl FnOnce(&T) -> f32,
+        speed: f32,
+    ) -> f32 {
+        let target = selector(self.use_resource::<T>());
+        let id = self.create_hook_entity("use_lerped_resource", "LerpedValue");
+        let mut entt = self.bc.world.entity_mut(id);
+        if !entt.contains::<LerpedValue>() {
+            entt.insert(LerpedValue::new(target, speed));
+        }
+        let mut lerp = entt.get_mut::<LerpedValue>().unwrap();
+        // Changing the target doesn't require a rebuild by itself; the lerp system marks the
+        // component as changed when the value moves.
+        let lerp = lerp.bypass_change_detection();
+        lerp.target = target;
+        lerp.speed = speed;
+        let value = lerp.value;
+        self.add_tracked_component::<LerpedValue>(id);
+        value
+    }
+
+    /// Return a reference to the resource `T`, rebuilding the presenter when the resource
+    /// changes, but at most `hz` times per second (a rate of zero means every frame). If
+    /// `leading` is true, a change which arrives after a quiet period rebuilds immediately,
+    /// and further changes within the following interval are deferred until it ends.
+    /// Otherwise, every rebuild waits for the end of an interval, which starts with the first
+    /// change. Either way, the most recent change is always picked up by a final rebuild.
+    pub fn use_resource_rate_limited<T: Resource>(&mut self, hz: f32, leading: bool) -> &T {
+        let id = self.create_hook_entity("use_resource_rate_limited", "RateLimit");
+        if !self.bc.world.entity(id).contains::<RateLimit>() {
+            let limit = RateLimit::new::<T>(self.bc.world, hz, leading);
+            self.bc.world.entity_mut(id).insert(limit);
+        }
+        let mut entt = self.bc.world.entity_mut(id);
+        let mut limit = entt.get_mut::<RateLimit>().unwrap();
+        let limit = limit.bypass_change_detection();
+        limit.interval = interval(hz);
+        limit.leading = leading;
+        self.add_tracked_component::<RateLimit>(id);
+        self.bc.world.resource::<T>()
+    }
+
+    /// Return the state of the drag operation in progress on the given entity, or `None`
+    /// if the entity is not being dragged. Calling this function causes the presenter to
+    /// rebuild whenever a drag starts, moves, or ends.
+    pub fn use_drag(&self, entity: Entity) -> Option<DragState> {
+        self.add_tracked_resource::<DragStates>();
+        self.bc
+            .world
+            .get_resource::<DragStates>()
+            .and_then(|states| states.get(entity))
+            .copied()
+    }
+
+    /// Create an [`ElementRef`] which can be attached to a child view with
+    /// [`View::element_ref`], in order to look up the child's output entity. The output is
+    /// only known once the child has been built, so the first render will see `None`; the
+    /// presenter is rebuilt whenever the referenced output changes.
+    pub fn use_element_ref(&mut self) -> ElementRef {
+        let id = self.create_hook_entity("use_element_ref", "ElementRef");
+        self.bc.world.init_component::<ElementRefNodes>();
+        self.add_tracked_component::<ElementRefNodes>(id);
+        ElementRef(id)
+    }
+
+    /// Return the first output entity of the view to which the [`ElementRef`] is attached.
+    pub fn read_element_ref(&self, element_ref: ElementRef) -> Option<Entity> {
+        element_ref.get(self.bc.world)
+    }
+
+    /// Return a [`Commands`] buffer for spawning or modifying entities. Commands are not
+    /// applied immediately; they are queued and flushed in a single batch after the presenter
+    /// and its view tree have finished building.
+    pub fn commands(&mut self) -> Commands<'_, '_> {
+        let tracking = self.tracking.get_mut();
+        Commands::new_from_entities(&mut tracking.commands, self.bc.world.entities())
+    }
+
+    /// Modify the resource `T` once the build phase is complete, so that all reads of the
+    /// resource during the build see a consistent value. Deferred mutations are applied in
+    /// the order in which they were requested, along with any other deferred
+    /// [`commands`](Self::commands).
+    pub fn update_resource<T: Resource>(&mut self, update: impl FnOnce(&mut T) + Send + 'static) {
+        self.tracking
+            .get_mut()
+            .commands
+            .push(move |world: &mut World| update(&mut world.resource_mut::<T>()));
+    }
+
+    /// Replace the value of the resource `T`, coalescing writes so that the resource is changed
+    /// at most once per frame. This is useful for values which are written continuously, such
+    /// as during a drag, since systems and presenters which depend on the resource only see
+    /// the final value. If several writes are made during the same frame, the last one wins.
+    pub fn write_resource_coalesced<T: Resource>(&mut self, value: T) {
+        let coalesce = self
+            .bc
+            .world
+            .get_resource::<QuillSettings>()
+            .map_or(true, |settings| settings.coalesce_resource_writes);
+        if coalesce {
+            self.bc
+                .world
+                .get_resource_or_insert_with(CoalescedWrites::default)
+                .write(value);
+        } else {
+            self.tracking
+                .get_mut()
+                .commands
+                .push(move |world: &mut World| world.insert_resource(value));
+        }
+    }
+
+    /// Replace the value of the resource `T` once the build phase is complete, inserting it
+    /// if it doesn't exist. If [`QuillSettings::skip_unchanged_resource_writes`] is enabled,
+    /// writing a value equal to the current one doesn't mark the resource as changed.
+    pub fn set_resource<T: Resource + PartialEq>(&mut self, value: T) {
+        self.tracking
+            .get_mut()
+            .commands
+            .push(move |world: &mut World| {
+                let skip_unchanged = world
+                    .get_resource::<QuillSettings>()
+                    .is_some_and(|settings| settings.skip_unchanged_resource_writes);
+                match world.get_resource_mut::<T>() {
+                    Some(mut current) if skip_unchanged => {
+                        current.set_if_neq(value);
+                    }
+                    Some(mut current) => *current = value,
+                    None => world.insert_resource(value),
+                }
+            });
+    }
+
+    /// Give [`Focus`] to `entity` once the build phase is complete, along with any other
+    /// deferred [`commands`](Self::commands). Returns the entity which had focus when the
+    /// presenter started building, so that a dialog can restore it when it closes. If `entity`
+    /// has been despawned by the time the request is applied, focus is left unchanged.
+    ///
+    /// Deferred commands are applied before [`AutoFocus`](crate::AutoFocus) requests, so an
+    /// autofocus element built on the same frame takes precedence.
+    pub fn set_focus(&mut self, entity: Entity) -> Option<Entity> {
+        self.tracking
+            .get_mut()
+            .commands
+            .push(move |world: &mut World| {
+                if world.get_entity(entity).is_some() {
+                    world.get_resource_or_insert_with(Focus::default).0 = Some(entity);
+                }
+            });
+        self.bc
+            .world
+            .get_resource::<Focus>()
+            .and_then(|focus| focus.0)
+    }
+
+    /// Remove [`Focus`] from whichever element has it, once the build phase is complete.
+    pub fn clear_focus(&mut self) {
+        self.tracking
+            .get_mut()
+            .commands
+            .push(move |world: &mut World| {
+                if let Some(mut focus) = world.get_resource_mut::<Focus>() {
+                    focus.0 = None;
+                }
+            });
+    }
+
     /// Return a reference to the Component `C` on the given entity.
     pub fn use_component<C: Component>(&self, entity: Entity) -> Option<&C> {
         match self.bc.world.get_entity(entity) {
@@ -68,13 +632,14 @@ impl<'w, 'p, Props> Cx<'w, 'p, Props> {
         self.bc.world.entity(self.bc.entity).get::<C>()
     }
 
-    /// Run a function on the view entity. Will only re-run when [`deps`] changes.
+    /// Run a function on the view entity. Will only re-run when [`deps`] changes. For effects
+    /// which need to be undone, see [`Cx::use_effect_with_cleanup`].
     pub fn use_effect<F: FnOnce(EntityWorldMut), D: Clone + PartialEq + Send + Sync + 'static>(
         &mut self,
         effect: F,
         deps: D,
     ) {
-        let handle = self.create_atom_handle::<D>();
+        let handle = self.create_atom_handle::<D>("use_effect");
         let mut entt = self.bc.world.entity_mut(handle.id);
         match entt.get_mut::<AtomCell>() {
             Some(mut cell) => {
@@ -91,6 +656,118 @@ impl<'w, 'p, Props> Cx<'w, 'p, Props> {
         }
     }
 
+    /// Run a side effect once the view has been built, and again whenever `deps` changes.
+    /// The effect returns a [`Cleanup`] function which undoes it, such as by despawning a
+    /// timer entity or removing an observer. The cleanup is run before the effect runs again,
+    /// and when the presenter is razed.
+    ///
+    /// ```ignore
+    /// cx.use_effect_with_cleanup(interval, move |world| {
+    ///     let timer = world.spawn(Ticker::new(interval)).id();
+    ///     Box::new(move |world| {
+    ///         world.despawn(timer);
+    ///     })
+    /// });
+    /// ```
+    pub fn use_effect_with_cleanup<D: PartialEq + Send + Sync + 'static>(
+        &mut self,
+        deps: D,
+        effect: impl FnOnce(&mut World) -> Cleanup + Send + 'static,
+    ) {
+        let id = self.create_hook_entity("use_effect_with_cleanup", std::any::type_name::<D>());
+        let mut entt = self.bc.world.entity_mut(id);
+        match entt.get_mut::<AtomCell>() {
+            Some(mut cell) => {
+                let deps_old = cell.0.downcast_mut::<D>().expect("Atom is incorrect type");
+                if *deps_old == deps {
+                    return;
+                }
+                *deps_old = deps;
+            }
+            None => {
+                entt.insert((AtomCell(Box::new(deps)), EffectCleanup(None)));
+            }
+        }
+        self.tracking
+            .get_mut()
+            .commands
+            .push(move |world: &mut World| {
+                run_cleanup(world, id);
+                let cleanup = effect(world);
+                if let Some(mut entt) = world.get_entity_mut(id) {
+                    entt.insert(EffectCleanup(Some(cleanup)));
+                }
+            });
+    }
+
+    /// Return a memoized value which is only recomputed when `deps` changes. The value is
+    /// kept across rebuilds, and dropped when the presenter is razed. Like other hooks, memos
+    /// are identified by the order in which they are called.
+    pub fn use_memo<D: PartialEq + Send + Sync + 'static, T: Send + Sync + 'static>(
+        &mut self,
+        deps: D,
+        compute: impl FnOnce(&D) -> T,
+    ) -> &T {
+        let id = self.create_hook_entity("use_memo", std::any::type_name::<T>());
+        let mut entt = self.bc.world.entity_mut(id);
+        let stale = match entt.get::<AtomCell>() {
+            Some(cell) => {
+                cell.0
+                    .downcast_ref::<Memo<D, T>>()
+                    .expect("Atom is incorrect type")
+                    .deps
+                    != deps
+            }
+            None => true,
+        };
+        if stale {
+            let value = compute(&deps);
+            entt.insert(AtomCell(Box::new(Memo { deps, value })));
+        }
+        &self
+            .bc
+            .world
+            .get::<AtomCell>(id)
+            .unwrap()
+            .0
+            .downcast_ref::<Memo<D, T>>()
+            .unwrap()
+            .value
+    }
+
+    /// Return a memoized value which is only recreated when `deps` changes. Before a new value
+    /// is created, `cleanup` is called on the value it replaces, and it is also called on the
+    /// final value when the presenter is razed. This is useful for values which own something
+    /// that needs to be freed, such as a texture.
+    pub fn use_memo_with_cleanup<
+        T: Clone + Send + Sync + 'static,
+        D: PartialEq + Send + Sync + 'static,
+    >(
+        &mut self,
+        deps: D,
+        create: impl FnOnce() -> T,
+        cleanup: impl Fn(T) + Send + Sync + 'static,
+    ) -> T {
+        let id = self.create_hook_entity("use_memo_with_cleanup", std::any::type_name::<T>());
+        let mut entt = self.bc.world.entity_mut(id);
+        match entt.get_mut::<AtomCell>() {
+            Some(mut cell) => cell
+                .0
+                .downcast_mut::<CleanupMemo<T, D>>()
+                .expect("Atom is incorrect type")
+                .update(deps, create, Box::new(cleanup)),
+            None => {
+                let value = create();
+                entt.insert(AtomCell(Box::new(CleanupMemo::new(
+                    deps,
+                    value.clone(),
+                    Box::new(cleanup),
+                ))));
+                value
+            }
+        }
+    }
+
     /// Return a reference to the entity that holds the current presenter invocation.
     pub fn use_view_entity(&self) -> EntityRef<'_> {
         self.bc.world.entity(self.bc.entity)
@@ -104,8 +781,20 @@ impl<'w, 'p, Props> Cx<'w, 'p, Props> {
     /// Spawn an empty [`Entity`] which is owned by this presenter. The entity will be
     /// despawned when the presenter state is razed.
     pub fn create_entity(&mut self) -> Entity {
+        self.create_hook_entity("create_entity", "Entity")
+    }
+
+    /// Allocate the owned entity for the next hook call. In strict mode, also validate that
+    /// the hook matches the one called at the same position on the previous render.
+    fn create_hook_entity(&mut self, hook: &'static str, ty: &'static str) -> Entity {
         let mut tracking = self.tracking.borrow_mut();
         let index = tracking.next_entity_index;
+        if let Some(ref prev) = tracking.prev_hooks {
+            validate_hook(prev, index, (hook, ty));
+        }
+        if let Some(ref mut hooks) = tracking.hooks {
+            hooks.push((hook, ty));
+        }
         tracking.next_entity_index = index + 1;
         match index.cmp(&tracking.owned_entities.len()) {
             Ordering::Less => tracking.owned_entities[index],
@@ -122,7 +811,7 @@ impl<'w, 'p, Props> Cx<'w, 'p, Props> {
     /// The handle is owned by the current context, and will be deleted when the presenter
     /// invocation is razed.
     pub fn create_atom<T: Clone + Sync + Send + Default + 'static>(&mut self) -> AtomHandle<T> {
-        let handle = self.create_atom_handle::<T>();
+        let handle = self.create_atom_handle::<T>("create_atom");
         let mut entt = self.bc.world.entity_mut(handle.id);
         match entt.get_mut::<AtomCell>() {
             Some(_) => {}
@@ -140,7 +829,7 @@ impl<'w, 'p, Props> Cx<'w, 'p, Props> {
         &mut self,
         init: impl FnOnce() -> T,
     ) -> AtomHandle<T> {
-        let handle = self.create_atom_handle::<T>();
+        let handle = self.create_atom_handle::<T>("create_atom");
         let mut entt = self.bc.world.entity_mut(handle.id);
         match entt.get_mut::<AtomCell>() {
             Some(_) => {}
@@ -227,6 +916,33 @@ impl<'w, 'p, Props> Cx<'w, 'p, Props> {
         }
     }
 
+    /// Provide a context value to this presenter and its descendants, which can retrieve it
+    /// with [`Cx::use_context`] without it having to be passed down as a property. Values are
+    /// keyed by type, and a descendant which provides a value of the same type shadows this
+    /// one for its own descendants. Providing a value equal to the current one does nothing,
+    /// so consumers are only rebuilt when the value changes.
+    pub fn provide_context<T: Clone + Send + Sync + PartialEq + 'static>(&mut self, value: T) {
+        self.define_scoped_value(context_key::<T>(), value);
+    }
+
+    /// Return the context value of type `T` provided by the nearest presenter, starting with
+    /// this one and searching up through its ancestors. The presenter is rebuilt when the
+    /// provider changes its value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no ancestor has provided a value of type `T`.
+    pub fn use_context<T: Clone + Send + Sync + 'static>(&self) -> T {
+        self.get_scoped_value(context_key::<T>())
+            .unwrap_or_else(|| {
+                panic!(
+                    "No context of type `{}` was provided: call `Cx::provide_context` in an \
+                    ancestor presenter",
+                    std::any::type_name::<T>()
+                )
+            })
+    }
+
     // / Return an object which can be used to send a message to the current presenter.
     // pub fn use_callback<In, Marker>(&mut self, sys: impl IntoSystem<In, (), Marker>) {
     //     todo!()
@@ -235,15 +951,18 @@ impl<'w, 'p, Props> Cx<'w, 'p, Props> {
     /// Create an [`AtomHandle`]. This can be used to read and write the content of an atom.
     /// The handle is owned by the current context, and will be deleted when the presenter
     /// invocation is razed.
-    fn create_atom_handle<T: Clone + Sync + Send + 'static>(&mut self) -> AtomHandle<T> {
-        let id = self.create_entity();
+    fn create_atom_handle<T: Clone + Sync + Send + 'static>(
+        &mut self,
+        hook: &'static str,
+    ) -> AtomHandle<T> {
+        let id = self.create_hook_entity(hook, std::any::type_name::<T>());
         AtomHandle {
             id,
             marker: PhantomData,
         }
     }
 
-    fn add_tracked_resource<T: Resource>(&self) {
+    pub(crate) fn add_tracked_resource<T: Resource>(&self) {
         self.tracking
             .borrow_mut()
             .resources
@@ -259,3 +978,1112 @@ impl<'w, 'p, Props> Cx<'w, 'p, Props> {
         self.tracking.borrow_mut().components.insert((entity, cid));
     }
 }
+
+/// Context values are stored as scoped values, keyed by the name of their type.
+fn context_key<T: Clone>() -> ScopedValueKey<T> {
+    ScopedValueKey::new(std::any::type_name::<T>())
+}
+
+/// Return a reference to a field of a resource, re-running the presenter only when that
+/// field changes. The field may be a nested path. This is shorthand for
+/// [`Cx::use_resource_select`] with a selector that borrows the field.
+///
+/// ```ignore
+/// let width = *use_field!(cx, Config, window.width);
+/// ```
+#[macro_export]
+macro_rules! use_field {
+    ($cx:expr, $res:ty, $($field:ident).+) => {
+        $cx.use_resource_select(|res: &$res| &res.$($field).+)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering as AtomicOrdering},
+        time::Duration,
+    };
+
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+    use crate::plugin::{render_frame, render_views};
+    use crate::presenter_state::{AnyPresenterState, PresenterState};
+    use crate::tracked_resources::TrackedResources;
+    use crate::tracking::{HookSignatures, OwnedEntities, TrackedComponents};
+    use crate::view::coalesced::flush_coalesced_writes;
+    use crate::view::countdown::update_countdowns;
+    use crate::view::lerp::{update_lerped_values, LerpedValue};
+    use crate::view::rate_limit::{update_rate_limits, RateLimit};
+    use crate::{Element, ForKeyed, MarkViewsDirtyMethods, View, ViewHandle};
+
+    /// Apply `update` to the resource `R`, then render a frame.
+    fn update_and_render<R: Resource>(world: &mut World, update: impl FnOnce(&mut R)) {
+        update(world.resource_mut::<R>().into_inner());
+        render_frame(world);
+    }
+
+    /// Advance the clock by one frame and run `system`, then render a frame. Returns true if
+    /// the system changed any `C` component, which is what causes presenters to rebuild.
+    fn advance_and_render<C: Component, M>(
+        world: &mut World,
+        system: impl IntoSystem<(), (), M>,
+    ) -> bool {
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(50));
+        world.run_system_once(system);
+        let changed = world.query::<Ref<C>>().iter(world).any(|c| c.is_changed());
+        render_frame(world);
+        changed
+    }
+
+    #[derive(Component)]
+    struct Helper;
+
+    fn count_helpers(world: &World) -> usize {
+        world
+            .iter_entities()
+            .filter(|e| e.contains::<Helper>())
+            .count()
+    }
+
+    fn spawn_helpers(mut cx: Cx) {
+        let mut commands = cx.commands();
+        for _ in 0..100 {
+            commands.spawn(Helper);
+        }
+        // Nothing is spawned until the build phase completes.
+        assert_eq!(count_helpers(cx.bc.world), 0);
+    }
+
+    #[derive(Resource)]
+    struct Counter(i32);
+
+    static DERIVE_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    fn mapped(mut cx: Cx) {
+        let doubled = *cx.use_resource_map(|counter: &Counter| {
+            DERIVE_COUNT.fetch_add(1, AtomicOrdering::Relaxed);
+            counter.0 * 2
+        });
+        assert_eq!(doubled, cx.use_resource::<Counter>().0 * 2);
+    }
+
+    #[test]
+    fn test_use_resource_map() {
+        let mut world = World::new();
+        world.insert_resource(Counter(1));
+        let entity = world.spawn_empty().id();
+        let mut state = PresenterState::new(mapped, ());
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        assert_eq!(DERIVE_COUNT.load(AtomicOrdering::Relaxed), 1);
+
+        // Rebuild without changing the resource
+        world.increment_change_tick();
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        assert_eq!(DERIVE_COUNT.load(AtomicOrdering::Relaxed), 1);
+
+        // Rebuild after changing the resource
+        world.increment_change_tick();
+        world.resource_mut::<Counter>().0 = 5;
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        assert_eq!(DERIVE_COUNT.load(AtomicOrdering::Relaxed), 2);
+    }
+
+    #[derive(Resource)]
+    struct Name1(&'static str);
+
+    #[derive(Resource)]
+    struct Name2(&'static str);
+
+    fn resource_tuple(cx: Cx) {
+        let (counter, a, b) = cx.use_resources::<(Counter, Name1, Name2)>();
+        assert_eq!(counter.0, 1);
+        assert_eq!(a.0, "a");
+        assert_eq!(b.0, "b");
+    }
+
+    #[test]
+    fn test_use_resources() {
+        let mut world = World::new();
+        world.insert_resource(Counter(1));
+        world.insert_resource(Name1("a"));
+        world.insert_resource(Name2("b"));
+        let entity = world.spawn_empty().id();
+        let mut state = PresenterState::new(resource_tuple, ());
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+
+        let is_changed = |world: &World| {
+            world
+                .get::<TrackedResources>(entity)
+                .unwrap()
+                .data
+                .iter()
+                .any(|res| res.is_changed(world))
+        };
+        assert_eq!(world.get::<TrackedResources>(entity).unwrap().data.len(), 3);
+        world.clear_trackers();
+        assert!(!is_changed(&world));
+
+        // A change to any one of the resources should trigger a rebuild.
+        world.resource_mut::<Counter>().set_changed();
+        assert!(is_changed(&world));
+        world.clear_trackers();
+        world.resource_mut::<Name1>().set_changed();
+        assert!(is_changed(&world));
+        world.clear_trackers();
+        world.resource_mut::<Name2>().set_changed();
+        assert!(is_changed(&world));
+    }
+
+    #[test]
+    #[should_panic(expected = "Name2")]
+    fn test_use_resources_missing() {
+        let mut world = World::new();
+        world.insert_resource(Counter(1));
+        world.insert_resource(Name1("a"));
+        let entity = world.spawn_empty().id();
+        let mut state = PresenterState::new(resource_tuple, ());
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+    }
+
+    static WHEN_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    fn resource_when(cx: Cx) {
+        WHEN_COUNT.fetch_add(1, AtomicOrdering::Relaxed);
+        cx.use_resource_when(|counter: &Counter| counter.0 > 10);
+    }
+
+    #[test]
+    fn test_use_resource_when() {
+        let mut world = World::new();
+        world.insert_resource(Counter(1));
+        world.spawn(ViewHandle::new(resource_when, ()));
+        render_frame(&mut world);
+        assert_eq!(WHEN_COUNT.load(AtomicOrdering::Relaxed), 1);
+
+        let step = |world: &mut World, value: i32| {
+            update_and_render(world, |counter: &mut Counter| counter.0 = value);
+        };
+
+        // Below threshold: no rebuild.
+        step(&mut world, 5);
+        assert_eq!(WHEN_COUNT.load(AtomicOrdering::Relaxed), 1);
+
+        // Crossing the threshold rebuilds, staying above it does not.
+        step(&mut world, 11);
+        assert_eq!(WHEN_COUNT.load(AtomicOrdering::Relaxed), 2);
+        step(&mut world, 20);
+        assert_eq!(WHEN_COUNT.load(AtomicOrdering::Relaxed), 2);
+
+        // Flipping back and forth rebuilds on each crossing.
+        step(&mut world, 3);
+        assert_eq!(WHEN_COUNT.load(AtomicOrdering::Relaxed), 3);
+        step(&mut world, 15);
+        assert_eq!(WHEN_COUNT.load(AtomicOrdering::Relaxed), 4);
+        step(&mut world, 2);
+        assert_eq!(WHEN_COUNT.load(AtomicOrdering::Relaxed), 5);
+        step(&mut world, 10);
+        assert_eq!(WHEN_COUNT.load(AtomicOrdering::Relaxed), 5);
+    }
+
+    fn conditional_hook(mut cx: Cx) {
+        if cx.is_first_render() {
+            cx.use_effect(|_| {}, 1);
+        }
+        cx.create_atom::<i32>();
+    }
+
+    #[test]
+    fn test_hook_order_lenient() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let mut state = PresenterState::new(conditional_hook, ());
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "hook #0 was `use_effect<i32>` on the previous render, \
+        but is `create_atom<i32>` on this render")]
+    fn test_hook_order_strict() {
+        let mut world = World::new();
+        world.insert_resource(QuillSettings {
+            strict_hooks: true,
+            ..default()
+        });
+        let entity = world.spawn_empty().id();
+        let mut state = PresenterState::new(conditional_hook, ());
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+    }
+
+    #[test]
+    fn test_hook_order_strict_toggle() {
+        let mut world = World::new();
+        world.insert_resource(QuillSettings {
+            strict_hooks: true,
+            ..default()
+        });
+        let entity = world.spawn_empty().id();
+        let mut state = PresenterState::new(conditional_hook, ());
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+
+        // The hook order changes while validation is off, so it isn't recorded.
+        world.resource_mut::<QuillSettings>().strict_hooks = false;
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        assert!(world.get::<HookSignatures>(entity).is_none());
+
+        // Re-enabling validation starts afresh, rather than comparing against stale hooks.
+        world.resource_mut::<QuillSettings>().strict_hooks = true;
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+    }
+
+    static ADDED_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    fn resource_added(mut cx: Cx) {
+        if cx.use_resource_added::<Name1>() {
+            ADDED_COUNT.fetch_add(1, AtomicOrdering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn test_use_resource_added() {
+        let mut world = World::new();
+        let entity = world.spawn(ViewHandle::new(resource_added, ())).id();
+        render_frame(&mut world);
+        assert_eq!(ADDED_COUNT.load(AtomicOrdering::Relaxed), 0);
+
+        // Inserting the resource triggers a rebuild, which reports the resource as added.
+        world.insert_resource(Name1("a"));
+        render_views(&mut world);
+        assert_eq!(ADDED_COUNT.load(AtomicOrdering::Relaxed), 1);
+
+        // Rebuilding again in the same frame doesn't report it twice.
+        world.mark_views_dirty([entity]);
+        render_frame(&mut world);
+        assert_eq!(ADDED_COUNT.load(AtomicOrdering::Relaxed), 1);
+
+        // Changes to the resource are not additions.
+        update_and_render(&mut world, |name: &mut Name1| name.0 = "b");
+        assert_eq!(ADDED_COUNT.load(AtomicOrdering::Relaxed), 1);
+    }
+
+    static FINISHED_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Resource, Default)]
+    struct LastRemaining(f32);
+
+    fn countdown(mut cx: Cx) {
+        let countdown = cx.use_countdown(1., 0.);
+        if countdown.just_finished {
+            FINISHED_COUNT.fetch_add(1, AtomicOrdering::Relaxed);
+        }
+        cx.bc.world.resource_mut::<LastRemaining>().0 = countdown.remaining;
+    }
+
+    #[test]
+    fn test_use_countdown() {
+        let mut world = World::new();
+        world.init_resource::<LastRemaining>();
+        world.insert_resource(Time::<()>::default());
+        world.spawn(ViewHandle::new(countdown, ()));
+        render_frame(&mut world);
+        assert_eq!(world.resource::<LastRemaining>().0, 1.);
+
+        let mut prev = 1.;
+        // Comfortably more than the countdown's one second duration.
+        for _ in 0..25 {
+            advance_and_render::<CountdownTimer, _>(&mut world, update_countdowns);
+            let remaining = world.resource::<LastRemaining>().0;
+            assert!(remaining <= prev);
+            prev = remaining;
+        }
+        assert_eq!(prev, 0.);
+        assert_eq!(FINISHED_COUNT.load(AtomicOrdering::Relaxed), 1);
+    }
+
+    fn deferred_update(mut cx: Cx) {
+        cx.update_resource::<Counter>(|counter| counter.0 += 1);
+        cx.update_resource::<Counter>(|counter| counter.0 *= 2);
+        // Not applied yet.
+        assert_eq!(cx.use_resource::<Counter>().0, 1);
+    }
+
+    #[test]
+    fn test_update_resource() {
+        let mut world = World::new();
+        world.insert_resource(Counter(1));
+        let entity = world.spawn_empty().id();
+        let mut state = PresenterState::new(deferred_update, ());
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        // Applied in order: (1 + 1) * 2
+        assert_eq!(world.resource::<Counter>().0, 4);
+    }
+
+    #[derive(Resource, Default)]
+    struct Renders(Vec<bool>);
+
+    fn record_first_render(mut cx: Cx) {
+        let first = cx.is_first_render();
+        cx.bc.world.resource_mut::<Renders>().0.push(first);
+    }
+
+    #[test]
+    fn test_is_first_render() {
+        let mut world = World::new();
+        world.init_resource::<Renders>();
+        let entity = world.spawn_empty().id();
+        let mut state = PresenterState::new(record_first_render, ());
+        for _ in 0..3 {
+            state.build(&mut BuildContext::new(&mut world, entity), entity);
+        }
+        assert_eq!(world.resource::<Renders>().0, vec![true, false, false]);
+    }
+
+    #[test]
+    fn test_deferred_commands() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let archetypes_before = world.archetypes().len();
+        let mut state = PresenterState::new(spawn_helpers, ());
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        assert_eq!(count_helpers(&world), 100);
+        // One archetype for the helpers, one for the presenter's graph marker.
+        assert!(world.archetypes().len() <= archetypes_before + 2);
+    }
+
+    #[derive(Component)]
+    struct Marker(i32);
+
+    #[derive(Resource, Default)]
+    struct Referenced {
+        marker: Option<i32>,
+        fragment_len: usize,
+    }
+
+    fn element_ref(mut cx: Cx) -> impl View {
+        let single = cx.use_element_ref();
+        let fragment = cx.use_element_ref();
+        let marker = cx
+            .read_element_ref(single)
+            .and_then(|e| cx.bc.world.get::<Marker>(e))
+            .map(|m| m.0);
+        let fragment_len = fragment.get_all(cx.bc.world).len();
+        let mut referenced = cx.bc.world.resource_mut::<Referenced>();
+        referenced.marker = marker;
+        referenced.fragment_len = fragment_len;
+        (
+            Element::new().insert(Marker(7)).element_ref(single),
+            (Element::new(), Element::new()).element_ref(fragment),
+        )
+    }
+
+    #[test]
+    fn test_use_element_ref() {
+        let mut world = World::new();
+        world.init_resource::<Referenced>();
+        let entity = world.spawn_empty().id();
+        let mut state = PresenterState::new(element_ref, ());
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        // Children haven't been built yet during the first render.
+        assert_eq!(world.resource::<Referenced>().marker, None);
+        assert_eq!(world.resource::<Referenced>().fragment_len, 0);
+
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        assert_eq!(world.resource::<Referenced>().marker, Some(7));
+        assert_eq!(world.resource::<Referenced>().fragment_len, 2);
+    }
+
+    #[derive(Resource)]
+    struct PanelWidth(f32);
+
+    fn drag_panel(mut cx: Cx) {
+        for width in 1..=10 {
+            cx.write_resource_coalesced(PanelWidth(width as f32));
+        }
+    }
+
+    fn drag_panel_again(mut cx: Cx) {
+        cx.write_resource_coalesced(PanelWidth(42.));
+    }
+
+    #[test]
+    fn test_write_resource_coalesced() {
+        let mut world = World::new();
+        world.insert_resource(PanelWidth(0.));
+        let first = world.spawn_empty().id();
+        let second = world.spawn_empty().id();
+        world.clear_trackers();
+
+        let mut state = PresenterState::new(drag_panel, ());
+        state.build(&mut BuildContext::new(&mut world, first), first);
+        let mut state_again = PresenterState::new(drag_panel_again, ());
+        state_again.build(&mut BuildContext::new(&mut world, second), second);
+        // Nothing is written until the flush.
+        assert_eq!(world.resource::<PanelWidth>().0, 0.);
+        assert!(!world.is_resource_changed::<PanelWidth>());
+
+        world.run_system_once(flush_coalesced_writes);
+        assert_eq!(world.resource::<PanelWidth>().0, 42.);
+        assert!(world.is_resource_changed::<PanelWidth>());
+
+        // Next frame: no writes, so the resource is left unchanged.
+        world.clear_trackers();
+        world.run_system_once(flush_coalesced_writes);
+        assert!(!world.is_resource_changed::<PanelWidth>());
+    }
+
+    #[derive(Resource, Clone)]
+    struct Level(i32);
+
+    #[derive(Resource, Default)]
+    struct Transitions(Vec<(i32, i32)>);
+
+    fn level_transition(mut cx: Cx) {
+        let (prev, current) = cx.use_resource_transition::<Level>();
+        cx.bc
+            .world
+            .resource_mut::<Transitions>()
+            .0
+            .push((prev.0, current.0));
+    }
+
+    #[test]
+    fn test_use_resource_transition() {
+        let mut world = World::new();
+        world.insert_resource(Level(1));
+        world.init_resource::<Transitions>();
+        let entity = world.spawn_empty().id();
+        let mut state = PresenterState::new(level_transition, ());
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+
+        world.resource_mut::<Level>().0 = 5;
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        world.resource_mut::<Level>().0 = 3;
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        // Rebuilding without a change reports the same value for both.
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+
+        assert_eq!(
+            world.resource::<Transitions>().0,
+            vec![(1, 1), (1, 5), (5, 3), (3, 3)]
+        );
+    }
+
+    static MEMO_COMPUTED: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Resource, Default)]
+    struct Unrelated(i32);
+
+    fn memo(mut cx: Cx) {
+        let deps = cx.use_resource::<Counter>().0;
+        cx.use_resource::<Unrelated>();
+        let squared = *cx.use_memo(deps, |n| {
+            MEMO_COMPUTED.fetch_add(1, AtomicOrdering::Relaxed);
+            n * n
+        });
+        assert_eq!(squared, deps * deps);
+    }
+
+    #[test]
+    fn test_use_memo() {
+        let mut world = World::new();
+        world.insert_resource(Counter(2));
+        world.init_resource::<Unrelated>();
+        let entity = world.spawn_empty().id();
+        let mut state = PresenterState::new(memo, ());
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        assert_eq!(MEMO_COMPUTED.load(AtomicOrdering::Relaxed), 1);
+
+        // Rebuilding with the same deps reuses the cached value.
+        world.resource_mut::<Unrelated>().0 = 1;
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        assert_eq!(MEMO_COMPUTED.load(AtomicOrdering::Relaxed), 1);
+
+        world.resource_mut::<Counter>().0 = 3;
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        assert_eq!(MEMO_COMPUTED.load(AtomicOrdering::Relaxed), 2);
+
+        // Razing the presenter despawns the entity holding the value.
+        let owned = world.get::<OwnedEntities>(entity).unwrap().0.clone();
+        state.raze(&mut world, entity);
+        assert!(owned.iter().all(|e| world.get_entity(*e).is_none()));
+    }
+
+    #[derive(Resource)]
+    struct Config {
+        title: &'static str,
+        window: WindowConfig,
+    }
+
+    struct WindowConfig {
+        width: u32,
+        height: u32,
+    }
+
+    static FIELD_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    fn config_field(cx: Cx) {
+        FIELD_COUNT.fetch_add(1, AtomicOrdering::Relaxed);
+        let width = *use_field!(cx, Config, window.width);
+        assert_eq!(width, cx.bc.world.resource::<Config>().window.width);
+    }
+
+    #[test]
+    fn test_use_field() {
+        let mut world = World::new();
+        world.insert_resource(Config {
+            title: "quill",
+            window: WindowConfig {
+                width: 800,
+                height: 600,
+            },
+        });
+        world.spawn(ViewHandle::new(config_field, ()));
+        render_frame(&mut world);
+        assert_eq!(FIELD_COUNT.load(AtomicOrdering::Relaxed), 1);
+
+        // Changing other fields, including a sibling of the selected one, doesn't rebuild.
+        update_and_render(&mut world, |config: &mut Config| config.title = "feather");
+        update_and_render(&mut world, |config: &mut Config| config.window.height = 400);
+        assert_eq!(FIELD_COUNT.load(AtomicOrdering::Relaxed), 1);
+
+        update_and_render(&mut world, |config: &mut Config| config.window.width = 1024);
+        assert_eq!(FIELD_COUNT.load(AtomicOrdering::Relaxed), 2);
+
+        // Writing the same value doesn't rebuild either.
+        update_and_render(&mut world, |config: &mut Config| config.window.width = 1024);
+        assert_eq!(FIELD_COUNT.load(AtomicOrdering::Relaxed), 2);
+        let config = world.resource::<Config>();
+        assert_eq!((config.title, config.window.height), ("feather", 400));
+    }
+
+    #[derive(Component)]
+    struct Label(&'static str);
+
+    #[derive(Component)]
+    struct Selected;
+
+    #[derive(Component)]
+    struct Tag;
+
+    #[derive(Resource, Default)]
+    struct SelectedLabels(Vec<&'static str>);
+
+    fn selected_labels(mut cx: Cx) {
+        let labels = cx
+            .use_query::<&Label, With<Selected>>()
+            .iter()
+            .map(|label| label.0)
+            .collect();
+        cx.bc.world.resource_mut::<SelectedLabels>().0 = labels;
+    }
+
+    #[test]
+    fn test_use_query() {
+        let mut world = World::new();
+        world.init_resource::<SelectedLabels>();
+        let a = world.spawn((Label("a"), Selected, Tag)).id();
+        let b = world.spawn(Label("b")).id();
+        world.spawn(ViewHandle::new(selected_labels, ()));
+        render_frame(&mut world);
+        assert_eq!(world.resource::<SelectedLabels>().0, vec!["a"]);
+
+        // Render a frame after `update`, returning the labels if the presenter was rebuilt.
+        let step = |world: &mut World, update: &dyn Fn(&mut World)| {
+            world.resource_mut::<SelectedLabels>().0.clear();
+            update(world);
+            render_frame(world);
+            world.resource::<SelectedLabels>().0.clone()
+        };
+
+        // Changes to unmatched entities, or to components the query doesn't read, are ignored.
+        let labels = step(&mut world, &|world| {
+            world.get_mut::<Label>(b).unwrap().0 = "B"
+        });
+        assert!(labels.is_empty());
+        let labels = step(&mut world, &|world| {
+            world.entity_mut(a).insert(Tag);
+        });
+        assert!(labels.is_empty());
+
+        // Changing a matched component rebuilds.
+        let labels = step(&mut world, &|world| {
+            world.get_mut::<Label>(a).unwrap().0 = "A"
+        });
+        assert_eq!(labels, vec!["A"]);
+
+        // So does an entity starting or stopping to match.
+        let labels = step(&mut world, &|world| {
+            world.entity_mut(b).insert(Selected);
+        });
+        assert_eq!(labels.len(), 2);
+        let labels = step(&mut world, &|world| {
+            world.entity_mut(a).remove::<Selected>();
+        });
+        assert_eq!(labels, vec!["B"]);
+    }
+
+    static MEMO_CREATED: AtomicUsize = AtomicUsize::new(0);
+    static MEMO_CLEANED: AtomicUsize = AtomicUsize::new(0);
+
+    fn memo_with_cleanup(mut cx: Cx) {
+        let deps = cx.use_resource::<Counter>().0;
+        cx.use_memo_with_cleanup(
+            deps,
+            || MEMO_CREATED.fetch_add(1, AtomicOrdering::Relaxed),
+            |_| {
+                MEMO_CLEANED.fetch_add(1, AtomicOrdering::Relaxed);
+            },
+        );
+    }
+
+    #[test]
+    fn test_use_memo_with_cleanup() {
+        let mut world = World::new();
+        world.insert_resource(Counter(0));
+        let entity = world.spawn_empty().id();
+        let mut state = PresenterState::new(memo_with_cleanup, ());
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        assert_eq!(MEMO_CREATED.load(AtomicOrdering::Relaxed), 1);
+        assert_eq!(MEMO_CLEANED.load(AtomicOrdering::Relaxed), 0);
+
+        world.resource_mut::<Counter>().0 = 1;
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        assert_eq!(MEMO_CREATED.load(AtomicOrdering::Relaxed), 2);
+        assert_eq!(MEMO_CLEANED.load(AtomicOrdering::Relaxed), 1);
+
+        world.resource_mut::<Counter>().0 = 2;
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        assert_eq!(MEMO_CREATED.load(AtomicOrdering::Relaxed), 3);
+        assert_eq!(MEMO_CLEANED.load(AtomicOrdering::Relaxed), 2);
+
+        // Razing the presenter cleans up the final value.
+        state.raze(&mut world, entity);
+        assert_eq!(MEMO_CREATED.load(AtomicOrdering::Relaxed), 3);
+        assert_eq!(MEMO_CLEANED.load(AtomicOrdering::Relaxed), 3);
+    }
+
+    #[derive(Resource)]
+    struct Score(i32);
+
+    #[derive(Resource, Default)]
+    struct DisplayedScore(f32);
+
+    fn lerped_score(mut cx: Cx) {
+        let score = cx.use_lerped_resource(|score: &Score| score.0 as f32, 10.);
+        cx.bc.world.resource_mut::<DisplayedScore>().0 = score;
+    }
+
+    #[test]
+    fn test_use_lerped_resource() {
+        let mut world = World::new();
+        world.insert_resource(Score(0));
+        world.init_resource::<DisplayedScore>();
+        world.insert_resource(Time::<()>::default());
+        world.spawn(ViewHandle::new(lerped_score, ()));
+        render_frame(&mut world);
+        assert_eq!(world.resource::<DisplayedScore>().0, 0.);
+
+        let step =
+            |world: &mut World| advance_and_render::<LerpedValue, _>(world, update_lerped_values);
+
+        update_and_render(&mut world, |score: &mut Score| score.0 = 100);
+        assert!(step(&mut world));
+        let halfway = world.resource::<DisplayedScore>().0;
+        assert!(halfway > 0. && halfway < 100.);
+
+        // Change the target mid-lerp; the value continues from where it was.
+        update_and_render(&mut world, |score: &mut Score| score.0 = 50);
+        assert!(step(&mut world));
+        let value = world.resource::<DisplayedScore>().0;
+        assert!(value > 0. && value < 100.);
+
+        let mut frames = 0;
+        while step(&mut world) {
+            frames += 1;
+            assert!(frames < 100, "lerp failed to converge");
+        }
+        assert_eq!(world.resource::<DisplayedScore>().0, 50.);
+        // Once converged, there are no further changes.
+        assert!(!step(&mut world));
+    }
+
+    #[derive(Resource, Clone, Debug, PartialEq)]
+    struct Theme(&'static str);
+
+    #[derive(Resource, Default)]
+    struct ThemeReads(Vec<&'static str>);
+
+    fn theme_name(mut cx: Cx) {
+        let theme = cx.use_resource_or(Theme("default"));
+        cx.bc.world.resource_mut::<ThemeReads>().0.push(theme.0);
+    }
+
+    #[test]
+    fn test_use_resource_or() {
+        let mut world = World::new();
+        world.init_resource::<ThemeReads>();
+        world.spawn(ViewHandle::new(theme_name, ()));
+
+        // Absent: the default is returned, and nothing changes on the next frame.
+        render_frame(&mut world);
+        render_frame(&mut world);
+        assert_eq!(world.resource::<ThemeReads>().0, vec!["default"]);
+
+        // Present: inserting the resource triggers a rebuild.
+        world.insert_resource(Theme("dark"));
+        render_frame(&mut world);
+        assert_eq!(world.resource::<ThemeReads>().0, vec!["default", "dark"]);
+
+        // Removed after being present: rebuild and return the default again.
+        world.remove_resource::<Theme>();
+        render_frame(&mut world);
+        render_frame(&mut world);
+        assert_eq!(
+            world.resource::<ThemeReads>().0,
+            vec!["default", "dark", "default"]
+        );
+    }
+
+    #[derive(Resource, Clone, PartialEq)]
+    struct Inventory(Vec<i32>);
+
+    #[derive(Resource, Default)]
+    struct InventoryReads(Vec<Vec<i32>>);
+
+    fn inventory_reads(mut cx: Cx) {
+        let first = cx.snapshot_resource::<Inventory>();
+        // Mutate the resource mid-render, both directly and with a deferred write.
+        cx.bc.world.resource_mut::<Inventory>().0.push(3);
+        cx.set_resource(Inventory(vec![]));
+        let second = cx.snapshot_resource::<Inventory>();
+        cx.bc.world.resource_mut::<InventoryReads>().0 = vec![first.0, second.0];
+    }
+
+    #[test]
+    fn test_snapshot_resource() {
+        let mut world = World::new();
+        world.insert_resource(Inventory(vec![1, 2]));
+        world.init_resource::<InventoryReads>();
+        let entity = world.spawn_empty().id();
+        let mut state = PresenterState::new(inventory_reads, ());
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        assert_eq!(
+            world.resource::<InventoryReads>().0,
+            vec![vec![1, 2], vec![1, 2]]
+        );
+        // The deferred write is applied once the build is complete.
+        assert!(world.resource::<Inventory>().0.is_empty());
+
+        // The next render takes a fresh snapshot.
+        world.resource_mut::<Inventory>().0 = vec![5];
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        assert_eq!(world.resource::<InventoryReads>().0, vec![vec![5], vec![5]]);
+    }
+
+    #[derive(Resource, Default)]
+    struct RenderedScore(i32);
+
+    fn rate_limited_score(mut cx: Cx) {
+        let score = cx.use_resource_rate_limited::<Score>(10., true).0;
+        cx.bc.world.resource_mut::<RenderedScore>().0 = score;
+    }
+
+    #[test]
+    fn test_use_resource_rate_limited() {
+        let mut world = World::new();
+        world.insert_resource(Score(0));
+        world.init_resource::<RenderedScore>();
+        world.insert_resource(Time::<()>::default());
+        world.spawn(ViewHandle::new(rate_limited_score, ()));
+        render_frame(&mut world);
+
+        // Step one frame, returning the rendered value.
+        let step = |world: &mut World| {
+            advance_and_render::<RateLimit, _>(world, update_rate_limits);
+            world.resource::<RenderedScore>().0
+        };
+
+        // A change after a quiet period rebuilds immediately.
+        world.resource_mut::<Score>().0 = 1;
+        assert_eq!(step(&mut world), 1);
+
+        // A change within the interval is deferred until the interval ends.
+        world.resource_mut::<Score>().0 = 2;
+        assert_eq!(step(&mut world), 1);
+        assert_eq!(step(&mut world), 2);
+
+        // Once things are quiet again, the next change is immediate.
+        assert_eq!(step(&mut world), 2);
+        assert_eq!(step(&mut world), 2);
+        world.resource_mut::<Score>().0 = 3;
+        assert_eq!(step(&mut world), 3);
+    }
+
+    #[derive(Resource)]
+    struct Rows(Vec<i32>);
+
+    #[derive(Resource, Default)]
+    struct LastChanged(Vec<usize>);
+
+    static ROW_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    fn row(_cx: Cx<i32>) -> impl View {
+        ROW_CALLS.fetch_add(1, AtomicOrdering::Relaxed);
+    }
+
+    fn row_list(mut cx: Cx) -> impl View {
+        let rows = cx.use_collection(|rows: &Rows| rows.0.iter().copied().enumerate().collect());
+        cx.bc.world.resource_mut::<LastChanged>().0 = rows.changed;
+        ForKeyed::new(&rows.items, |(key, _)| *key, |(_, value)| row.bind(*value))
+    }
+
+    #[test]
+    fn test_use_collection() {
+        let mut world = World::new();
+        world.insert_resource(Rows((0..100).collect()));
+        world.init_resource::<LastChanged>();
+        let entity = world.spawn(ViewHandle::new(row_list, ())).id();
+        render_frame(&mut world);
+        assert_eq!(ROW_CALLS.load(AtomicOrdering::Relaxed), 100);
+
+        // Changing one item only re-runs that row.
+        world.resource_mut::<Rows>().0[42] = -1;
+        render_frame(&mut world);
+        assert_eq!(world.resource::<LastChanged>().0, vec![42]);
+        assert_eq!(ROW_CALLS.load(AtomicOrdering::Relaxed), 101);
+
+        // Rebuilding without changes reports nothing.
+        world.mark_views_dirty([entity]);
+        render_frame(&mut world);
+        assert!(world.resource::<LastChanged>().0.is_empty());
+        assert_eq!(ROW_CALLS.load(AtomicOrdering::Relaxed), 101);
+    }
+
+    #[derive(Resource, PartialEq)]
+    struct Volume(i32);
+
+    fn set_volume(mut cx: Cx) {
+        cx.set_resource(Volume(5));
+    }
+
+    #[derive(Resource)]
+    struct FocusRequest(Option<Entity>, Option<Entity>);
+
+    fn request_focus(mut cx: Cx) -> impl View {
+        match cx.use_resource::<FocusRequest>().0 {
+            Some(target) => {
+                let previous = cx.set_focus(target);
+                cx.update_resource(move |request: &mut FocusRequest| request.1 = previous);
+            }
+            None => cx.clear_focus(),
+        }
+    }
+
+    #[test]
+    fn test_set_focus() {
+        let mut world = World::new();
+        let first = world.spawn_empty().id();
+        let second = world.spawn_empty().id();
+        world.insert_resource(Focus(Some(first)));
+        world.insert_resource(FocusRequest(Some(second), None));
+        let entity = world.spawn_empty().id();
+        let mut state = PresenterState::new(request_focus, ());
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        assert_eq!(world.resource::<Focus>().0, Some(second));
+        assert_eq!(world.resource::<FocusRequest>().1, Some(first));
+
+        // Requests for despawned entities are ignored.
+        let gone = world.spawn_empty().id();
+        world.despawn(gone);
+        world.resource_mut::<FocusRequest>().0 = Some(gone);
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        assert_eq!(world.resource::<Focus>().0, Some(second));
+
+        world.resource_mut::<FocusRequest>().0 = None;
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        assert_eq!(world.resource::<Focus>().0, None);
+    }
+
+    #[test]
+    fn test_skip_unchanged_resource_writes() {
+        let mut world = World::new();
+        world.insert_resource(Volume(5));
+        world.init_resource::<QuillSettings>();
+        let entity = world.spawn_empty().id();
+        let mut state = PresenterState::new(set_volume, ());
+
+        // By default, writing the same value marks the resource as changed.
+        world.clear_trackers();
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        assert!(world.is_resource_changed::<Volume>());
+
+        world
+            .resource_mut::<QuillSettings>()
+            .skip_unchanged_resource_writes = true;
+        world.clear_trackers();
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        assert!(!world.is_resource_changed::<Volume>());
+
+        // Different values are still written.
+        world.resource_mut::<Volume>().0 = 1;
+        world.clear_trackers();
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        assert!(world.is_resource_changed::<Volume>());
+        assert_eq!(world.resource::<Volume>().0, 5);
+    }
+
+    #[derive(Clone, PartialEq)]
+    struct ThemeValue(i32);
+
+    static THEME: AtomicUsize = AtomicUsize::new(0);
+
+    fn theme_provider(mut cx: Cx) {
+        let value = cx.use_resource::<Counter>().0;
+        cx.provide_context(ThemeValue(value));
+    }
+
+    fn theme_consumer(cx: Cx) {
+        THEME.store(
+            cx.use_context::<ThemeValue>().0 as usize,
+            AtomicOrdering::Relaxed,
+        );
+    }
+
+    #[test]
+    fn test_use_context() {
+        let mut world = World::new();
+        world.insert_resource(Counter(1));
+        let provider = world.spawn_empty().id();
+        let middle = world.spawn_empty().set_parent(provider).id();
+        let consumer = world.spawn_empty().set_parent(middle).id();
+        let mut provider_state = PresenterState::new(theme_provider, ());
+        let mut consumer_state = PresenterState::new(theme_consumer, ());
+        provider_state.build(&mut BuildContext::new(&mut world, provider), provider);
+        consumer_state.build(&mut BuildContext::new(&mut world, consumer), consumer);
+        assert_eq!(THEME.load(AtomicOrdering::Relaxed), 1);
+
+        // The consumer tracks the provider's context values.
+        let cid = world.component_id::<ScopedValueMap>().unwrap();
+        let tracked = world.get::<TrackedComponents>(consumer).unwrap();
+        assert!(tracked.data.contains(&(provider, cid)));
+
+        // Providing the same value again doesn't trigger a change.
+        world.clear_trackers();
+        provider_state.build(&mut BuildContext::new(&mut world, provider), provider);
+        assert!(!world
+            .entity(provider)
+            .get_ref::<ScopedValueMap>()
+            .unwrap()
+            .is_changed());
+
+        world.resource_mut::<Counter>().0 = 2;
+        world.clear_trackers();
+        provider_state.build(&mut BuildContext::new(&mut world, provider), provider);
+        assert!(world
+            .entity(provider)
+            .get_ref::<ScopedValueMap>()
+            .unwrap()
+            .is_changed());
+        consumer_state.build(&mut BuildContext::new(&mut world, consumer), consumer);
+        assert_eq!(THEME.load(AtomicOrdering::Relaxed), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "No context of type")]
+    fn test_use_context_missing() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let mut state = PresenterState::new(theme_consumer, ());
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+    }
+
+    #[derive(Resource, Default)]
+    struct EffectLog(Vec<String>);
+
+    fn effect_with_cleanup(mut cx: Cx) {
+        let value = cx.use_resource::<Counter>().0;
+        cx.use_effect_with_cleanup(value, move |world| {
+            world
+                .resource_mut::<EffectLog>()
+                .0
+                .push(format!("effect {}", value));
+            Box::new(move |world| {
+                world
+                    .resource_mut::<EffectLog>()
+                    .0
+                    .push(format!("cleanup {}", value));
+            })
+        });
+    }
+
+    #[test]
+    fn test_use_effect_with_cleanup() {
+        let mut world = World::new();
+        world.insert_resource(Counter(1));
+        world.init_resource::<EffectLog>();
+        let entity = world.spawn_empty().id();
+        let mut state = PresenterState::new(effect_with_cleanup, ());
+        let log = |world: &World| world.resource::<EffectLog>().0.join(", ");
+
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        assert_eq!(log(&world), "effect 1");
+
+        // Unchanged deps: the effect doesn't run again.
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        assert_eq!(log(&world), "effect 1");
+
+        // Changed deps: the previous effect is cleaned up first.
+        world.resource_mut::<Counter>().0 = 2;
+        state.build(&mut BuildContext::new(&mut world, entity), entity);
+        assert_eq!(log(&world), "effect 1, cleanup 1, effect 2");
+
+        // Razing the presenter runs the final cleanup.
+        state.raze(&mut world, entity);
+        assert_eq!(log(&world), "effect 1, cleanup 1, effect 2, cleanup 2");
+    }
+
+    #[derive(Resource)]
+    struct Stock {
+        items: Vec<i32>,
+        ticks: u32,
+    }
+
+    static SELECTOR_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    fn stock_size(cx: Cx) {
+        SELECTOR_COUNT.fetch_add(1, AtomicOrdering::Relaxed);
+        let size = cx.use_resource_selector(|stock: &Stock| stock.items.len());
+        assert_eq!(size, cx.bc.world.resource::<Stock>().items.len());
+    }
+
+    #[test]
+    fn test_use_resource_selector() {
+        let mut world = World::new();
+        world.insert_resource(Stock {
+            items: vec![1, 2],
+            ticks: 0,
+        });
+        world.spawn(ViewHandle::new(stock_size, ()));
+        render_frame(&mut world);
+        assert_eq!(SELECTOR_COUNT.load(AtomicOrdering::Relaxed), 1);
+
+        // Mutating other parts of the resource doesn't rebuild.
+        update_and_render(&mut world, |stock: &mut Stock| stock.ticks += 1);
+        update_and_render(&mut world, |stock: &mut Stock| stock.items[0] = 5);
+        assert_eq!(SELECTOR_COUNT.load(AtomicOrdering::Relaxed), 1);
+
+        // Changing the selected value does.
+        update_and_render(&mut world, |stock: &mut Stock| stock.items.push(3));
+        assert_eq!(SELECTOR_COUNT.load(AtomicOrdering::Relaxed), 2);
+        update_and_render(&mut world, |stock: &mut Stock| stock.ticks += 1);
+        assert_eq!(SELECTOR_COUNT.load(AtomicOrdering::Relaxed), 2);
+    }
+}
@@ -0,0 +1,204 @@
+use bevy::{prelude::*, utils::HashMap};
+use bevy_mod_picking::{
+    events::{Drag, DragEnd, DragStart, Pointer},
+    pointer::PointerId,
+};
+
+/// The state of a drag operation in progress, as returned by [`Cx::use_drag`].
+///
+/// [`Cx::use_drag`]: crate::Cx::use_drag
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DragState {
+    /// The pointer which is doing the dragging.
+    pub pointer: PointerId,
+
+    /// Pointer position at the start of the drag.
+    pub start: Vec2,
+
+    /// Current pointer position.
+    pub current: Vec2,
+
+    /// Offset of the current pointer position from the start of the drag.
+    pub delta: Vec2,
+}
+
+/// Resource which holds the drag operations currently in progress, keyed by the entity
+/// being dragged.
+#[derive(Resource, Default)]
+pub struct DragStates(HashMap<Entity, DragState>);
+
+impl DragStates {
+    /// Return the drag in progress for the given entity, if any.
+    pub fn get(&self, entity: Entity) -> Option<&DragState> {
+        self.0.get(&entity)
+    }
+}
+
+/// System which tracks drag events. Drags continue to be reported for the original target
+/// even if the pointer leaves it, and are cleared when the drag ends, the target is despawned,
+/// or the pointer goes away.
+pub(crate) fn update_drag_states(
+    mut drag_states: ResMut<DragStates>,
+    mut starts: EventReader<Pointer<DragStart>>,
+    mut drags: EventReader<Pointer<Drag>>,
+    mut ends: EventReader<Pointer<DragEnd>>,
+    pointers: Query<&PointerId>,
+    entities: Query<Entity>,
+) {
+    for event in starts.read() {
+        let position = event.pointer_location.position;
+        drag_states.0.insert(
+            event.target,
+            DragState {
+                pointer: event.pointer_id,
+                start: position,
+                current: position,
+                delta: Vec2::ZERO,
+            },
+        );
+    }
+
+    for event in drags.read() {
+        if let Some(state) = drag_states.0.get_mut(&event.target) {
+            state.current = event.pointer_location.position;
+            state.delta = state.current - state.start;
+        }
+    }
+
+    for event in ends.read() {
+        drag_states.0.remove(&event.target);
+    }
+
+    // Cancel drags whose target or pointer no longer exists. Avoid marking the resource as
+    // changed if there is nothing to remove.
+    let cancelled = drag_states.0.iter().any(|(entity, state)| {
+        !entities.contains(*entity) || !pointers.iter().any(|id| *id == state.pointer)
+    });
+    if cancelled {
+        drag_states.0.retain(|entity, state| {
+            entities.contains(*entity) && pointers.iter().any(|id| *id == state.pointer)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::system::RunSystemOnce, render::camera::NormalizedRenderTarget};
+    use bevy_mod_picking::{
+        backend::HitData,
+        pointer::{Location, PointerButton},
+    };
+
+    use super::*;
+    use crate::{
+        presenter_state::{AnyPresenterState, PresenterState},
+        BuildContext, Cx,
+    };
+
+    #[derive(Resource, Default)]
+    struct Target(Option<Entity>);
+
+    #[derive(Resource, Default)]
+    struct LastDrag(Option<DragState>);
+
+    fn draggable(mut cx: Cx) {
+        let target = cx.use_resource::<Target>().0.unwrap();
+        let drag = cx.use_drag(target);
+        cx.bc.world.resource_mut::<LastDrag>().0 = drag;
+    }
+
+    fn location(x: f32, y: f32) -> Location {
+        Location {
+            target: NormalizedRenderTarget::Image(Handle::default()),
+            position: Vec2::new(x, y),
+        }
+    }
+
+    fn setup() -> (World, Entity) {
+        let mut world = World::new();
+        world.init_resource::<Events<Pointer<DragStart>>>();
+        world.init_resource::<Events<Pointer<Drag>>>();
+        world.init_resource::<Events<Pointer<DragEnd>>>();
+        world.init_resource::<DragStates>();
+        world.spawn(PointerId::Mouse);
+        let target = world.spawn_empty().id();
+        (world, target)
+    }
+
+    fn start(world: &mut World, target: Entity, x: f32, y: f32) {
+        world.send_event(Pointer::new(
+            PointerId::Mouse,
+            location(x, y),
+            target,
+            DragStart {
+                button: PointerButton::Primary,
+                hit: HitData::new(Entity::PLACEHOLDER, 0., None, None),
+            },
+        ));
+    }
+
+    fn drag(world: &mut World, target: Entity, x: f32, y: f32) {
+        world.send_event(Pointer::new(
+            PointerId::Mouse,
+            location(x, y),
+            target,
+            Drag {
+                button: PointerButton::Primary,
+                distance: Vec2::ZERO,
+                delta: Vec2::ZERO,
+            },
+        ));
+    }
+
+    #[test]
+    fn test_use_drag() {
+        let (mut world, target) = setup();
+        world.insert_resource(Target(Some(target)));
+        world.init_resource::<LastDrag>();
+        let entity = world.spawn_empty().id();
+        let mut state = PresenterState::new(draggable, ());
+        let mut render = |world: &mut World| {
+            world.run_system_once(update_drag_states);
+            state.build(&mut BuildContext::new(world, entity), entity);
+            world.resource::<LastDrag>().0
+        };
+
+        assert_eq!(render(&mut world), None);
+
+        start(&mut world, target, 10., 10.);
+        let state = render(&mut world).unwrap();
+        assert_eq!(state.start, Vec2::new(10., 10.));
+        assert_eq!(state.delta, Vec2::ZERO);
+
+        // Pointer moves outside of the node: the drag is still reported.
+        drag(&mut world, target, 25., 5.);
+        let state = render(&mut world).unwrap();
+        assert_eq!(state.current, Vec2::new(25., 5.));
+        assert_eq!(state.delta, Vec2::new(15., -5.));
+
+        // Release clears the drag.
+        world.send_event(Pointer::new(
+            PointerId::Mouse,
+            location(25., 5.),
+            target,
+            DragEnd {
+                button: PointerButton::Primary,
+                distance: Vec2::new(15., -5.),
+            },
+        ));
+        assert_eq!(render(&mut world), None);
+    }
+
+    #[test]
+    fn test_drag_cancelled() {
+        let (mut world, target) = setup();
+        start(&mut world, target, 0., 0.);
+        world.run_system_once(update_drag_states);
+        assert!(world.resource::<DragStates>().get(target).is_some());
+
+        // Target despawned mid-drag.
+        world.despawn(target);
+        world.run_system_once(update_drag_states);
+        assert!(world.resource::<DragStates>().get(target).is_none());
+    }
+}
@@ -0,0 +1,22 @@
+use bevy::prelude::*;
+
+/// Function returned by an effect created with [`Cx::use_effect_with_cleanup`], which undoes
+/// whatever the effect did. It is run before the effect runs again, and when the presenter
+/// is razed.
+///
+/// [`Cx::use_effect_with_cleanup`]: crate::Cx::use_effect_with_cleanup
+pub type Cleanup = Box<dyn FnOnce(&mut World) + Send + Sync>;
+
+/// Component which holds the pending cleanup of an effect, on the hook entity of the effect.
+#[derive(Component)]
+pub(crate) struct EffectCleanup(pub(crate) Option<Cleanup>);
+
+/// Run the pending cleanup of the effect on `entity`, if any.
+pub(crate) fn run_cleanup(world: &mut World, entity: Entity) {
+    let cleanup = world
+        .get_mut::<EffectCleanup>(entity)
+        .and_then(|mut cleanup| cleanup.0.take());
+    if let Some(cleanup) = cleanup {
+        cleanup(world);
+    }
+}
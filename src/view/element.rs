@@ -1,18 +1,43 @@
 use bevy::prelude::*;
 
-use crate::{BuildContext, View};
+use crate::{AutoFocus, BuildContext, InlineFlow, View};
 
 use crate::node_span::NodeSpan;
 
 /// A View which renders a NodeBundle that can have multiple children, with no inherent style
 /// or behavior. Basically the equivalent of an HTML 'div'.
 #[derive(Copy, Default)]
-pub struct Element {}
+pub struct Element {
+    inline: bool,
+    autofocus: bool,
+}
 
 impl Element {
     /// Construct a new, empty `Element`.
     pub fn new() -> Self {
-        Self {}
+        Self {
+            inline: false,
+            autofocus: false,
+        }
+    }
+
+    /// Construct a new `Element` whose children are laid out as inline content, flowing
+    /// horizontally and wrapping at the element's width, like text in a paragraph. An inline
+    /// element nested within another acts as a span within the same line run. See
+    /// [`InlineFlow`] for details.
+    pub fn inline() -> Self {
+        Self {
+            inline: true,
+            autofocus: false,
+        }
+    }
+
+    /// Request that the element be given [`Focus`](bevy::a11y::Focus) when it is first built.
+    /// Rebuilding the element doesn't request focus again. See [`AutoFocus`] for when the
+    /// request is applied.
+    pub fn autofocus(mut self) -> Self {
+        self.autofocus = true;
+        self
     }
 }
 
@@ -25,20 +50,32 @@ impl View for Element {
     }
 
     fn build(&self, bc: &mut BuildContext) -> Self::State {
-        let new_entity = bc
-            .world
-            .spawn((
-                NodeBundle {
-                    visibility: Visibility::Visible,
-                    ..default()
-                },
-                Name::new("element"),
-            ))
-            .id();
-        new_entity
+        let mut new_entity = bc.world.spawn((
+            NodeBundle {
+                visibility: Visibility::Visible,
+                ..default()
+            },
+            Name::new("element"),
+        ));
+        if self.inline {
+            new_entity.insert(InlineFlow);
+        }
+        if self.autofocus {
+            new_entity.insert(AutoFocus);
+        }
+        new_entity.id()
     }
 
-    fn update(&self, _vc: &mut BuildContext, _state: &mut Self::State) {}
+    fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
+        let mut entt = bc.world.entity_mut(*state);
+        if self.inline != entt.contains::<InlineFlow>() {
+            if self.inline {
+                entt.insert(InlineFlow);
+            } else {
+                entt.remove::<InlineFlow>();
+            }
+        }
+    }
 
     fn assemble(&self, _vc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
         NodeSpan::Node(*state)
@@ -53,14 +90,12 @@ impl View for Element {
 
 impl Clone for Element {
     fn clone(&self) -> Self {
-        // Element has no state, so cloning is always the same.
         *self
     }
 }
 
 impl PartialEq for Element {
-    fn eq(&self, _other: &Self) -> bool {
-        // Element has no state, so comparison is always true.
-        true
+    fn eq(&self, other: &Self) -> bool {
+        self.inline == other.inline && self.autofocus == other.autofocus
     }
 }
@@ -0,0 +1,132 @@
+use bevy::prelude::*;
+
+use crate::node_span::NodeSpan;
+use crate::{BuildContext, View};
+
+/// A reference to the output entities of a child view, created by [`Cx::use_element_ref`]
+/// and attached to a view with [`View::element_ref`]. Once the child view has been built,
+/// the reference can be used to look up its output entity.
+///
+/// [`Cx::use_element_ref`]: crate::Cx::use_element_ref
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ElementRef(pub(crate) Entity);
+
+impl ElementRef {
+    /// Return the first output entity of the referenced view, or `None` if the view has not
+    /// been built yet, or has no output.
+    pub fn get(&self, world: &World) -> Option<Entity> {
+        self.get_all(world).first().copied()
+    }
+
+    /// Return all of the output entities of the referenced view. Views which produce fragments
+    /// may have more than one.
+    pub fn get_all(&self, world: &World) -> Vec<Entity> {
+        let mut entities = Vec::new();
+        if let Some(nodes) = world.get::<ElementRefNodes>(self.0) {
+            nodes.0.flatten(&mut entities);
+        }
+        entities
+    }
+}
+
+/// Holds the output nodes of the view to which an [`ElementRef`] is attached.
+#[derive(Component, Default)]
+pub(crate) struct ElementRefNodes(pub(crate) NodeSpan);
+
+/// A wrapper view which records the output nodes of the inner view in an [`ElementRef`].
+pub struct ViewElementRef<V: View> {
+    pub(crate) inner: V,
+    pub(crate) element_ref: ElementRef,
+}
+
+impl<V: View> ViewElementRef<V> {
+    fn write_nodes(&self, nodes: NodeSpan, world: &mut World) {
+        let Some(mut entt) = world.get_entity_mut(self.element_ref.0) else {
+            return;
+        };
+        match entt.get_mut::<ElementRefNodes>() {
+            Some(mut current) => {
+                // Only mark as changed if the output is different, to avoid needless rebuilds.
+                if current.0 != nodes {
+                    current.0 = nodes;
+                }
+            }
+            None => {
+                entt.insert(ElementRefNodes(nodes));
+            }
+        }
+    }
+}
+
+impl<V: View> View for ViewElementRef<V> {
+    type State = V::State;
+
+    fn nodes(&self, bc: &BuildContext, state: &Self::State) -> NodeSpan {
+        self.inner.nodes(bc, state)
+    }
+
+    fn build(&self, bc: &mut BuildContext) -> Self::State {
+        let state = self.inner.build(bc);
+        let nodes = self.nodes(bc, &state);
+        self.write_nodes(nodes, bc.world);
+        state
+    }
+
+    fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
+        self.inner.update(bc, state);
+        let nodes = self.nodes(bc, state);
+        self.write_nodes(nodes, bc.world);
+    }
+
+    fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
+        self.inner.assemble(bc, state)
+    }
+
+    fn raze(&self, world: &mut World, state: &mut Self::State) {
+        self.inner.raze(world, state);
+        self.write_nodes(NodeSpan::Empty, world);
+    }
+}
+
+impl<V: View + Clone> Clone for ViewElementRef<V> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            element_ref: self.element_ref,
+        }
+    }
+}
+
+impl<V: View + PartialEq> PartialEq for ViewElementRef<V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner && self.element_ref == other.element_ref
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Element, If};
+
+    #[test]
+    fn test_element_ref_replaced_and_razed() {
+        let mut world = World::new();
+        let root = world.spawn_empty().id();
+        let element_ref = ElementRef(world.spawn_empty().id());
+        let view = If::new(true, Element::new(), Element::new()).element_ref(element_ref);
+        let mut state = view.build(&mut BuildContext::new(&mut world, root));
+        let first = element_ref.get(&world).unwrap();
+
+        // Replacing the output entity updates the reference.
+        let view = If::new(false, Element::new(), Element::new()).element_ref(element_ref);
+        view.update(&mut BuildContext::new(&mut world, root), &mut state);
+        let second = element_ref.get(&world).unwrap();
+        assert_ne!(first, second);
+        assert!(world.get_entity(first).is_none());
+
+        // Razing the view resets it.
+        view.raze(&mut world, &mut state);
+        assert_eq!(element_ref.get(&world), None);
+        assert!(world.get_entity(second).is_none());
+    }
+}
@@ -10,7 +10,8 @@ impl For {
     /// Construct an index for loop for an array of items. The callback is called once for each
     /// array element; its arguments are the item and the array index, and its result is a View.
     /// During rebuild, the elements are overwritten based on their current array index, so the
-    /// order of child views never changes.
+    /// order of child views never changes. Use this for items which have no stable identity;
+    /// child views are only built when the array grows, and razed when it shrinks.
     pub fn index<Item: Send + Clone, V: View, F: Fn(&Item, usize) -> V + Send + Clone>(
         items: &[Item],
         each: F,
@@ -148,3 +148,56 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+    use crate::presenter_state::ViewHandle;
+    use crate::{plugin::render_frame, Cx, For};
+
+    #[derive(Resource)]
+    struct Numbers(Vec<i32>);
+
+    fn numbers(cx: Cx) -> impl View {
+        For::index(&cx.use_resource::<Numbers>().0, |n, _| format!("{}", n))
+    }
+
+    /// Render a frame, and return the presenter's output nodes.
+    fn render(world: &mut World, entity: Entity) -> Vec<Entity> {
+        render_frame(world);
+        let mut nodes = Vec::new();
+        world
+            .get::<ViewHandle>(entity)
+            .unwrap()
+            .nodes()
+            .flatten(&mut nodes);
+        nodes
+    }
+
+    #[test]
+    fn test_for_index_length() {
+        let mut world = World::new();
+        world.insert_resource(Numbers(vec![1, 2, 3]));
+        let entity = world.spawn(ViewHandle::new(numbers, ())).id();
+        let initial = render(&mut world, entity);
+        assert_eq!(initial.len(), 3);
+
+        // Growing reuses the existing entities positionally, and appends new ones.
+        world.resource_mut::<Numbers>().0 = vec![4, 5, 6, 7, 8];
+        let grown = render(&mut world, entity);
+        assert_eq!(grown.len(), 5);
+        assert_eq!(grown[..3], initial[..]);
+        assert_eq!(world.get::<Text>(grown[0]).unwrap().sections[0].value, "4");
+
+        // Shrinking razes the surplus tail.
+        world.resource_mut::<Numbers>().0 = vec![9];
+        let shrunk = render(&mut world, entity);
+        assert_eq!(shrunk, vec![initial[0]]);
+        assert!(grown[1..].iter().all(|e| world.get_entity(*e).is_none()));
+
+        world.resource_mut::<Numbers>().0.clear();
+        assert!(render(&mut world, entity).is_empty());
+    }
+}
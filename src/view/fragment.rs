@@ -5,6 +5,17 @@ use crate::{BuildContext, View, ViewTuple};
 use crate::node_span::NodeSpan;
 
 /// A View which renders a sequence of nodes which are inserted into the parent view.
+///
+/// There are several ways to construct a fragment:
+/// * [`Fragment::new`] takes a tuple of views, which may be of different types. Use this
+///   when the number of items is fixed.
+/// * [`Fragment::from_iter`] takes any number of views of the same type, collected from an
+///   iterator. Items are matched by index when the fragment is rebuilt, so an item which
+///   moves to a different position is rebuilt rather than moved. Use this when the items
+///   are generated at runtime, but rarely reordered.
+/// * [`For`](crate::For) also renders a runtime-sized list, but matches items by value or by
+///   key, so that reordering or inserting into the list preserves the state of existing
+///   items.
 pub struct Fragment<A: ViewTuple> {
     items: A,
 }
@@ -16,6 +27,16 @@ impl<A: ViewTuple> Fragment<A> {
     }
 }
 
+impl<V: View + Clone> FromIterator<V> for Fragment<Vec<V>> {
+    /// Construct a new [`Fragment`] from a sequence of views of the same type. The state of
+    /// each child is kept in a `Vec`, matched to the views by index.
+    fn from_iter<I: IntoIterator<Item = V>>(items: I) -> Self {
+        Self {
+            items: items.into_iter().collect(),
+        }
+    }
+}
+
 impl<A: ViewTuple> View for Fragment<A> {
     type State = A::State;
 
@@ -53,3 +74,40 @@ impl<A: ViewTuple + PartialEq> PartialEq for Fragment<A> {
         self.items.eq(&other.items)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+
+    fn text(world: &World, span: &NodeSpan) -> Vec<String> {
+        span.iter()
+            .map(|e| world.get::<Text>(e).unwrap().sections[0].value.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_from_iter() {
+        let mut world = World::new();
+        let root = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, root);
+
+        let view = Fragment::from_iter((1..=3).map(|i| format!("item {}", i)));
+        let mut state = view.build(&mut bc);
+        assert_eq!(state.len(), 3);
+        let span = view.assemble(&mut bc, &mut state);
+        assert_eq!(text(bc.world, &span), vec!["item 1", "item 2", "item 3"]);
+        let first = span.iter().next().unwrap();
+
+        // Items are matched by index, so the first item is updated in place.
+        let view = Fragment::from_iter(["first".to_string(), "second".to_string()]);
+        view.update(&mut bc, &mut state);
+        let span = view.assemble(&mut bc, &mut state);
+        assert_eq!(text(bc.world, &span), vec!["first", "second"]);
+        assert_eq!(span.iter().next(), Some(first));
+
+        view.raze(bc.world, &mut state);
+        assert!(bc.world.get_entity(first).is_none());
+    }
+}
@@ -0,0 +1,230 @@
+use std::{any::Any, collections::VecDeque};
+
+use bevy::prelude::*;
+
+use super::rate_limit::interval;
+
+/// A fixed-capacity ring buffer of sampled values. Used by [`Cx::use_history`] to record
+/// the value of a resource over time.
+///
+/// [`Cx::use_history`]: crate::Cx::use_history
+#[derive(Clone, Debug)]
+pub(crate) struct ResourceHistory<V> {
+    values: VecDeque<V>,
+    capacity: usize,
+}
+
+impl<V: Clone> ResourceHistory<V> {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            values: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Change the capacity of the buffer. If the buffer shrinks, the oldest values are
+    /// discarded, so that the most recent values are preserved.
+    pub(crate) fn set_capacity(&mut self, capacity: usize) {
+        while self.values.len() > capacity {
+            self.values.pop_front();
+        }
+        self.capacity = capacity;
+    }
+
+    /// Append a value, evicting the oldest value if the buffer is full.
+    pub(crate) fn push(&mut self, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+        while self.values.len() >= self.capacity {
+            self.values.pop_front();
+        }
+        self.values.push_back(value);
+    }
+
+    /// Return the recorded values, oldest first.
+    pub(crate) fn to_vec(&self) -> Vec<V> {
+        self.values.iter().cloned().collect()
+    }
+}
+
+/// Reads the current value of a resource from the world, boxed as the history's value type.
+type ReadFn = Box<dyn Fn(&World) -> Option<Box<dyn Any + Send>> + Send + Sync>;
+
+/// Component which records the history for [`Cx::use_history`]. The resource is sampled by
+/// a system at the configured rate, and the component is marked as changed (causing the
+/// presenter to rebuild) each time a sample is recorded.
+///
+/// [`Cx::use_history`]: crate::Cx::use_history
+#[derive(Component)]
+pub(crate) struct HistorySampler {
+    read: ReadFn,
+    /// Appends a value returned by `read` to the `ResourceHistory` in `history`.
+    push: fn(&mut (dyn Any + Send + Sync), Box<dyn Any + Send>),
+    history: Box<dyn Any + Send + Sync>,
+    pub(crate) interval: f32,
+    since_sample: f32,
+}
+
+impl HistorySampler {
+    /// Create a sampler for the resource `T`, recording an initial sample if the resource
+    /// exists.
+    pub(crate) fn new<T: Resource, V: Clone + Send + Sync + 'static>(
+        world: &World,
+        selector: impl Fn(&T) -> V + Send + Sync + 'static,
+        capacity: usize,
+        hz: f32,
+    ) -> Self {
+        let mut sampler = Self {
+            read: Box::new(|_| None),
+            push: |history, value| {
+                let history = history
+                    .downcast_mut::<ResourceHistory<V>>()
+                    .expect("History is incorrect type");
+                history.push(*value.downcast::<V>().expect("Sample is incorrect type"));
+            },
+            history: Box::new(ResourceHistory::<V>::new(capacity)),
+            interval: interval(hz),
+            since_sample: 0.,
+        };
+        sampler.set_selector::<T, V>(selector);
+        if let Some(value) = (sampler.read)(world) {
+            (sampler.push)(&mut *sampler.history, value);
+        }
+        sampler
+    }
+
+    /// Replace the function used to derive sampled values from the resource.
+    pub(crate) fn set_selector<T: Resource, V: Clone + Send + Sync + 'static>(
+        &mut self,
+        selector: impl Fn(&T) -> V + Send + Sync + 'static,
+    ) {
+        self.read = Box::new(move |world| {
+            world
+                .get_resource::<T>()
+                .map(|res| Box::new(selector(res)) as Box<dyn Any + Send>)
+        });
+    }
+
+    /// Return the recorded history.
+    pub(crate) fn history_mut<V: Clone + 'static>(&mut self) -> &mut ResourceHistory<V> {
+        self.history
+            .downcast_mut::<ResourceHistory<V>>()
+            .expect("History is incorrect type")
+    }
+
+    /// Advance the sampler by `delta` seconds. Returns true if a sample is due.
+    fn tick(&mut self, delta: f32) -> bool {
+        self.since_sample += delta;
+        if self.since_sample >= self.interval {
+            self.since_sample = 0.;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// System which samples the resources observed by [`Cx::use_history`], and marks each
+/// sampler as changed when a new sample is recorded.
+///
+/// [`Cx::use_history`]: crate::Cx::use_history
+pub(crate) fn update_histories(world: &mut World) {
+    let delta = world.resource::<Time>().delta_seconds();
+    let mut query = world.query::<(Entity, &mut HistorySampler)>();
+    let due: Vec<Entity> = query
+        .iter_mut(world)
+        .filter_map(|(entity, mut sampler)| {
+            sampler
+                .bypass_change_detection()
+                .tick(delta)
+                .then_some(entity)
+        })
+        .collect();
+    let samples: Vec<(Entity, Box<dyn Any + Send>)> = due
+        .into_iter()
+        .filter_map(|entity| {
+            let sampler = world.get::<HistorySampler>(entity)?;
+            (sampler.read)(world).map(|value| (entity, value))
+        })
+        .collect();
+    for (entity, value) in samples {
+        let mut sampler = world.get_mut::<HistorySampler>(entity).unwrap();
+        let push = sampler.push;
+        push(&mut *sampler.history, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_push_order() {
+        let mut history = ResourceHistory::<i32>::new(3);
+        history.push(1);
+        history.push(2);
+        assert_eq!(history.to_vec(), vec![1, 2]);
+        history.push(3);
+        history.push(4);
+        history.push(5);
+        assert_eq!(history.to_vec(), vec![3, 4, 5]);
+    }
+
+    #[derive(Resource)]
+    struct Fps(f32);
+
+    #[test]
+    fn test_sample_rate() {
+        let mut world = World::new();
+        world.insert_resource(Time::<()>::default());
+        world.insert_resource(Fps(1.));
+        let sampler = HistorySampler::new::<Fps, f32>(&world, |fps| fps.0, 4, 10.);
+        let entity = world.spawn(sampler).id();
+        world.clear_trackers();
+
+        // Too soon for another sample: the sampler is not marked as changed.
+        world.resource_mut::<Fps>().0 = 2.;
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(50));
+        update_histories(&mut world);
+        assert!(!world
+            .entity(entity)
+            .get_ref::<HistorySampler>()
+            .unwrap()
+            .is_changed());
+
+        // The resource is sampled at the configured rate, whether or not it changed.
+        for _ in 0..3 {
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_millis(120));
+            update_histories(&mut world);
+            let sampler = world.entity(entity).get_ref::<HistorySampler>().unwrap();
+            assert!(sampler.is_changed());
+            world.clear_trackers();
+        }
+        let mut sampler = world.get_mut::<HistorySampler>(entity).unwrap();
+        assert_eq!(sampler.history_mut::<f32>().to_vec(), vec![1., 2., 2., 2.]);
+    }
+
+    #[test]
+    fn test_resize() {
+        let mut history = ResourceHistory::<i32>::new(4);
+        for i in 0..4 {
+            history.push(i);
+        }
+        history.set_capacity(2);
+        assert_eq!(history.to_vec(), vec![2, 3]);
+        history.set_capacity(3);
+        history.push(4);
+        history.push(5);
+        assert_eq!(history.to_vec(), vec![3, 4, 5]);
+        history.set_capacity(0);
+        history.push(6);
+        assert_eq!(history.to_vec(), Vec::<i32>::new());
+    }
+}
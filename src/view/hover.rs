@@ -0,0 +1,121 @@
+use std::sync::Arc;
+
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+use bevy_mod_picking::focus::HoverMap;
+
+/// Component holding a callback which is called when the hover state of an element changes.
+/// Added by [`View::on_hover_change`].
+///
+/// [`View::on_hover_change`]: crate::View::on_hover_change
+#[derive(Component, Clone)]
+pub struct OnHoverChange(pub(crate) Arc<dyn Fn(bool, &mut World) + Send + Sync>);
+
+/// Elements which are currently hovered, along with their hover callbacks. The callback is
+/// kept here so that a leave can still be reported if the element is despawned while hovered.
+#[derive(Resource, Default)]
+pub(crate) struct HoveredCallbacks(HashMap<Entity, OnHoverChange>);
+
+/// System which calls [`OnHoverChange`] callbacks for elements which the pointer has entered
+/// or left since the last frame.
+pub(crate) fn update_hover_callbacks(world: &mut World) {
+    let hovered: HashSet<Entity> = world
+        .get_resource::<HoverMap>()
+        .map(|hover_map| {
+            hover_map
+                .0
+                .values()
+                .flat_map(|hits| hits.keys().copied())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut pending: Vec<(OnHoverChange, bool)> = Vec::new();
+    world.resource_scope(|world, mut callbacks: Mut<HoveredCallbacks>| {
+        // Elements which are no longer hovered, or which no longer exist.
+        callbacks.0.retain(|entity, callback| {
+            match world.get::<OnHoverChange>(*entity) {
+                Some(current) if hovered.contains(entity) => {
+                    // Keep the most recent callback, in case the element was rebuilt.
+                    callback.clone_from(current);
+                    true
+                }
+                _ => {
+                    pending.push((callback.clone(), false));
+                    false
+                }
+            }
+        });
+
+        // Elements which have just become hovered.
+        for entity in hovered.iter() {
+            if callbacks.0.contains_key(entity) {
+                continue;
+            }
+            if let Some(callback) = world.get::<OnHoverChange>(*entity) {
+                callbacks.0.insert(*entity, callback.clone());
+                pending.push((callback.clone(), true));
+            }
+        }
+    });
+
+    for (callback, is_hovered) in pending {
+        (callback.0)(is_hovered, world);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+    use bevy_mod_picking::{backend::HitData, pointer::PointerId};
+
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct HoverLog(Vec<bool>);
+
+    fn set_hovered(world: &mut World, entity: Option<Entity>) {
+        let mut hover_map = HoverMap::default();
+        if let Some(entity) = entity {
+            hover_map.0.insert(
+                PointerId::Mouse,
+                HashMap::from([(entity, HitData::new(Entity::PLACEHOLDER, 0., None, None))]),
+            );
+        }
+        world.insert_resource(hover_map);
+        world.run_system_once(update_hover_callbacks);
+    }
+
+    #[test]
+    fn test_on_hover_change() {
+        let mut world = World::new();
+        world.init_resource::<HoverLog>();
+        world.init_resource::<HoveredCallbacks>();
+        let entity = world
+            .spawn(OnHoverChange(Arc::new(|hovered, world| {
+                world.resource_mut::<HoverLog>().0.push(hovered);
+            })))
+            .id();
+
+        set_hovered(&mut world, Some(entity));
+        assert_eq!(world.resource::<HoverLog>().0, vec![true]);
+
+        // No transition, no callback.
+        set_hovered(&mut world, Some(entity));
+        assert_eq!(world.resource::<HoverLog>().0, vec![true]);
+
+        set_hovered(&mut world, None);
+        assert_eq!(world.resource::<HoverLog>().0, vec![true, false]);
+
+        // Despawning a hovered element reports a leave.
+        set_hovered(&mut world, Some(entity));
+        world.despawn(entity);
+        set_hovered(&mut world, None);
+        assert_eq!(
+            world.resource::<HoverLog>().0,
+            vec![true, false, true, false]
+        );
+    }
+}
@@ -26,6 +26,14 @@ impl<Pos: View, Neg: View> If<Pos, Neg> {
     }
 }
 
+impl<Pos: View> If<Pos, ()> {
+    /// Construct a new If View which renders `pos` when the condition is true, and nothing
+    /// otherwise.
+    pub fn when(test: bool, pos: Pos) -> Self {
+        Self { test, pos, neg: () }
+    }
+}
+
 impl<Pos: View, Neg: View> View for If<Pos, Neg> {
     /// Union of true and false states.
     type State = IfState<Pos::State, Neg::State>;
@@ -91,3 +99,49 @@ impl<Pos: View, Neg: View> View for If<Pos, Neg> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+
+    fn nodes<V: View>(view: &V, world: &mut World, state: &mut V::State) -> Vec<Entity> {
+        let root = world.spawn_empty().id();
+        let mut out = Vec::new();
+        view.assemble(&mut BuildContext::new(world, root), state)
+            .flatten(&mut out);
+        out
+    }
+
+    #[test]
+    fn test_if_when() {
+        let mut world = World::new();
+        let root = world.spawn_empty().id();
+        let view = If::when(true, "shown");
+        let mut state = view.build(&mut BuildContext::new(&mut world, root));
+        let shown = nodes(&view, &mut world, &mut state);
+        assert_eq!(shown.len(), 1);
+
+        // An unchanged condition updates the live arm in place.
+        let view = If::when(true, "still shown");
+        view.update(&mut BuildContext::new(&mut world, root), &mut state);
+        assert_eq!(nodes(&view, &mut world, &mut state), shown);
+        assert_eq!(
+            world.get::<Text>(shown[0]).unwrap().sections[0].value,
+            "still shown"
+        );
+
+        // Flipping the condition razes the previous arm.
+        let view = If::when(false, "shown");
+        view.update(&mut BuildContext::new(&mut world, root), &mut state);
+        assert!(nodes(&view, &mut world, &mut state).is_empty());
+        assert!(world.get_entity(shown[0]).is_none());
+
+        let view = If::when(true, "shown");
+        view.update(&mut BuildContext::new(&mut world, root), &mut state);
+        let reshown = nodes(&view, &mut world, &mut state);
+        assert_eq!(reshown.len(), 1);
+        assert_ne!(reshown, shown);
+    }
+}
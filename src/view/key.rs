@@ -0,0 +1,179 @@
+use std::sync::Arc;
+
+use bevy::{
+    a11y::Focus,
+    ecs::event::ManualEventReader,
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState,
+    },
+    prelude::*,
+};
+
+/// State of the modifier keys at the time of a [`KeyEvent`]. Left and right keys are not
+/// distinguished.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub super_key: bool,
+}
+
+impl Modifiers {
+    fn from_input(keys: &ButtonInput<KeyCode>) -> Self {
+        Self {
+            shift: keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]),
+            ctrl: keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]),
+            alt: keys.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]),
+            super_key: keys.any_pressed([KeyCode::SuperLeft, KeyCode::SuperRight]),
+        }
+    }
+}
+
+/// A keyboard event delivered to an [`OnKey`] handler.
+#[derive(Debug, Clone)]
+pub struct KeyEvent {
+    /// Physical key.
+    pub key_code: KeyCode,
+    /// Logical key, taking the keyboard layout into account.
+    pub logical_key: Key,
+    /// Whether the key was pressed or released.
+    pub state: ButtonState,
+    /// Modifier keys held when the event occurred.
+    pub modifiers: Modifiers,
+    /// The element which is handling the event. This is the focused element, or one of its
+    /// ancestors if the event is bubbling.
+    pub target: Entity,
+    consumed: bool,
+}
+
+impl KeyEvent {
+    /// True if this is a key press (including key repeats) rather than a release.
+    pub fn is_pressed(&self) -> bool {
+        self.state == ButtonState::Pressed
+    }
+
+    /// Stop the event from bubbling up to the handlers of ancestor elements.
+    pub fn consume(&mut self) {
+        self.consumed = true;
+    }
+
+    /// True if a handler has consumed the event.
+    pub fn is_consumed(&self) -> bool {
+        self.consumed
+    }
+}
+
+/// Component holding a callback which is called for keyboard events while the element, or
+/// one of its descendants, has focus. Added by [`View::on_key`].
+///
+/// [`View::on_key`]: crate::View::on_key
+#[derive(Component, Clone)]
+pub struct OnKey(pub(crate) Arc<dyn Fn(&mut KeyEvent, &mut World) + Send + Sync>);
+
+/// System which delivers keyboard input to the [`OnKey`] handler of the focused element. If
+/// the handler doesn't consume the event, or there is no handler, the event bubbles up to the
+/// nearest ancestor with a handler, and so on up to the root.
+pub(crate) fn dispatch_key_events(
+    world: &mut World,
+    mut reader: Local<ManualEventReader<KeyboardInput>>,
+) {
+    let Some(events) = world.get_resource::<Events<KeyboardInput>>() else {
+        return;
+    };
+    let inputs: Vec<KeyboardInput> = reader.read(events).cloned().collect();
+    let Some(focused) = world.get_resource::<Focus>().and_then(|focus| focus.0) else {
+        return;
+    };
+    let modifiers = world
+        .get_resource::<ButtonInput<KeyCode>>()
+        .map(Modifiers::from_input)
+        .unwrap_or_default();
+
+    for input in inputs {
+        let mut event = KeyEvent {
+            key_code: input.key_code,
+            logical_key: input.logical_key,
+            state: input.state,
+            modifiers,
+            target: focused,
+            consumed: false,
+        };
+        let mut next = Some(focused);
+        while let Some(entity) = next {
+            next = world.get::<Parent>(entity).map(|parent| parent.get());
+            // Clone the callback so that it can mutate the world, including despawning
+            // its own element.
+            if let Some(handler) = world.get::<OnKey>(entity).cloned() {
+                event.target = entity;
+                (handler.0)(&mut event, world);
+                if event.consumed {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::system::RunSystemOnce, input::keyboard::NativeKey};
+
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct KeyLog(Vec<(Entity, KeyCode)>);
+
+    fn log_keys(consume: bool) -> OnKey {
+        OnKey(Arc::new(move |event, world| {
+            if event.is_pressed() {
+                world
+                    .resource_mut::<KeyLog>()
+                    .0
+                    .push((event.target, event.key_code));
+                if consume && event.key_code == KeyCode::Enter {
+                    event.consume();
+                }
+            }
+        }))
+    }
+
+    fn press(world: &mut World, key_code: KeyCode) {
+        world.send_event(KeyboardInput {
+            key_code,
+            logical_key: Key::Unidentified(NativeKey::Unidentified),
+            state: ButtonState::Pressed,
+            window: Entity::PLACEHOLDER,
+        });
+        world.run_system_once(dispatch_key_events);
+        world.resource_mut::<Events<KeyboardInput>>().clear();
+    }
+
+    #[test]
+    fn test_on_key() {
+        let mut world = World::new();
+        world.init_resource::<Events<KeyboardInput>>();
+        world.init_resource::<KeyLog>();
+        world.init_resource::<Focus>();
+        let field = world.spawn(log_keys(true)).id();
+        let dialog = world.spawn(log_keys(false)).add_child(field).id();
+
+        // Nothing is focused.
+        press(&mut world, KeyCode::Enter);
+        assert!(world.resource::<KeyLog>().0.is_empty());
+
+        // Consumed events don't bubble; others do.
+        world.resource_mut::<Focus>().0 = Some(field);
+        press(&mut world, KeyCode::Enter);
+        press(&mut world, KeyCode::Escape);
+        assert_eq!(
+            world.resource::<KeyLog>().0,
+            vec![
+                (field, KeyCode::Enter),
+                (field, KeyCode::Escape),
+                (dialog, KeyCode::Escape),
+            ]
+        );
+    }
+}
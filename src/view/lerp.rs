@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+
+/// Distance from the target below which a lerped value snaps to the target, so that it
+/// stops changing (and stops causing rebuilds).
+pub(crate) const LERP_THRESHOLD: f32 = 0.001;
+
+/// Component which drives a value created by [`Cx::use_lerped_resource`], easing it toward
+/// its target each frame.
+///
+/// [`Cx::use_lerped_resource`]: crate::Cx::use_lerped_resource
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub(crate) struct LerpedValue {
+    pub(crate) value: f32,
+    pub(crate) target: f32,
+    pub(crate) speed: f32,
+}
+
+impl LerpedValue {
+    pub(crate) fn new(target: f32, speed: f32) -> Self {
+        Self {
+            value: target,
+            target,
+            speed,
+        }
+    }
+
+    /// Move the value toward the target by `delta` seconds. The value approaches the target
+    /// exponentially, so changing the target mid-way continues smoothly from the current
+    /// value. Returns true if the value changed.
+    pub(crate) fn advance(&mut self, delta: f32) -> bool {
+        if self.value == self.target {
+            return false;
+        }
+        let t = 1. - (-self.speed * delta).exp();
+        self.value += (self.target - self.value) * t;
+        if (self.target - self.value).abs() < LERP_THRESHOLD {
+            self.value = self.target;
+        }
+        true
+    }
+}
+
+/// System which advances all lerped values.
+pub(crate) fn update_lerped_values(mut query: Query<&mut LerpedValue>, time: Res<Time>) {
+    let delta = time.delta_seconds();
+    for mut lerp in query.iter_mut() {
+        if lerp.bypass_change_detection().advance(delta) {
+            lerp.set_changed();
+        }
+    }
+}
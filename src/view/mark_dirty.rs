@@ -0,0 +1,86 @@
+use bevy::{ecs::system::Command, prelude::*};
+
+use super::presenter_state::{PresenterStateChanged, ViewHandle};
+
+/// Command which forces the presenters held by the given entities to re-run on the next
+/// update, even if none of the resources or components they depend on have changed. Entities
+/// which don't exist (for example because the presenter was razed), or which aren't
+/// presenters, are ignored. Marking a presenter which is already going to re-run has no
+/// further effect.
+pub struct MarkViewsDirty(pub Vec<Entity>);
+
+impl Command for MarkViewsDirty {
+    fn apply(self, world: &mut World) {
+        for entity in self.0 {
+            if let Some(mut entt) = world.get_entity_mut(entity) {
+                if entt.contains::<ViewHandle>() {
+                    entt.insert(PresenterStateChanged);
+                }
+            }
+        }
+    }
+}
+
+/// Methods for forcing presenters to re-run. See [`MarkViewsDirty`].
+pub trait MarkViewsDirtyMethods {
+    /// Mark the presenters held by the given entities as needing to re-run.
+    fn mark_views_dirty(&mut self, views: impl IntoIterator<Item = Entity>);
+}
+
+impl MarkViewsDirtyMethods for Commands<'_, '_> {
+    fn mark_views_dirty(&mut self, views: impl IntoIterator<Item = Entity>) {
+        self.add(MarkViewsDirty(views.into_iter().collect()));
+    }
+}
+
+impl MarkViewsDirtyMethods for World {
+    fn mark_views_dirty(&mut self, views: impl IntoIterator<Item = Entity>) {
+        MarkViewsDirty(views.into_iter().collect()).apply(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+    use crate::{plugin::render_frame, Cx, View};
+
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    fn counted(_cx: Cx) -> impl View {
+        CALLS.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[derive(Resource)]
+    struct Target(Entity);
+
+    fn handler(mut commands: Commands, target: Res<Target>) {
+        // Marking twice is the same as marking once.
+        commands.mark_views_dirty([target.0, target.0]);
+    }
+
+    #[test]
+    fn test_mark_views_dirty() {
+        let mut world = World::new();
+        let view = world.spawn(ViewHandle::new(counted, ())).id();
+        render_frame(&mut world);
+        assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+
+        // Nothing changed, so nothing is re-run.
+        render_frame(&mut world);
+        assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+
+        world.insert_resource(Target(view));
+        world.run_system_once(handler);
+        render_frame(&mut world);
+        assert_eq!(CALLS.load(Ordering::Relaxed), 2);
+
+        // Marking a despawned view is ignored.
+        world.despawn(view);
+        world.mark_views_dirty([view]);
+        assert!(world.get_entity(view).is_none());
+    }
+}
@@ -0,0 +1,56 @@
+/// Memoized value created by [`Cx::use_memo`], along with the dependencies it was computed
+/// from.
+///
+/// [`Cx::use_memo`]: crate::Cx::use_memo
+pub(crate) struct Memo<D, T> {
+    pub(crate) deps: D,
+    pub(crate) value: T,
+}
+
+/// Memoized value created by [`Cx::use_memo_with_cleanup`], along with the dependencies it
+/// was computed from. The cleanup function is run on the value when it is replaced, or when
+/// the memo is dropped because the owning presenter was razed.
+///
+/// [`Cx::use_memo_with_cleanup`]: crate::Cx::use_memo_with_cleanup
+pub(crate) struct CleanupMemo<T, D> {
+    deps: D,
+    value: Option<T>,
+    cleanup: Box<dyn Fn(T) + Send + Sync>,
+}
+
+impl<T: Clone, D: PartialEq> CleanupMemo<T, D> {
+    pub(crate) fn new(deps: D, value: T, cleanup: Box<dyn Fn(T) + Send + Sync>) -> Self {
+        Self {
+            deps,
+            value: Some(value),
+            cleanup,
+        }
+    }
+
+    /// Return the memoized value, first replacing it with a new one if the dependencies have
+    /// changed. The old value is cleaned up before the new one is created.
+    pub(crate) fn update(
+        &mut self,
+        deps: D,
+        create: impl FnOnce() -> T,
+        cleanup: Box<dyn Fn(T) + Send + Sync>,
+    ) -> T {
+        self.cleanup = cleanup;
+        if self.deps != deps {
+            if let Some(old) = self.value.take() {
+                (self.cleanup)(old);
+            }
+            self.deps = deps;
+            self.value = Some(create());
+        }
+        self.value.clone().unwrap()
+    }
+}
+
+impl<T, D> Drop for CleanupMemo<T, D> {
+    fn drop(&mut self) {
+        if let Some(value) = self.value.take() {
+            (self.cleanup)(value);
+        }
+    }
+}
@@ -1,47 +1,86 @@
 mod atom;
+pub(crate) mod autofocus;
 mod bind;
+mod callback;
+pub(crate) mod coalesced;
+mod collection;
+pub(crate) mod countdown;
 mod cx;
+pub(crate) mod effect;
+pub(crate) mod drag;
 mod element;
+mod element_ref;
 mod r#for;
 mod for_index;
 mod for_keyed;
 mod fragment;
+pub(crate) mod history;
+pub(crate) mod hover;
 mod r#if;
+pub(crate) mod key;
 mod lcs;
+pub(crate) mod lerp;
+mod mark_dirty;
+mod memo;
 mod portal;
+pub(crate) mod rate_limit;
 pub(crate) mod presenter_state;
 mod ref_element;
+mod resource_tuple;
 mod scoped_values;
+mod scroll_view;
 pub(crate) mod tracked_resources;
 pub(crate) mod tracking;
 #[allow(clippy::module_inception)]
 pub(crate) mod view;
+mod view_after_render;
 mod view_children;
 mod view_classes;
+mod view_insert;
 mod view_insert_bundle;
 mod view_named;
 mod view_param;
+mod view_single_node;
 mod view_styled;
+mod view_switch;
+mod view_text;
 mod view_tuple;
+mod view_vec;
 mod view_with;
 mod view_with_memo;
 
 pub use atom::*;
+pub use autofocus::AutoFocus;
 pub use bind::Bind;
+pub use callback::Callback;
+pub use collection::Collection;
+pub use countdown::{Countdown, CountdownHandle, CountdownTimer};
 pub use cx::Cx;
+pub use drag::{DragState, DragStates};
+pub use effect::Cleanup;
 pub use element::Element;
+pub use element_ref::{ElementRef, ViewElementRef};
 pub use for_index::ForIndex;
 pub use for_keyed::ForKeyed;
 pub use fragment::Fragment;
+pub use hover::OnHoverChange;
+pub use key::{KeyEvent, Modifiers, OnKey};
+pub use mark_dirty::{MarkViewsDirty, MarkViewsDirtyMethods};
 pub use portal::Portal;
 pub use presenter_state::ViewHandle;
 pub use r#for::For;
 pub use r#if::If;
 pub use ref_element::RefElement;
+pub use resource_tuple::ResourceTuple;
 pub use scoped_values::ScopedValueKey;
+pub use scroll_view::ScrollView;
 pub(crate) use tracking::TrackingContext;
 pub use view::PresenterFn;
 pub use view::View;
 pub use view::*;
+pub use view_after_render::ViewAfterRender;
 pub use view_param::ViewParam;
+pub use view_single_node::ViewSingleNode;
+pub use view_switch::{Switch, SwitchState};
+pub use view_text::{RichText, RichTextNode};
 pub use view_tuple::ViewTuple;
@@ -1,63 +1,191 @@
 use bevy::prelude::*;
 
-use crate::{BuildContext, View};
+use crate::{BuildContext, View, ViewTuple};
 
 use crate::node_span::NodeSpan;
 
-/// Portal behaves just like Element, except that the generated UI nodes are unparented,
-/// making them roots.
-#[derive(Default)]
-pub struct Portal {}
+/// A View which mounts its children somewhere else in the UI hierarchy. This lets overlays
+/// such as dropdowns and modal dialogs escape the clipping and stacking context of the view
+/// which owns them.
+///
+/// The output nodes of the children are attached to the `target` entity, after any children
+/// it already has, rather than to the parent of the portal; if there is no target, they
+/// become UI roots. The portal itself has no output nodes. Styles are inherited from the new
+/// parent.
+///
+/// The children of the target should not be managed by another view, since a view which
+/// replaces its children would detach those of the portal. A dedicated overlay node works
+/// well.
+///
+/// ```ignore
+/// Portal::new(overlay_layer, Element::new().styled(STYLE_MENU.clone()).children(items))
+/// ```
+pub struct Portal<A: ViewTuple> {
+    target: Option<Entity>,
+    items: A,
+}
+
+impl<A: ViewTuple> Portal<A> {
+    /// Construct a new `Portal` which attaches its children to `target`.
+    pub fn new(target: Entity, items: A) -> Self {
+        Self {
+            target: Some(target),
+            items,
+        }
+    }
+
+    /// Construct a new `Portal` whose children are UI roots.
+    pub fn root(items: A) -> Self {
+        Self {
+            target: None,
+            items,
+        }
+    }
+}
 
-impl Portal {
-    /// Construct a new, empty `Element`.
-    pub fn new() -> Self {
-        Self {}
+/// Detach the given nodes from the portal target, if they are still attached to it.
+fn detach(world: &mut World, target: Option<Entity>, nodes: &[Entity]) {
+    for node in nodes {
+        if let Some(mut entt) = world.get_entity_mut(*node) {
+            if entt.get::<Parent>().map(|parent| parent.get()) == target {
+                entt.remove_parent();
+            }
+        }
     }
 }
 
-impl View for Portal {
-    type State = Entity;
+impl<A: ViewTuple> View for Portal<A> {
+    /// Nodes currently attached to the target, and state of the child views.
+    type State = (Vec<Entity>, A::State);
 
-    fn nodes(&self, _vc: &BuildContext, _state: &Self::State) -> NodeSpan {
+    fn nodes(&self, _bc: &BuildContext, _state: &Self::State) -> NodeSpan {
         NodeSpan::Empty
     }
 
     fn build(&self, bc: &mut BuildContext) -> Self::State {
-        let new_entity = bc
-            .world
-            .spawn((
-                NodeBundle {
-                    visibility: Visibility::Visible,
-                    ..default()
-                },
-                Name::new("Portal"),
-            ))
-            .id();
-        new_entity
-    }
-
-    fn update(&self, _vc: &mut BuildContext, _state: &mut Self::State) {}
-
-    fn assemble(&self, _vc: &mut BuildContext, _state: &mut Self::State) -> NodeSpan {
+        (Vec::new(), self.items.build_spans(bc))
+    }
+
+    fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
+        self.items.update_spans(bc, &mut state.1);
+    }
+
+    fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
+        let children = self.items.assemble_spans(bc, &mut state.1);
+        let mut flat: Vec<Entity> = Vec::with_capacity(children.count());
+        children.flatten(&mut flat);
+
+        // A despawned target is treated like no target.
+        let target = self
+            .target
+            .filter(|target| bc.world.get_entity(*target).is_some());
+        let attached = flat
+            .iter()
+            .all(|node| bc.world.get::<Parent>(*node).map(|parent| parent.get()) == target);
+        if !attached || flat != state.0 {
+            detach(bc.world, target, &state.0);
+            match target {
+                Some(target) => {
+                    bc.entity_mut(target).push_children(&flat);
+                }
+                None => {
+                    for node in flat.iter() {
+                        bc.entity_mut(*node).remove_parent();
+                    }
+                }
+            }
+            state.0 = flat;
+        }
         NodeSpan::Empty
     }
 
     fn raze(&self, world: &mut World, state: &mut Self::State) {
-        let mut entt = world.entity_mut(*state);
-        entt.remove_parent();
-        entt.despawn();
+        // Razing the child views also detaches their nodes from the target.
+        self.items.raze_spans(world, &mut state.1);
+        state.0.clear();
     }
 }
 
-impl Clone for Portal {
+impl<A: ViewTuple + Clone> Clone for Portal<A> {
     fn clone(&self) -> Self {
-        Self {}
+        Self {
+            target: self.target,
+            items: self.items.clone(),
+        }
     }
 }
 
-impl PartialEq for Portal {
-    fn eq(&self, _other: &Self) -> bool {
-        true
+impl<A: ViewTuple + PartialEq> PartialEq for Portal<A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.target == other.target && self.items == other.items
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Element, If};
+
+    #[test]
+    fn test_portal() {
+        let mut world = World::new();
+        let root = world.spawn_empty().id();
+        let existing = world.spawn(NodeBundle::default()).id();
+        let target = world.spawn(NodeBundle::default()).add_child(existing).id();
+        let portal =
+            |show: bool| Element::new().children(Portal::new(target, ("a", If::when(show, "b"))));
+
+        let view = portal(true);
+        let mut bc = BuildContext::new(&mut world, root);
+        let mut state = view.build(&mut bc);
+        let element = state.0;
+        view.assemble(&mut bc, &mut state);
+
+        // The nodes are attached to the target, after its own children, and not to the
+        // element which contains the portal.
+        assert!(world
+            .get::<Children>(element)
+            .map_or(true, |children| children.is_empty()));
+        let children = world.get::<Children>(target).unwrap().to_vec();
+        assert_eq!(children.len(), 3);
+        assert_eq!(children[0], existing);
+        let (a, b) = (children[1], children[2]);
+
+        // Changing the children updates only the portal's nodes.
+        let view = portal(false);
+        let mut bc = BuildContext::new(&mut world, root);
+        view.update(&mut bc, &mut state);
+        view.assemble(&mut bc, &mut state);
+        assert_eq!(
+            world.get::<Children>(target).unwrap().to_vec(),
+            vec![existing, a]
+        );
+        assert!(world.get_entity(b).is_none());
+
+        // Razing the view despawns the nodes, despite them being elsewhere.
+        view.raze(&mut world, &mut state);
+        assert!(world.get_entity(element).is_none());
+        assert!(world.get_entity(a).is_none());
+        assert_eq!(
+            world.get::<Children>(target).unwrap().to_vec(),
+            vec![existing]
+        );
+    }
+
+    #[test]
+    fn test_portal_root() {
+        let mut world = World::new();
+        let root = world.spawn_empty().id();
+        let view = Element::new().children(Portal::root("a"));
+
+        let mut bc = BuildContext::new(&mut world, root);
+        let mut state = view.build(&mut bc);
+        view.assemble(&mut bc, &mut state);
+        let node = state.1 .1;
+        assert!(world.get::<Parent>(node).is_none());
+        assert!(world.get::<Text>(node).is_some());
+
+        view.raze(&mut world, &mut state);
+        assert!(world.get_entity(node).is_none());
     }
 }
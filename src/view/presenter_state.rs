@@ -3,15 +3,19 @@ use std::{
     sync::{Arc, Mutex},
 };
 
-use bevy::{prelude::*, utils::HashSet};
+use bevy::{
+    ecs::system::CommandQueue,
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
 
 use crate::{
     tracked_resources::TrackedResources,
-    tracking::{OwnedEntities, TrackedComponents},
-    BuildContext, NodeSpan, PresenterFn, TrackingContext,
+    tracking::{validate_hook_count, HookSignatures, OwnedEntities, TrackedComponents},
+    BuildContext, NodeSpan, PresenterFn, QuillSettings, TrackingContext,
 };
 
-use super::{cx::Cx, View};
+use super::{cx::Cx, effect::run_cleanup, View};
 
 /// A ViewHandle holds a type-erased reference to a presenter function and its props and state.
 #[derive(Component)]
@@ -107,15 +111,32 @@ impl<Marker, F: PresenterFn<Marker>> AnyPresenterState for PresenterState<Marker
             Some(owned) => owned.0.clone(),
             None => Vec::new(),
         };
+        let strict_hooks = cfg!(debug_assertions)
+            && bc
+                .world
+                .get_resource::<QuillSettings>()
+                .is_some_and(|settings| settings.strict_hooks);
+        let prev_hooks = match bc.world.entity(entity).get::<HookSignatures>() {
+            Some(hooks) if strict_hooks => Some(hooks.0.clone()),
+            _ => None,
+        };
         let mut child_context = bc.for_entity(entity);
         let mut tracking = TrackingContext {
             resources: Vec::new(),
             components: HashSet::new(),
             next_entity_index: 0,
             owned_entities: atom_handles,
+            first_render: self.state.is_none(),
+            commands: CommandQueue::default(),
+            prev_hooks,
+            hooks: if strict_hooks { Some(Vec::new()) } else { None },
+            snapshots: HashMap::new(),
         };
         let cx = Cx::new(&self.props, &mut child_context, &mut tracking);
         self.view = Some(self.presenter.call(cx));
+        if let (Some(prev), Some(hooks)) = (&tracking.prev_hooks, &tracking.hooks) {
+            validate_hook_count(prev, hooks);
+        }
         match self.state {
             Some(ref mut state) => {
                 self.view
@@ -134,6 +155,9 @@ impl<Marker, F: PresenterFn<Marker>> AnyPresenterState for PresenterState<Marker
             }
         };
 
+        // Apply deferred commands now that the view graph has been built.
+        tracking.commands.apply(bc.world);
+
         let tick = bc.world.change_tick();
         let mut entt = bc.world.entity_mut(entity);
         if tracking.resources.is_empty() {
@@ -158,6 +182,17 @@ impl<Marker, F: PresenterFn<Marker>> AnyPresenterState for PresenterState<Marker
         } else {
             entt.insert(OwnedEntities(tracking.owned_entities));
         }
+
+        // Signatures recorded while strict hooks were enabled are stale once it's disabled,
+        // and must not be compared against if it's enabled again.
+        match tracking.hooks {
+            Some(hooks) => {
+                entt.insert(HookSignatures(hooks));
+            }
+            None => {
+                entt.remove::<HookSignatures>();
+            }
+        }
     }
 
     fn raze(&mut self, world: &mut World, entity: Entity) {
@@ -175,6 +210,7 @@ impl<Marker, F: PresenterFn<Marker>> AnyPresenterState for PresenterState<Marker
             let mut handles_copy: Vec<Entity> = Vec::new();
             std::mem::swap(&mut handles.0, &mut handles_copy);
             for handle in handles_copy.iter() {
+                run_cleanup(world, *handle);
                 world.despawn(*handle);
             }
         }
@@ -187,13 +223,15 @@ impl<Marker, F: PresenterFn<Marker>> AnyPresenterState for PresenterState<Marker
             .as_ref()
             .unwrap()
             .assemble(&mut child_context, self.state.as_mut().unwrap());
-        if self.nodes != nodes {
-            self.nodes = nodes;
+        // The parent only needs to rebuild its children if the attached entities changed,
+        // not if they are merely nested differently.
+        if !self.nodes.same_entities(&nodes) {
             // Parent needs to rebuild children
             if let Some(parent) = bc.entity(bc.entity).get::<Parent>() {
                 bc.entity_mut(parent.get()).insert(PresenterGraphChanged);
             }
         }
+        self.nodes = nodes;
     }
 
     fn nodes(&self) -> NodeSpan {
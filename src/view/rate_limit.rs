@@ -0,0 +1,84 @@
+use bevy::{ecs::component::Tick, prelude::*};
+
+/// Component which limits how often a presenter is rebuilt in response to changes to a
+/// resource, created by [`Cx::use_resource_rate_limited`]. Changes to the resource are
+/// observed by a system each frame, but the component is only marked as changed (causing the
+/// presenter to rebuild) at most once per interval.
+///
+/// [`Cx::use_resource_rate_limited`]: crate::Cx::use_resource_rate_limited
+#[derive(Component)]
+pub(crate) struct RateLimit {
+    /// Returns the tick at which the resource was last changed.
+    last_changed: fn(&World) -> Option<Tick>,
+    seen: Option<Tick>,
+    pub(crate) interval: f32,
+    pub(crate) leading: bool,
+    cooldown: f32,
+    pending: bool,
+}
+
+impl RateLimit {
+    pub(crate) fn new<T: Resource>(world: &World, hz: f32, leading: bool) -> Self {
+        let last_changed: fn(&World) -> Option<Tick> =
+            |world| world.get_resource_ref::<T>().map(|res| res.last_changed());
+        Self {
+            last_changed,
+            seen: last_changed(world),
+            interval: interval(hz),
+            leading,
+            cooldown: 0.,
+            pending: false,
+        }
+    }
+
+    /// Advance the limiter by `delta` seconds, given the tick at which the resource was last
+    /// changed. Returns true if the presenter should be rebuilt.
+    ///
+    /// A change which arrives during a quiet period rebuilds immediately if `leading` is set;
+    /// otherwise it starts the interval, and the rebuild happens when the interval ends.
+    /// Changes which arrive during an interval are deferred until it ends, and the rebuild
+    /// at the end of an interval starts another one.
+    fn advance(&mut self, delta: f32, changed: Option<Tick>) -> bool {
+        self.cooldown = (self.cooldown - delta).max(0.);
+        if changed != self.seen {
+            let quiet = self.cooldown <= 0. && !self.pending;
+            self.seen = changed;
+            self.pending = true;
+            if quiet && !self.leading {
+                self.cooldown = self.interval;
+            }
+        }
+        if self.pending && self.cooldown <= 0. {
+            self.pending = false;
+            self.cooldown = self.interval;
+            return true;
+        }
+        false
+    }
+}
+
+/// Convert a rate in hertz into an interval in seconds. A rate of zero means every frame.
+pub(crate) fn interval(hz: f32) -> f32 {
+    if hz > 0. {
+        1. / hz
+    } else {
+        0.
+    }
+}
+
+/// System which observes changes to rate-limited resources, and marks their limiters as
+/// changed when the presenter should be rebuilt.
+pub(crate) fn update_rate_limits(world: &mut World) {
+    let delta = world.resource::<Time>().delta_seconds();
+    let mut query = world.query::<(Entity, &RateLimit)>();
+    let changes: Vec<(Entity, Option<Tick>)> = query
+        .iter(world)
+        .map(|(entity, limit)| (entity, (limit.last_changed)(world)))
+        .collect();
+    for (entity, changed) in changes {
+        let mut limit = world.get_mut::<RateLimit>(entity).unwrap();
+        if limit.bypass_change_detection().advance(delta, changed) {
+            limit.set_changed();
+        }
+    }
+}
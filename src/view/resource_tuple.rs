@@ -0,0 +1,43 @@
+use bevy::{prelude::*, utils::all_tuples};
+
+use crate::Cx;
+
+/// A tuple of [`Resource`] types which can be read all at once via [`crate::Cx::use_resources`].
+pub trait ResourceTuple {
+    /// Tuple of references to each of the resources.
+    type Item<'a>;
+
+    /// Add all of the resources in the tuple as dependencies of the current presenter.
+    fn track<Props>(cx: &Cx<Props>);
+
+    /// Return references to all of the resources in the tuple.
+    fn fetch(world: &World) -> Self::Item<'_>;
+}
+
+fn fetch_resource<T: Resource>(world: &World) -> &T {
+    match world.get_resource::<T>() {
+        Some(res) => res,
+        None => panic!(
+            "Resource requested by use_resources does not exist: {}",
+            std::any::type_name::<T>()
+        ),
+    }
+}
+
+macro_rules! impl_resource_tuple {
+    ($($T:ident),*) => {
+        impl<$($T: Resource),*> ResourceTuple for ($($T,)*) {
+            type Item<'a> = ($(&'a $T,)*);
+
+            fn track<Props>(cx: &Cx<Props>) {
+                $(cx.add_tracked_resource::<$T>();)*
+            }
+
+            fn fetch(world: &World) -> Self::Item<'_> {
+                ($(fetch_resource::<$T>(world),)*)
+            }
+        }
+    };
+}
+
+all_tuples!(impl_resource_tuple, 1, 12, T);
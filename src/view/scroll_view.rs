@@ -0,0 +1,217 @@
+use bevy::{prelude::*, ui};
+use bevy_mod_picking::prelude::On;
+
+use crate::{
+    BuildContext, ScrollArea, ScrollContent, ScrollDirection, ScrollWheel, View, ViewTuple,
+};
+
+use crate::node_span::NodeSpan;
+
+/// A View which displays its children in a scrollable region. The output node is a
+/// [`ScrollArea`] which clips its content, and which is scrolled by the mouse wheel while
+/// the pointer is over it. Scroll events are consumed by the innermost scroll view, so
+/// scroll views can be nested.
+///
+/// The children are placed within an absolutely-positioned [`ScrollContent`] node, so the
+/// scroll view doesn't grow to fit them: give it a size, either directly or by letting it
+/// flex within its parent. The content is stretched to fill the visible area along any axis
+/// which doesn't scroll, and the scroll position is clamped to the measured size of the
+/// content.
+///
+/// ```ignore
+/// ScrollView::new(For::each(&items, |item| item.name.clone()))
+///     .direction(ScrollDirection::Vertical)
+///     .styled(STYLE_LIST.clone())
+/// ```
+pub struct ScrollView<A: ViewTuple> {
+    direction: ScrollDirection,
+    items: A,
+}
+
+impl<A: ViewTuple> ScrollView<A> {
+    /// Construct a new `ScrollView` which scrolls vertically.
+    pub fn new(items: A) -> Self {
+        Self {
+            direction: ScrollDirection::Vertical,
+            items,
+        }
+    }
+
+    /// Set the axes along which the content can be scrolled.
+    pub fn direction(mut self, direction: ScrollDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+}
+
+/// Layout of the content node: it fills the scroll area along the axes which don't scroll,
+/// and is at least as large as the scroll area along the axes which do.
+fn content_style(direction: ScrollDirection) -> Style {
+    let fill = |scrolls: bool| {
+        if scrolls {
+            (ui::Val::Auto, ui::Val::Percent(100.))
+        } else {
+            (ui::Val::Percent(100.), ui::Val::Auto)
+        }
+    };
+    let (width, min_width) = fill(direction.horizontal());
+    let (height, min_height) = fill(direction.vertical());
+    Style {
+        position_type: ui::PositionType::Absolute,
+        flex_direction: ui::FlexDirection::Column,
+        width,
+        min_width,
+        height,
+        min_height,
+        ..default()
+    }
+}
+
+impl<A: ViewTuple> View for ScrollView<A> {
+    /// Scroll area, scroll content, and state of the child views.
+    type State = (Entity, Entity, A::State);
+
+    fn nodes(&self, _bc: &BuildContext, state: &Self::State) -> NodeSpan {
+        NodeSpan::Node(state.0)
+    }
+
+    fn build(&self, bc: &mut BuildContext) -> Self::State {
+        let content = bc
+            .world
+            .spawn((
+                NodeBundle {
+                    style: content_style(self.direction),
+                    visibility: Visibility::Visible,
+                    ..default()
+                },
+                ScrollContent,
+                Name::new("ScrollContent"),
+            ))
+            .id();
+        let area = bc
+            .world
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        overflow: ui::Overflow::clip(),
+                        ..default()
+                    },
+                    visibility: Visibility::Visible,
+                    ..default()
+                },
+                ScrollArea {
+                    direction: self.direction,
+                    ..default()
+                },
+                On::<ScrollWheel>::listener_component_mut::<ScrollArea>(|ev, scrolling| {
+                    ev.stop_propagation();
+                    // A plain mouse wheel only scrolls vertically, so when the view can only
+                    // scroll horizontally, use the wheel for that instead.
+                    if scrolling.direction == ScrollDirection::Horizontal && ev.delta.x == 0. {
+                        scrolling.scroll_by(-ev.delta.y, 0.);
+                    } else {
+                        scrolling.scroll_by(-ev.delta.x, -ev.delta.y);
+                    }
+                }),
+                Name::new("ScrollView"),
+            ))
+            .add_child(content)
+            .id();
+        let items = self.items.build_spans(bc);
+        (area, content, items)
+    }
+
+    fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
+        let mut area = bc.entity_mut(state.0);
+        let mut scrolling = area.get_mut::<ScrollArea>().unwrap();
+        if scrolling.direction != self.direction {
+            scrolling.direction = self.direction;
+            let (x, y) = (scrolling.scroll_left, scrolling.scroll_top);
+            scrolling.scroll_to(x, y);
+            let style = content_style(self.direction);
+            bc.entity_mut(state.1).insert(style);
+        }
+        self.items.update_spans(bc, &mut state.2);
+    }
+
+    fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
+        let children = self.items.assemble_spans(bc, &mut state.2);
+        let mut flat: Vec<Entity> = Vec::with_capacity(children.count());
+        children.flatten(&mut flat);
+        let mut content = bc.entity_mut(state.1);
+        if content
+            .get::<Children>()
+            .map_or(true, |children| !children.eq(&flat))
+        {
+            content.replace_children(&flat);
+        }
+        NodeSpan::Node(state.0)
+    }
+
+    fn raze(&self, world: &mut World, state: &mut Self::State) {
+        self.items.raze_spans(world, &mut state.2);
+        world.entity_mut(state.1).despawn();
+        let mut entt = world.entity_mut(state.0);
+        entt.remove_parent();
+        entt.despawn();
+    }
+}
+
+impl<A: ViewTuple + Clone> Clone for ScrollView<A> {
+    fn clone(&self) -> Self {
+        Self {
+            direction: self.direction,
+            items: self.items.clone(),
+        }
+    }
+}
+
+impl<A: ViewTuple + PartialEq> PartialEq for ScrollView<A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.direction == other.direction && self.items == other.items
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scroll_view() {
+        let mut world = World::new();
+        let root = world.spawn_empty().id();
+        let view = ScrollView::new(("a", "b"));
+
+        let mut bc = BuildContext::new(&mut world, root);
+        let mut state = view.build(&mut bc);
+        assert_eq!(view.assemble(&mut bc, &mut state), NodeSpan::Node(state.0));
+        let (area, content) = (state.0, state.1);
+        assert_eq!(world.get::<Children>(area).unwrap().to_vec(), vec![content]);
+        assert_eq!(world.get::<Children>(content).unwrap().len(), 2);
+        assert!(world.get::<ScrollContent>(content).is_some());
+        assert_eq!(
+            world.get::<ScrollArea>(area).unwrap().direction,
+            ScrollDirection::Vertical
+        );
+        assert_eq!(
+            world.get::<Style>(content).unwrap().width,
+            ui::Val::Percent(100.)
+        );
+
+        // Changing the direction updates the area and the layout of the content.
+        let view = ScrollView::new(("a", "b")).direction(ScrollDirection::Horizontal);
+        let mut bc = BuildContext::new(&mut world, root);
+        view.update(&mut bc, &mut state);
+        view.assemble(&mut bc, &mut state);
+        assert_eq!(
+            world.get::<ScrollArea>(area).unwrap().direction,
+            ScrollDirection::Horizontal
+        );
+        assert_eq!(world.get::<Style>(content).unwrap().width, ui::Val::Auto);
+        assert_eq!(world.get::<Children>(content).unwrap().len(), 2);
+
+        view.raze(&mut world, &mut state);
+        assert!(world.get_entity(area).is_none());
+        assert!(world.get_entity(content).is_none());
+    }
+}
@@ -1,6 +1,12 @@
-use std::marker::PhantomData;
+use std::{marker::PhantomData, sync::Mutex};
 
-use bevy::ecs::{component::Component, system::Resource, world::World};
+use bevy::ecs::{
+    component::{Component, ComponentId},
+    entity::Entity,
+    query::{QueryFilter, QueryState, ReadOnlyQueryData},
+    system::Resource,
+    world::World,
+};
 
 pub trait AnyResource: Send + Sync {
     fn is_changed(&self, world: &World) -> bool;
@@ -26,6 +32,161 @@ where
     }
 }
 
+/// A tracked resource which only reports a change when the result of a predicate on the
+/// resource differs from the result at the time the presenter was built.
+pub struct TrackedResourcePredicate<T, F> {
+    pub predicate: F,
+    pub last: bool,
+    pub pdata: PhantomData<T>,
+}
+
+impl<T, F> TrackedResourcePredicate<T, F> {
+    pub(crate) fn new(predicate: F, last: bool) -> Self {
+        Self {
+            predicate,
+            last,
+            pdata: PhantomData,
+        }
+    }
+}
+
+impl<T, F> AnyResource for TrackedResourcePredicate<T, F>
+where
+    T: Resource,
+    F: Fn(&T) -> bool + Send + Sync,
+{
+    fn is_changed(&self, world: &World) -> bool {
+        world.is_resource_changed::<T>() && (self.predicate)(world.resource::<T>()) != self.last
+    }
+}
+
+/// A tracked resource which only reports a change when the value picked out of the resource
+/// by a selector differs from the value at the time the presenter was built.
+pub struct TrackedResourceSelector<T, R, F> {
+    pub selector: F,
+    pub last: R,
+    pub pdata: PhantomData<T>,
+}
+
+impl<T, R, F> TrackedResourceSelector<T, R, F> {
+    pub(crate) fn new(selector: F, last: R) -> Self {
+        Self {
+            selector,
+            last,
+            pdata: PhantomData,
+        }
+    }
+}
+
+impl<T, R, F> AnyResource for TrackedResourceSelector<T, R, F>
+where
+    T: Resource,
+    R: PartialEq + Send + Sync,
+    F: Fn(&T) -> &R + Send + Sync,
+{
+    fn is_changed(&self, world: &World) -> bool {
+        world.is_resource_changed::<T>() && *(self.selector)(world.resource::<T>()) != self.last
+    }
+}
+
+/// A tracked resource which only reports a change when a value computed from the resource
+/// by a selector differs from the value at the time the presenter was built. Unlike
+/// [`TrackedResourceSelector`], the selected value is owned rather than borrowed, so it can be
+/// derived from several fields.
+pub struct TrackedResourceProjection<T, U, F> {
+    pub selector: F,
+    pub last: U,
+    pub pdata: PhantomData<T>,
+}
+
+impl<T, U, F> TrackedResourceProjection<T, U, F> {
+    pub(crate) fn new(selector: F, last: U) -> Self {
+        Self {
+            selector,
+            last,
+            pdata: PhantomData,
+        }
+    }
+}
+
+impl<T, U, F> AnyResource for TrackedResourceProjection<T, U, F>
+where
+    T: Resource,
+    U: PartialEq + Send + Sync,
+    F: Fn(&T) -> U + Send + Sync,
+{
+    fn is_changed(&self, world: &World) -> bool {
+        world.is_resource_changed::<T>() && (self.selector)(world.resource::<T>()) != self.last
+    }
+}
+
+/// A tracked resource which also reports a change when the resource is inserted or removed,
+/// for resources which may not exist.
+pub struct TrackedResourcePresence<T> {
+    pub present: bool,
+    pub pdata: PhantomData<T>,
+}
+
+impl<T> TrackedResourcePresence<T> {
+    pub(crate) fn new(present: bool) -> Self {
+        Self {
+            present,
+            pdata: PhantomData,
+        }
+    }
+}
+
+impl<T> AnyResource for TrackedResourcePresence<T>
+where
+    T: Resource,
+{
+    fn is_changed(&self, world: &World) -> bool {
+        world.contains_resource::<T>() != self.present || world.is_resource_changed::<T>()
+    }
+}
+
+/// A tracked query, which reports a change when the set of entities matched by the query
+/// changes, or when any of the components read by the query changed on a matched entity. This
+/// uses change ticks, so writing an identical value to a component still counts as a change.
+pub struct TrackedQuery<Q: ReadOnlyQueryData + 'static, F: QueryFilter + 'static> {
+    pub state: Mutex<QueryState<(Entity, Q), F>>,
+    pub components: Vec<ComponentId>,
+    pub entities: Vec<Entity>,
+}
+
+impl<Q: ReadOnlyQueryData + 'static, F: QueryFilter + 'static> TrackedQuery<Q, F> {
+    pub(crate) fn new(
+        state: QueryState<(Entity, Q), F>,
+        components: Vec<ComponentId>,
+        entities: Vec<Entity>,
+    ) -> Self {
+        Self {
+            state: Mutex::new(state),
+            components,
+            entities,
+        }
+    }
+}
+
+impl<Q: ReadOnlyQueryData + 'static, F: QueryFilter + 'static> AnyResource for TrackedQuery<Q, F> {
+    fn is_changed(&self, world: &World) -> bool {
+        let mut state = self.state.lock().unwrap();
+        state.update_archetypes(world);
+        let matched = state.iter_manual(world).map(|(entity, _)| entity);
+        if !matched.eq(self.entities.iter().copied()) {
+            return true;
+        }
+        let (last_run, this_run) = (world.last_change_tick(), world.read_change_tick());
+        self.entities.iter().any(|entity| {
+            let entt = world.entity(*entity);
+            self.components.iter().any(|cid| {
+                entt.get_change_ticks_by_id(*cid)
+                    .is_some_and(|ticks| ticks.is_changed(last_run, this_run))
+            })
+        })
+    }
+}
+
 /// List of resources used by a presenter.
 pub(crate) type TrackedResourceList = Vec<Box<dyn AnyResource>>;
 
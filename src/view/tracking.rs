@@ -1,8 +1,13 @@
+use std::any::{Any, TypeId};
+
 use crate::tracked_resources::TrackedResourceList;
 use bevy::{
-    ecs::component::{ComponentId, Tick},
+    ecs::{
+        component::{ComponentId, Tick},
+        system::CommandQueue,
+    },
     prelude::*,
-    utils::HashSet,
+    utils::{HashMap, HashSet},
 };
 
 pub(crate) struct TrackingContext {
@@ -10,6 +15,63 @@ pub(crate) struct TrackingContext {
     pub(crate) components: HashSet<(Entity, ComponentId)>,
     pub(crate) next_entity_index: usize,
     pub(crate) owned_entities: Vec<Entity>,
+    /// True if the presenter has not been built before.
+    pub(crate) first_render: bool,
+    /// Commands issued by the presenter, applied once the build phase is complete.
+    pub(crate) commands: CommandQueue,
+    /// Hook signatures from the previous render, if hook order is being validated.
+    pub(crate) prev_hooks: Option<Vec<HookSignature>>,
+    /// Hook signatures recorded during this render, if hook order is being validated.
+    pub(crate) hooks: Option<Vec<HookSignature>>,
+    /// Resource values captured by `Cx::snapshot_resource` during this render.
+    pub(crate) snapshots: HashMap<TypeId, Box<dyn Any>>,
+}
+
+/// Name and value type of a hook call, used to validate hook order in strict mode.
+pub(crate) type HookSignature = (&'static str, &'static str);
+
+/// Hook signatures recorded during the previous render of a presenter.
+#[derive(Component, Default)]
+pub(crate) struct HookSignatures(pub(crate) Vec<HookSignature>);
+
+/// Validate the hook called at position `index` against the previous render, panicking with
+/// a description of the mismatch if it differs.
+pub(crate) fn validate_hook(prev: &[HookSignature], index: usize, hook: HookSignature) {
+    match prev.get(index) {
+        Some(prev_hook) if *prev_hook == hook => {}
+        Some(prev_hook) => panic!(
+            "Hook order changed between renders: hook #{} was `{}<{}>` on the previous render, \
+            but is `{}<{}>` on this render. Hooks must be called in the same order on every \
+            render, and must not be called conditionally or in loops of varying length.",
+            index, prev_hook.0, prev_hook.1, hook.0, hook.1
+        ),
+        None => panic!(
+            "Hook order changed between renders: hook #{} `{}<{}>` was not called on the \
+            previous render, which called {} hooks. Hooks must not be called conditionally or \
+            in loops of varying length.",
+            index,
+            hook.0,
+            hook.1,
+            prev.len()
+        ),
+    }
+}
+
+/// Validate that the same number of hooks were called as on the previous render.
+pub(crate) fn validate_hook_count(prev: &[HookSignature], hooks: &[HookSignature]) {
+    if hooks.len() < prev.len() {
+        let missing = prev[hooks.len()];
+        panic!(
+            "Hook order changed between renders: {} hooks were called on the previous render, \
+            but only {} on this render; hook #{} `{}<{}>` was skipped. Hooks must not be called \
+            conditionally or in loops of varying length.",
+            prev.len(),
+            hooks.len(),
+            hooks.len(),
+            missing.0,
+            missing.1
+        );
+    }
 }
 
 /// Tracks components used by each View tree entity
@@ -1,15 +1,29 @@
 use std::{cell::Cell, sync::Arc};
 
-use bevy::prelude::*;
+use bevy::{asset::AssetPath, prelude::*};
 
-use crate::{presenter_state::*, ClassNames, Cx, StyleTuple, ViewTuple};
+use crate::{
+    presenter_state::*, ClassNames, Cx, ElementAttributes, ElementStyleAsset, StyleAsset,
+    StyleTuple, ViewTuple,
+};
 
 use crate::node_span::NodeSpan;
 
 use super::{
-    bind::Bind, view_children::ViewChildren, view_classes::ViewClasses,
-    view_insert_bundle::ViewInsertBundle, view_named::ViewNamed, view_styled::ViewStyled,
-    view_with::ViewWith, view_with_memo::ViewWithMemo,
+    bind::Bind,
+    element_ref::{ElementRef, ViewElementRef},
+    hover::OnHoverChange,
+    key::{KeyEvent, OnKey},
+    view_after_render::ViewAfterRender,
+    view_children::ViewChildren,
+    view_classes::ViewClasses,
+    view_insert::ViewInsert,
+    view_insert_bundle::ViewInsertBundle,
+    view_named::ViewNamed,
+    view_single_node::ViewSingleNode,
+    view_styled::ViewStyled,
+    view_with::ViewWith,
+    view_with_memo::ViewWithMemo,
 };
 
 /// Passed to `build`, `update` and `raze` methods to give access to the world and the view entity.
@@ -86,16 +100,70 @@ where
         ViewNamed::new(self, name)
     }
 
+    /// Record the output entity of this view in the given [`ElementRef`], so that the
+    /// presenter can look it up after the view has been built.
+    fn element_ref(self, element_ref: ElementRef) -> ViewElementRef<Self> {
+        ViewElementRef {
+            inner: self,
+            element_ref,
+        }
+    }
+
     /// Apply styles to this view.
     fn styled<S: StyleTuple>(self, styles: S) -> ViewStyled<Self> {
         ViewStyled::new(self, styles)
     }
 
+    /// Apply a style loaded from a `.style.ron` asset file to this view, in addition to any
+    /// styles set with [`View::styled`]. The element is restyled when the asset finishes
+    /// loading, and again whenever it is reloaded; see [`StyleAsset`].
+    #[allow(clippy::type_complexity)]
+    fn style_asset(
+        self,
+        path: impl Into<AssetPath<'static>>,
+    ) -> ViewWith<Self, Box<dyn Fn(EntityWorldMut) + Send>> {
+        let path = path.into();
+        self.with(Box::new(move |mut entity: EntityWorldMut| {
+            let handle: Handle<StyleAsset> =
+                entity.world().resource::<AssetServer>().load(path.clone());
+            if entity.get::<ElementStyleAsset>().map(|asset| &asset.0) != Some(&handle) {
+                entity.insert(ElementStyleAsset(handle));
+            }
+        }))
+    }
+
     /// Set the class names for this View. This replaces any existing class names.
     fn class_names<'a, CN: ClassNames<'a>>(self, class_names: CN) -> ViewClasses<Self> {
         ViewClasses::new(self, class_names)
     }
 
+    /// Set an attribute on the output entity of this View, which can be matched by selectors
+    /// such as `[variant]` and `[variant=primary]`. Other attributes are left unchanged.
+    #[allow(clippy::type_complexity)]
+    fn attribute(
+        self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) -> ViewWith<Self, Box<dyn Fn(EntityWorldMut) + Send>> {
+        let name = name.into();
+        let value = value.into();
+        self.with(Box::new(move |mut entity: EntityWorldMut| {
+            match entity.get_mut::<ElementAttributes>() {
+                // Avoid triggering change detection, which would restyle the element.
+                Some(mut attributes) => {
+                    if attributes.get(&name) != Some(value.as_str()) {
+                        attributes.set(name.clone(), value.clone());
+                    }
+                }
+                None => {
+                    let mut attributes = ElementAttributes::default();
+                    attributes.set(name.clone(), value.clone());
+                    entity.insert(attributes);
+                }
+            }
+        }))
+    }
+
     /// Inserts a default instance of the specified component or bundle to the display entity.
     /// This insertion occurs only once per output entity. The entity takes ownership of the
     /// bundle.
@@ -109,6 +177,53 @@ where
         }
     }
 
+    /// Inserts a component on the display entity, replacing it whenever the view is rebuilt
+    /// with a value which differs from the one on the entity. Use this to drive components
+    /// from presenter props. Like [`View::insert`], this panics if the view produces more than
+    /// one output entity.
+    fn insert_component<C: Component + PartialEq>(self, component: C) -> ViewInsert<Self, C> {
+        ViewInsert {
+            inner: self,
+            component: Cell::new(Some(component)),
+        }
+    }
+
+    /// In debug builds, panic with a message naming this view if it doesn't produce exactly
+    /// one node. Use this to guard views which are passed to combinators that require a
+    /// single node, such as [`View::insert`], so that mistakes are reported clearly.
+    fn debug_assert_single_node(self) -> ViewSingleNode<Self> {
+        ViewSingleNode { inner: self }
+    }
+
+    /// Sets up a callback which is called with `true` when the pointer starts hovering over
+    /// the output entity of this `View`, and with `false` when it stops hovering, including
+    /// when the entity is despawned while hovered.
+    #[allow(clippy::type_complexity)]
+    fn on_hover_change(
+        self,
+        callback: impl Fn(bool, &mut World) + Send + Sync + 'static,
+    ) -> ViewWith<Self, Box<dyn Fn(EntityWorldMut) + Send>> {
+        let callback = OnHoverChange(Arc::new(callback));
+        self.with(Box::new(move |mut entity: EntityWorldMut| {
+            entity.insert(callback.clone());
+        }))
+    }
+
+    /// Sets up a callback which is called for keyboard events while the output entity of this
+    /// `View`, or one of its descendants, has [`Focus`](bevy::a11y::Focus). Events which the
+    /// callback doesn't [consume](KeyEvent::consume) bubble up to handlers on ancestor
+    /// elements.
+    #[allow(clippy::type_complexity)]
+    fn on_key(
+        self,
+        callback: impl Fn(&mut KeyEvent, &mut World) + Send + Sync + 'static,
+    ) -> ViewWith<Self, Box<dyn Fn(EntityWorldMut) + Send>> {
+        let callback = OnKey(Arc::new(callback));
+        self.with(Box::new(move |mut entity: EntityWorldMut| {
+            entity.insert(callback.clone());
+        }))
+    }
+
     /// Sets up a callback which is called for each output UiNode generated by this `View`.
     /// Typically used to manipulate components on the entity. This is called each time the
     /// view is rebuilt.
@@ -136,11 +251,44 @@ where
         }
     }
 
+    /// Sets up a callback which is called once each time this `View` is rebuilt, after its
+    /// output nodes have been assembled and their children attached. Useful for coordinating
+    /// with systems outside of Quill.
+    fn after_render<F: Fn(&mut World) + Send>(self, callback: F) -> ViewAfterRender<Self, F> {
+        ViewAfterRender {
+            inner: self,
+            callback,
+        }
+    }
+
     /// Sets up a callback which is called for each output UiNode, but only when the node is first
     /// created.
     fn children<A: ViewTuple>(self, items: A) -> ViewChildren<Self, A> {
         ViewChildren { inner: self, items }
     }
+
+    /// Wraps the output of this view in the single node produced by `wrapper`, which becomes
+    /// the output of the combined view. If this view produces a fragment, all of its nodes
+    /// are nested under the wrapper. The wrapper is reconciled along with the wrapped view
+    /// when rebuilt, and razed along with it.
+    ///
+    /// `"Hello".wrapped_in(Element::new())` is equivalent to `Element::new().children("Hello")`.
+    fn wrapped_in<W: View>(self, wrapper: W) -> ViewChildren<W, Self> {
+        ViewChildren {
+            inner: wrapper,
+            items: self,
+        }
+    }
+
+    /// Generates the children of this view from a presenter closure. The closure behaves like
+    /// a nested presenter: it has its own hook storage, is re-run whenever the resources or
+    /// components it reads change, and is razed along with this view.
+    fn children_fn<C: View + 'static, F: Fn(Cx<()>) -> C + Copy + Send + 'static>(
+        self,
+        items: F,
+    ) -> ViewChildren<Self, F> {
+        ViewChildren { inner: self, items }
+    }
 }
 
 /// View which renders nothing
@@ -190,7 +338,11 @@ impl View for String {
         let nodes = self.nodes(bc, state);
         if let NodeSpan::Node(text_node) = nodes {
             if let Some(mut old_text) = bc.entity_mut(text_node).get_mut::<Text>() {
-                // TODO: compare text for equality.
+                // Leave the text untouched if it's unchanged, so that it isn't re-laid out
+                // or restyled.
+                if old_text.sections.len() == 1 && old_text.sections[0].value == *self {
+                    return;
+                }
                 old_text.sections.clear();
                 old_text.sections.push(TextSection {
                     value: self.to_owned(),
@@ -245,7 +397,11 @@ impl View for &str {
         let nodes = self.nodes(bc, state);
         if let NodeSpan::Node(text_node) = nodes {
             if let Some(mut old_text) = bc.entity_mut(text_node).get_mut::<Text>() {
-                // TODO: compare text for equality.
+                // Leave the text untouched if it's unchanged, so that it isn't re-laid out
+                // or restyled.
+                if old_text.sections.len() == 1 && old_text.sections[0].value == *self {
+                    return;
+                }
                 old_text.sections.clear();
                 old_text.sections.push(TextSection {
                     value: self.to_string(),
@@ -318,7 +474,9 @@ where
 
 /// A trait that allows methods to be added to presenter function references.
 pub trait PresenterFn<Marker: 'static>: Sized + Send + Copy + 'static {
-    /// The type of properties expected by this presenter.
+    /// The type of properties expected by this presenter. Props are compared with the
+    /// previous props when the parent re-renders, and the presenter is only re-run if they
+    /// differ. Closures can be passed as props by wrapping them in a [`crate::Callback`].
     type Props: Send + PartialEq + Clone;
 
     /// The type of view produced by this presenter.
@@ -378,3 +536,64 @@ impl<Inner: View + Sync> View for Arc<Inner> {
         self.as_ref().raze(bc, state)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{plugin::render_frame, If};
+
+    #[derive(Resource)]
+    struct Show(bool);
+
+    fn child(mut cx: Cx) -> impl View {
+        cx.create_entity();
+        "child"
+    }
+
+    fn parent(cx: Cx) -> impl View {
+        If::when(cx.use_resource::<Show>().0, child)
+    }
+
+    #[test]
+    fn test_unchanged_text() {
+        let mut world = World::new();
+        let root = world.spawn_empty().id();
+        let view = "label";
+        let mut state = view.build(&mut BuildContext::new(&mut world, root));
+        let owned = String::from("owned");
+        let mut owned_state = owned.build(&mut BuildContext::new(&mut world, root));
+
+        world.clear_trackers();
+        "label".update(&mut BuildContext::new(&mut world, root), &mut state);
+        String::from("owned").update(&mut BuildContext::new(&mut world, root), &mut owned_state);
+        assert!(!world.entity(state).get_ref::<Text>().unwrap().is_changed());
+        assert!(!world
+            .entity(owned_state)
+            .get_ref::<Text>()
+            .unwrap()
+            .is_changed());
+
+        "changed".update(&mut BuildContext::new(&mut world, root), &mut state);
+        String::from("changed").update(&mut BuildContext::new(&mut world, root), &mut owned_state);
+        for entity in [state, owned_state] {
+            let text = world.entity(entity).get_ref::<Text>().unwrap();
+            assert!(text.is_changed());
+            assert_eq!(text.sections[0].value, "changed");
+        }
+    }
+
+    #[test]
+    fn test_raze_bare_presenter() {
+        let mut world = World::new();
+        world.insert_resource(Show(true));
+        world.spawn(ViewHandle::new(parent, ()));
+        let baseline = world.entities().len();
+        render_frame(&mut world);
+        // Presenter entity, its owned entity, and its text node.
+        assert_eq!(world.entities().len(), baseline + 3);
+
+        world.resource_mut::<Show>().0 = false;
+        render_frame(&mut world);
+        assert_eq!(world.entities().len(), baseline);
+    }
+}
@@ -0,0 +1,111 @@
+use bevy::prelude::*;
+
+use crate::{BuildContext, View};
+
+use crate::node_span::NodeSpan;
+
+/// An implementation of View that calls a callback once each time the view is rebuilt, after
+/// its output has been assembled.
+pub struct ViewAfterRender<V: View, F: Fn(&mut World) + Send> {
+    /// Inner view
+    pub(crate) inner: V,
+
+    /// Callback function called after each rebuild
+    pub(crate) callback: F,
+}
+
+impl<V: View, F: Fn(&mut World) + Send> View for ViewAfterRender<V, F> {
+    /// Inner state, and whether the view was rebuilt since it was last assembled.
+    type State = (V::State, bool);
+
+    fn nodes(&self, bc: &BuildContext, state: &Self::State) -> NodeSpan {
+        self.inner.nodes(bc, &state.0)
+    }
+
+    fn build(&self, bc: &mut BuildContext) -> Self::State {
+        (self.inner.build(bc), true)
+    }
+
+    fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
+        self.inner.update(bc, &mut state.0);
+        state.1 = true;
+    }
+
+    fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
+        // Children aren't attached until the view is assembled, so the callback is deferred
+        // until then. Assembling again without a rebuild doesn't call it a second time.
+        let nodes = self.inner.assemble(bc, &mut state.0);
+        if std::mem::take(&mut state.1) {
+            (self.callback)(bc.world);
+        }
+        nodes
+    }
+
+    fn raze(&self, world: &mut World, state: &mut Self::State) {
+        self.inner.raze(world, &mut state.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::presenter_state::{PresenterGraphChanged, ViewHandle};
+    use crate::{plugin::render_frame, Cx, Element};
+
+    #[derive(Resource)]
+    struct Label(&'static str);
+
+    /// Number of times the callback ran, and the parent of the label's text node each time.
+    #[derive(Resource, Default)]
+    struct Renders(usize, Option<Entity>);
+
+    fn label(cx: Cx) -> impl View {
+        let text = cx.use_resource::<Label>().0;
+        Element::new().children(text).after_render(move |world| {
+            let parent = world
+                .query::<(&Text, &Parent)>()
+                .iter(world)
+                .find(|(t, _)| t.sections[0].value == text)
+                .map(|(_, parent)| parent.get());
+            let mut renders = world.resource_mut::<Renders>();
+            renders.0 += 1;
+            renders.1 = parent;
+        })
+    }
+
+    /// Return the element produced by the presenter.
+    fn element(world: &World, entity: Entity) -> Entity {
+        world
+            .get::<ViewHandle>(entity)
+            .unwrap()
+            .nodes()
+            .iter()
+            .next()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_after_render() {
+        let mut world = World::new();
+        world.insert_resource(Label("one"));
+        world.init_resource::<Renders>();
+        let entity = world.spawn(ViewHandle::new(label, ())).id();
+        render_frame(&mut world);
+        // The label is already attached to the element when the callback runs.
+        let element = element(&world, entity);
+        assert_eq!(world.resource::<Renders>().0, 1);
+        assert_eq!(world.resource::<Renders>().1, Some(element));
+
+        world.resource_mut::<Label>().0 = "two";
+        render_frame(&mut world);
+        world.resource_mut::<Label>().0 = "three";
+        render_frame(&mut world);
+        assert_eq!(world.resource::<Renders>().0, 3);
+        assert_eq!(world.resource::<Renders>().1, Some(element));
+
+        // Re-attaching without a rebuild doesn't run the callback.
+        world.entity_mut(entity).insert(PresenterGraphChanged);
+        render_frame(&mut world);
+        assert_eq!(world.resource::<Renders>().0, 3);
+    }
+}
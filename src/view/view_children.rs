@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::{BuildContext, View, ViewTuple};
+use crate::{BackgroundLayers, BuildContext, View, ViewTuple};
 
 use crate::node_span::NodeSpan;
 
@@ -38,10 +38,16 @@ impl<V: View, A: ViewTuple> View for ViewChildren<V, A> {
         let children = self.items.assemble_spans(bc, &mut state.1);
         if let NodeSpan::Node(parent) = nodes {
             // Attach child view outputs to parent.
-            let mut flat: Vec<Entity> = Vec::with_capacity(children.count());
+            let mut em = bc.entity_mut(parent);
+
+            // Background layer nodes always stay underneath the child views.
+            let mut flat: Vec<Entity> = em
+                .get::<BackgroundLayers>()
+                .map(|layers| layers.nodes().to_vec())
+                .unwrap_or_default();
+            flat.reserve(children.count());
             children.flatten(&mut flat);
 
-            let mut em = bc.entity_mut(parent);
             if let Some(children) = em.get::<Children>() {
                 // See if children changed
                 if !children.eq(&flat) {
@@ -77,3 +83,81 @@ impl<V: View + Clone, A: ViewTuple + Clone> Clone for ViewChildren<V, A> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cx, Element, Fragment, StyleHandle, ViewHandle};
+
+    #[derive(Resource)]
+    struct Counter(i32);
+
+    /// Run the presenter on the given entity, as `render_views` would.
+    fn run_presenter(world: &mut World, entity: Entity) {
+        let inner = world.get::<ViewHandle>(entity).unwrap().inner.clone();
+        let mut bc = BuildContext::new(world, entity);
+        inner.lock().unwrap().build(&mut bc, entity);
+        inner.lock().unwrap().attach(&mut bc, entity);
+    }
+
+    fn text(world: &World, parent: Entity) -> String {
+        let children = world.get::<Children>(parent).unwrap();
+        assert_eq!(children.len(), 1);
+        world.get::<Text>(children[0]).unwrap().sections[0].value.clone()
+    }
+
+    #[test]
+    fn test_children_fn() {
+        let mut world = World::new();
+        world.insert_resource(Counter(1));
+        let root = world.spawn_empty().id();
+        let view = Element::new()
+            .children_fn(|cx: Cx| format!("count: {}", cx.use_resource::<Counter>().0));
+
+        let mut bc = BuildContext::new(&mut world, root);
+        let mut state = view.build(&mut bc);
+        let (element, presenter) = state;
+        run_presenter(bc.world, presenter);
+        view.assemble(&mut BuildContext::new(&mut world, root), &mut state);
+        assert_eq!(text(&world, element), "count: 1");
+
+        // Changing the resource re-runs the closure.
+        world.resource_mut::<Counter>().0 = 2;
+        run_presenter(&mut world, presenter);
+        view.assemble(&mut BuildContext::new(&mut world, root), &mut state);
+        assert_eq!(text(&world, element), "count: 2");
+
+        // Razing the view despawns the presenter and its output.
+        let text_node = world.get::<Children>(element).unwrap()[0];
+        view.raze(&mut world, &mut state);
+        assert!(world.get_entity(element).is_none());
+        assert!(world.get_entity(presenter).is_none());
+        assert!(world.get_entity(text_node).is_none());
+    }
+
+    #[test]
+    fn test_wrapped_in() {
+        let mut world = World::new();
+        let root = world.spawn_empty().id();
+        let style = StyleHandle::build(|ss| ss.padding(4));
+        let view = Fragment::new(("a", "b")).wrapped_in(Element::new().styled(style));
+
+        let mut bc = BuildContext::new(&mut world, root);
+        let mut state = view.build(&mut bc);
+        let nodes = view.assemble(&mut bc, &mut state);
+        let NodeSpan::Node(wrapper) = nodes else {
+            panic!("expected a single node");
+        };
+        // Both nodes of the fragment are nested under the wrapper.
+        let children = world.get::<Children>(wrapper).unwrap().to_vec();
+        assert_eq!(children.len(), 2);
+        let value = |e: Entity| world.get::<Text>(e).unwrap().sections[0].value.clone();
+        assert_eq!(value(children[0]), "a");
+        assert_eq!(value(children[1]), "b");
+
+        // Razing the view despawns the wrapper and the wrapped nodes.
+        view.raze(&mut world, &mut state);
+        assert!(world.get_entity(wrapper).is_none());
+        assert!(children.iter().all(|c| world.get_entity(*c).is_none()));
+    }
+}
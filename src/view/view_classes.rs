@@ -17,25 +17,16 @@ impl<V: View> ViewClasses<V> {
     }
 
     fn set_class_names(&self, nodes: &NodeSpan, bc: &mut BuildContext) {
-        match nodes {
-            NodeSpan::Empty => (),
-            NodeSpan::Node(entity) => {
-                let em = &mut bc.entity_mut(*entity);
-                match em.get_mut::<ElementClasses>() {
-                    Some(mut ec) => {
-                        if !ec.0.eq(&self.class_names) {
-                            ec.as_mut().0.clone_from(&self.class_names);
-                        }
-                    }
-                    None => {
-                        em.insert((ElementClasses(self.class_names.clone()),));
+        for entity in nodes {
+            let em = &mut bc.entity_mut(entity);
+            match em.get_mut::<ElementClasses>() {
+                Some(mut ec) => {
+                    if !ec.0.eq(&self.class_names) {
+                        ec.as_mut().0.clone_from(&self.class_names);
                     }
                 }
-            }
-            NodeSpan::Fragment(ref nodes) => {
-                for node in nodes.iter() {
-                    // Recurse
-                    self.set_class_names(node, bc);
+                None => {
+                    em.insert((ElementClasses(self.class_names.clone()),));
                 }
             }
         }
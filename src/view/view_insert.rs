@@ -0,0 +1,105 @@
+use std::cell::Cell;
+
+use bevy::prelude::*;
+
+use crate::{BuildContext, View};
+
+use crate::node_span::NodeSpan;
+
+/// An implementation of [`View`] that inserts an ECS Component on the generated display entity,
+/// and keeps it up to date. Unlike [`ViewInsertBundle`], the component is compared with the
+/// one on the entity each time the view is rebuilt, and replaced if it differs.
+///
+/// [`ViewInsertBundle`]: super::view_insert_bundle::ViewInsertBundle
+pub struct ViewInsert<V: View, C: Component + PartialEq> {
+    pub(crate) inner: V,
+    pub(crate) component: Cell<Option<C>>,
+}
+
+impl<V: View, C: Component + PartialEq> ViewInsert<V, C> {
+    fn insert_component(&self, nodes: &NodeSpan, bc: &mut BuildContext) {
+        // A fragment containing a single node is the same as the node itself.
+        match nodes.normalize() {
+            NodeSpan::Empty => (),
+            NodeSpan::Node(entity) => {
+                let Some(component) = self.component.take() else {
+                    panic!("No component to insert");
+                };
+                let em = &mut bc.entity_mut(entity);
+                // Leave the component untouched if it's equal, so it isn't marked as changed.
+                if em.get::<C>() != Some(&component) {
+                    em.insert(component);
+                }
+            }
+            NodeSpan::Fragment(ref _nodes) => {
+                panic!("Can only insert into a singular node")
+            }
+        }
+    }
+}
+
+impl<V: View, C: Component + PartialEq> View for ViewInsert<V, C> {
+    type State = V::State;
+
+    fn nodes(&self, bc: &BuildContext, state: &Self::State) -> NodeSpan {
+        self.inner.nodes(bc, state)
+    }
+
+    fn build(&self, bc: &mut BuildContext) -> Self::State {
+        let state = self.inner.build(bc);
+        self.insert_component(&self.inner.nodes(bc, &state), bc);
+        state
+    }
+
+    fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
+        self.inner.update(bc, state);
+        self.insert_component(&self.inner.nodes(bc, state), bc);
+    }
+
+    fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
+        self.inner.assemble(bc, state)
+    }
+
+    fn raze(&self, world: &mut World, state: &mut Self::State) {
+        self.inner.raze(world, state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Element;
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Progress(i32);
+
+    #[test]
+    fn test_insert_component() {
+        let mut world = World::new();
+        let root = world.spawn_empty().id();
+        let view = Element::new().insert_component(Progress(1));
+        let mut state = view.build(&mut BuildContext::new(&mut world, root));
+        let mut nodes = Vec::new();
+        view.nodes(&BuildContext::new(&mut world, root), &state)
+            .flatten(&mut nodes);
+        let entity = nodes[0];
+        assert_eq!(world.get::<Progress>(entity), Some(&Progress(1)));
+
+        // An equal value leaves the component untouched.
+        world.clear_trackers();
+        let view = Element::new().insert_component(Progress(1));
+        view.update(&mut BuildContext::new(&mut world, root), &mut state);
+        assert!(!world
+            .entity(entity)
+            .get_ref::<Progress>()
+            .unwrap()
+            .is_changed());
+
+        // A different value replaces it.
+        let view = Element::new().insert_component(Progress(2));
+        view.update(&mut BuildContext::new(&mut world, root), &mut state);
+        let progress = world.entity(entity).get_ref::<Progress>().unwrap();
+        assert!(progress.is_changed());
+        assert_eq!(*progress, Progress(2));
+    }
+}
@@ -17,10 +17,11 @@ pub struct ViewInsertBundle<V: View, B: Bundle> {
 
 impl<V: View, B: Bundle> ViewInsertBundle<V, B> {
     fn insert_bundle(&self, nodes: &NodeSpan, bc: &mut BuildContext) {
-        match nodes {
+        // A fragment containing a single node is the same as the node itself.
+        match nodes.normalize() {
             NodeSpan::Empty => (),
             NodeSpan::Node(entity) => {
-                let em = &mut bc.entity_mut(*entity);
+                let em = &mut bc.entity_mut(entity);
                 if let Some(bundle) = self.bundle.take() {
                     em.insert(bundle);
                 } else {
@@ -52,7 +53,7 @@ impl<V: View, B: Bundle> View for ViewInsertBundle<V, B> {
         self.inner.update(bc, &mut state.0);
         let nodes = self.inner.nodes(bc, &state.0);
         // Only insert the component when the output entity has changed.
-        if state.1 != nodes {
+        if !state.1.same_entities(&nodes) {
             state.1 = nodes;
             self.insert_bundle(&state.1, bc);
         }
@@ -66,3 +67,60 @@ impl<V: View, B: Bundle> View for ViewInsertBundle<V, B> {
         self.inner.raze(world, &mut state.0);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Marker(i32);
+
+    /// View whose single output entity is optionally wrapped in a fragment.
+    struct Wrapped(bool);
+
+    impl View for Wrapped {
+        type State = Entity;
+
+        fn nodes(&self, _bc: &BuildContext, state: &Self::State) -> NodeSpan {
+            if self.0 {
+                NodeSpan::Fragment(Box::new([NodeSpan::Node(*state)]))
+            } else {
+                NodeSpan::Node(*state)
+            }
+        }
+
+        fn build(&self, bc: &mut BuildContext) -> Self::State {
+            bc.world.spawn_empty().id()
+        }
+
+        fn update(&self, _bc: &mut BuildContext, _state: &mut Self::State) {}
+
+        fn raze(&self, world: &mut World, state: &mut Self::State) {
+            world.despawn(*state);
+        }
+    }
+
+    #[test]
+    fn test_toggle_fragment_wrapping() {
+        let mut world = World::new();
+        let root = world.spawn_empty().id();
+        let view = Wrapped(false).insert(Marker(1));
+        let mut state = view.build(&mut BuildContext::new(&mut world, root));
+        let entity = state.0;
+        assert_eq!(world.get::<Marker>(entity), Some(&Marker(1)));
+
+        // Wrapping the same entity in a fragment doesn't re-insert the bundle.
+        for wrapped in [true, false, true] {
+            let view = Wrapped(wrapped).insert(Marker(2));
+            view.update(&mut BuildContext::new(&mut world, root), &mut state);
+            assert_eq!(world.get::<Marker>(entity), Some(&Marker(1)));
+        }
+
+        // A different entity is still detected.
+        let view = Wrapped(true).insert(Marker(3));
+        let other = world.spawn_empty().id();
+        state.0 = other;
+        view.update(&mut BuildContext::new(&mut world, root), &mut state);
+        assert_eq!(world.get::<Marker>(other), Some(&Marker(3)));
+    }
+}
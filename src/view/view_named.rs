@@ -14,19 +14,9 @@ impl<'a, V: View> ViewNamed<'a, V> {
     }
 
     fn set_name(&self, nodes: &NodeSpan, bc: &mut BuildContext) {
-        match nodes {
-            NodeSpan::Empty => (),
-            NodeSpan::Node(entity) => {
-                let em = &mut bc.entity_mut(*entity);
-                em.insert(Name::new(self.name.to_string()));
-            }
-
-            NodeSpan::Fragment(ref nodes) => {
-                for node in nodes.iter() {
-                    // Recurse
-                    self.set_name(node, bc);
-                }
-            }
+        for entity in nodes {
+            bc.entity_mut(entity)
+                .insert(Name::new(self.name.to_string()));
         }
     }
 }
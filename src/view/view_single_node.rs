@@ -0,0 +1,99 @@
+use bevy::prelude::*;
+
+use crate::{BuildContext, View};
+
+use crate::node_span::NodeSpan;
+
+/// An implementation of [`View`] which checks, in debug builds, that the inner view produces
+/// exactly one node, panicking with a message naming the view if it doesn't. This is useful
+/// for catching mistakes early when the view is passed to something which requires a single
+/// node, such as [`View::insert`]. In release builds, the check is skipped.
+pub struct ViewSingleNode<V: View> {
+    pub(crate) inner: V,
+}
+
+impl<V: View> ViewSingleNode<V> {
+    fn check(nodes: &NodeSpan) {
+        if !cfg!(debug_assertions) {
+            return;
+        }
+        let produced = match nodes {
+            NodeSpan::Node(_) => return,
+            NodeSpan::Empty => "no nodes".to_string(),
+            NodeSpan::Fragment(_) => format!("a fragment of {} nodes", nodes.count()),
+        };
+        panic!(
+            "View `{}` must produce exactly one node, but produced {}",
+            std::any::type_name::<V>(),
+            produced
+        );
+    }
+}
+
+impl<V: View> View for ViewSingleNode<V> {
+    type State = V::State;
+
+    fn nodes(&self, bc: &BuildContext, state: &Self::State) -> NodeSpan {
+        self.inner.nodes(bc, state)
+    }
+
+    fn build(&self, bc: &mut BuildContext) -> Self::State {
+        let state = self.inner.build(bc);
+        Self::check(&self.inner.nodes(bc, &state));
+        state
+    }
+
+    fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
+        self.inner.update(bc, state);
+        Self::check(&self.inner.nodes(bc, state));
+    }
+
+    fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
+        let nodes = self.inner.assemble(bc, state);
+        Self::check(&nodes);
+        nodes
+    }
+
+    fn raze(&self, world: &mut World, state: &mut Self::State) {
+        self.inner.raze(world, state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Element, Fragment};
+
+    #[test]
+    fn test_single_node() {
+        let mut world = World::new();
+        let root = world.spawn_empty().id();
+        let view = Element::new().debug_assert_single_node();
+        let mut bc = BuildContext::new(&mut world, root);
+        let mut state = view.build(&mut bc);
+        assert!(matches!(
+            view.assemble(&mut bc, &mut state),
+            NodeSpan::Node(_)
+        ));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "must produce exactly one node, but produced a fragment of 2 nodes")]
+    fn test_fragment_panics() {
+        let mut world = World::new();
+        let root = world.spawn_empty().id();
+        let view = Fragment::new(("a", "b")).debug_assert_single_node();
+        view.build(&mut BuildContext::new(&mut world, root));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "but produced no nodes")]
+    fn test_empty_panics() {
+        let mut world = World::new();
+        let root = world.spawn_empty().id();
+        let view = ().debug_assert_single_node();
+        view.build(&mut BuildContext::new(&mut world, root));
+    }
+}
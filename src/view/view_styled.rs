@@ -18,29 +18,24 @@ impl<V: View> ViewStyled<V> {
     }
 
     fn insert_styles(&self, nodes: &NodeSpan, bc: &mut BuildContext) {
-        match nodes {
-            NodeSpan::Empty => (),
-            NodeSpan::Node(entity) => {
-                let em = &mut bc.entity_mut(*entity);
-                match em.get_mut::<ElementStyles>() {
-                    Some(mut sc) => {
+        for entity in nodes {
+            let em = &mut bc.entity_mut(entity);
+            match em.get_mut::<ElementStyles>() {
+                Some(mut sc) => {
+                    // Style handles compare by pointer, so this is cheap. Skipping the
+                    // update avoids marking the component as changed, which would
+                    // trigger a restyle.
+                    if sc.styles != self.styles {
                         sc.update(&self.styles);
                     }
-                    None => {
-                        em.insert(ElementStyles::new(&self.styles));
-                    }
                 }
-
-                if em.get_mut::<ElementClasses>().is_none() {
-                    em.insert(ElementClasses::default());
+                None => {
+                    em.insert(ElementStyles::new(&self.styles));
                 }
             }
 
-            NodeSpan::Fragment(ref nodes) => {
-                for node in nodes.iter() {
-                    // Recurse
-                    self.insert_styles(node, bc);
-                }
+            if em.get_mut::<ElementClasses>().is_none() {
+                em.insert(ElementClasses::default());
             }
         }
     }
@@ -93,3 +88,49 @@ where
         self.inner == other.inner && self.styles == other.styles
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+    use crate::Element;
+
+    #[test]
+    fn test_unchanged_styles() {
+        let style = StyleHandle::build(|ss| ss.width(10));
+        let mut world = World::new();
+        let root = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, root);
+
+        let view = ViewStyled::new(Element::new(), style.clone());
+        let mut state = view.build(&mut bc);
+        let changed = bc
+            .entity(state)
+            .get_ref::<ElementStyles>()
+            .unwrap()
+            .last_changed();
+        bc.world.increment_change_tick();
+
+        // Identical styles: component should not be modified.
+        let view = ViewStyled::new(Element::new(), style.clone());
+        view.update(&mut bc, &mut state);
+        let last_changed = bc
+            .entity(state)
+            .get_ref::<ElementStyles>()
+            .unwrap()
+            .last_changed();
+        assert_eq!(last_changed, changed);
+
+        // Different styles: component should be modified.
+        let style2 = StyleHandle::build(|ss| ss.width(20));
+        let view = ViewStyled::new(Element::new(), (style, style2));
+        view.update(&mut bc, &mut state);
+        let last_changed = bc
+            .entity(state)
+            .get_ref::<ElementStyles>()
+            .unwrap()
+            .last_changed();
+        assert_ne!(last_changed, changed);
+    }
+}
@@ -0,0 +1,251 @@
+use std::any::Any;
+
+use bevy::ecs::world::World;
+
+use crate::{BuildContext, View};
+
+use crate::node_span::NodeSpan;
+
+/// Type-erased view for a single arm of a [`Switch`]. Arms can have different view types,
+/// so the state of the active arm is stored as `dyn Any`.
+trait AnyCase: Send {
+    fn nodes(&self, bc: &BuildContext, state: &(dyn Any + Send)) -> NodeSpan;
+    fn build(&self, bc: &mut BuildContext) -> Box<dyn Any + Send>;
+    fn update(&self, bc: &mut BuildContext, state: &mut (dyn Any + Send));
+    fn assemble(&self, bc: &mut BuildContext, state: &mut (dyn Any + Send)) -> NodeSpan;
+    fn raze(&self, world: &mut World, state: &mut (dyn Any + Send));
+}
+
+impl<V: View + 'static> AnyCase for V
+where
+    V::State: 'static,
+{
+    fn nodes(&self, bc: &BuildContext, state: &(dyn Any + Send)) -> NodeSpan {
+        View::nodes(self, bc, downcast_ref::<V>(state))
+    }
+
+    fn build(&self, bc: &mut BuildContext) -> Box<dyn Any + Send> {
+        Box::new(View::build(self, bc))
+    }
+
+    fn update(&self, bc: &mut BuildContext, state: &mut (dyn Any + Send)) {
+        View::update(self, bc, downcast_mut::<V>(state))
+    }
+
+    fn assemble(&self, bc: &mut BuildContext, state: &mut (dyn Any + Send)) -> NodeSpan {
+        View::assemble(self, bc, downcast_mut::<V>(state))
+    }
+
+    fn raze(&self, world: &mut World, state: &mut (dyn Any + Send)) {
+        View::raze(self, world, downcast_mut::<V>(state))
+    }
+}
+
+fn downcast_ref<V: View>(state: &(dyn Any + Send)) -> &V::State
+where
+    V::State: 'static,
+{
+    state
+        .downcast_ref::<V::State>()
+        .expect("Switch case changed view type between renders")
+}
+
+fn downcast_mut<V: View>(state: &mut (dyn Any + Send)) -> &mut V::State
+where
+    V::State: 'static,
+{
+    state
+        .downcast_mut::<V::State>()
+        .expect("Switch case changed view type between renders")
+}
+
+/// State for a [`Switch`]: which arm is mounted, and the state of that arm.
+pub struct SwitchState {
+    /// Position of the active case, or `None` if the fallback is mounted.
+    active: Option<usize>,
+    state: Box<dyn Any + Send>,
+}
+
+/// A conditional view which renders one of several children, selected by comparing a
+/// discriminant computed from `value` against the pattern of each case. If no case matches,
+/// the fallback is rendered (which defaults to nothing).
+///
+/// The active arm is updated in place as long as the same case stays selected; when the
+/// selection changes, the old arm is razed and the new one is built. As with view tuples,
+/// each case position must produce the same view type on every render.
+///
+/// ```ignore
+/// Switch::new(state, |s| match s {
+///     Loading => 0,
+///     Loaded(_) => 1,
+///     Error(_) => 2,
+/// })
+/// .case(0, "Loading...")
+/// .case(1, loaded_view)
+/// .fallback("Something went wrong")
+/// ```
+pub struct Switch<T: Send, F: Fn(&T) -> usize + Send> {
+    value: T,
+    discriminant: F,
+    cases: Vec<(usize, Box<dyn AnyCase>)>,
+    fallback: Box<dyn AnyCase>,
+}
+
+impl<T: Send, F: Fn(&T) -> usize + Send> Switch<T, F> {
+    /// Construct a new Switch View, with no cases.
+    pub fn new(value: T, discriminant: F) -> Self {
+        Self {
+            value,
+            discriminant,
+            cases: Vec::new(),
+            fallback: Box::new(()),
+        }
+    }
+
+    /// Add a case which renders `view` when the discriminant equals `pattern`. If more than
+    /// one case has the same pattern, the first one wins.
+    pub fn case<V: View + 'static>(mut self, pattern: usize, view: V) -> Self
+    where
+        V::State: 'static,
+    {
+        self.cases.push((pattern, Box::new(view)));
+        self
+    }
+
+    /// Set the view which is rendered when no case matches.
+    pub fn fallback<V: View + 'static>(mut self, view: V) -> Self
+    where
+        V::State: 'static,
+    {
+        self.fallback = Box::new(view);
+        self
+    }
+
+    fn select(&self) -> Option<usize> {
+        let key = (self.discriminant)(&self.value);
+        self.cases.iter().position(|(pattern, _)| *pattern == key)
+    }
+
+    fn arm(&self, active: Option<usize>) -> &dyn AnyCase {
+        match active {
+            Some(index) => self.cases[index].1.as_ref(),
+            None => self.fallback.as_ref(),
+        }
+    }
+}
+
+impl<T: Send, F: Fn(&T) -> usize + Send> View for Switch<T, F> {
+    type State = SwitchState;
+
+    fn nodes(&self, bc: &BuildContext, state: &Self::State) -> NodeSpan {
+        self.arm(state.active).nodes(bc, state.state.as_ref())
+    }
+
+    fn build(&self, bc: &mut BuildContext) -> Self::State {
+        let active = self.select();
+        SwitchState {
+            active,
+            state: self.arm(active).build(bc),
+        }
+    }
+
+    fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
+        let active = self.select();
+        if active == state.active {
+            // Mutate state in place
+            self.arm(active).update(bc, state.state.as_mut());
+        } else {
+            // Despawn old state and construct new state
+            self.raze(bc.world, state);
+            bc.mark_changed_shape();
+            *state = SwitchState {
+                active,
+                state: self.arm(active).build(bc),
+            };
+        }
+    }
+
+    fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
+        self.arm(state.active).assemble(bc, state.state.as_mut())
+    }
+
+    fn raze(&self, world: &mut World, state: &mut Self::State) {
+        self.arm(state.active).raze(world, state.state.as_mut());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+
+    enum Load {
+        Pending,
+        Ready(&'static str),
+        Failed,
+    }
+
+    fn switch(load: Load) -> impl View<State = SwitchState> {
+        let label = match load {
+            Load::Ready(label) => label.to_string(),
+            _ => String::new(),
+        };
+        Switch::new(load, |load| match load {
+            Load::Pending => 0,
+            Load::Ready(_) => 1,
+            Load::Failed => 2,
+        })
+        .case(0, "pending")
+        .case(1, label)
+        .fallback("failed")
+    }
+
+    fn text(
+        world: &mut World,
+        view: &impl View<State = SwitchState>,
+        state: &mut SwitchState,
+    ) -> (Entity, String) {
+        let root = world.spawn_empty().id();
+        let mut out = Vec::new();
+        view.assemble(&mut BuildContext::new(world, root), state)
+            .flatten(&mut out);
+        assert_eq!(out.len(), 1);
+        let value = world.get::<Text>(out[0]).unwrap().sections[0].value.clone();
+        (out[0], value)
+    }
+
+    #[test]
+    fn test_switch() {
+        let mut world = World::new();
+        let root = world.spawn_empty().id();
+        let view = switch(Load::Pending);
+        let mut state = view.build(&mut BuildContext::new(&mut world, root));
+        let (pending, value) = text(&mut world, &view, &mut state);
+        assert_eq!(value, "pending");
+        assert_eq!(state.active, Some(0));
+
+        // Switching cases razes the old arm.
+        let view = switch(Load::Ready("ready"));
+        view.update(&mut BuildContext::new(&mut world, root), &mut state);
+        let (ready, _) = text(&mut world, &view, &mut state);
+        assert!(world.get_entity(pending).is_none());
+        assert_eq!(state.active, Some(1));
+
+        // Staying on the same case updates it in place.
+        let view = switch(Load::Ready("still ready"));
+        view.update(&mut BuildContext::new(&mut world, root), &mut state);
+        assert_eq!(
+            text(&mut world, &view, &mut state),
+            (ready, "still ready".to_string())
+        );
+
+        // Unmatched values mount the fallback.
+        let view = switch(Load::Failed);
+        view.update(&mut BuildContext::new(&mut world, root), &mut state);
+        let (_, value) = text(&mut world, &view, &mut state);
+        assert_eq!(value, "failed");
+        assert_eq!(state.active, None);
+        assert!(world.get_entity(ready).is_none());
+    }
+}
@@ -0,0 +1,199 @@
+use bevy::prelude::*;
+
+use crate::{BuildContext, View};
+
+use crate::node_span::NodeSpan;
+
+/// Marker component for text nodes whose sections are styled individually by a [`RichText`]
+/// view. The style system leaves the font, size and color of their sections alone, rather
+/// than replacing them with the inherited text styles.
+#[derive(Component, Default, Clone, Copy)]
+pub struct RichTextNode;
+
+/// A View which renders a single text node made up of several sections, each with its own
+/// [`TextStyle`]. Use this for inline styling, such as a bold word or a colored span within
+/// a sentence.
+///
+/// When rebuilt, the existing text node is kept, and only the sections which differ are
+/// updated. If nothing differs, the [`Text`] component is left untouched, so the text isn't
+/// laid out again.
+///
+/// ```ignore
+/// RichText::new()
+///     .section("Press ", plain.clone())
+///     .section("Space", bold.clone())
+///     .section(" to continue", plain)
+/// ```
+#[derive(Clone, Default)]
+pub struct RichText {
+    sections: Vec<TextSection>,
+}
+
+impl RichText {
+    /// Construct a new `RichText` with no sections.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a section of text with the given style.
+    pub fn section(mut self, value: impl Into<String>, style: TextStyle) -> Self {
+        self.sections.push(TextSection::new(value, style));
+        self
+    }
+}
+
+impl From<Vec<(String, TextStyle)>> for RichText {
+    fn from(sections: Vec<(String, TextStyle)>) -> Self {
+        Self {
+            sections: sections
+                .into_iter()
+                .map(|(value, style)| TextSection::new(value, style))
+                .collect(),
+        }
+    }
+}
+
+fn same_style(a: &TextStyle, b: &TextStyle) -> bool {
+    a.font == b.font && a.font_size == b.font_size && a.color == b.color
+}
+
+fn same_section(a: &TextSection, b: &TextSection) -> bool {
+    a.value == b.value && same_style(&a.style, &b.style)
+}
+
+impl View for RichText {
+    type State = Entity;
+
+    fn nodes(&self, _bc: &BuildContext, state: &Self::State) -> NodeSpan {
+        NodeSpan::Node(*state)
+    }
+
+    fn build(&self, bc: &mut BuildContext) -> Self::State {
+        bc.world
+            .spawn((
+                TextBundle {
+                    text: Text::from_sections(self.sections.clone()),
+                    ..default()
+                },
+                RichTextNode,
+            ))
+            .id()
+    }
+
+    fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
+        let mut entt = bc.entity_mut(*state);
+        let Some(mut text) = entt.get_mut::<Text>() else {
+            // Not a text node any more, so replace it.
+            NodeSpan::Node(*state).despawn(bc.world);
+            bc.mark_changed_shape();
+            *state = self.build(bc);
+            return;
+        };
+
+        // Compare without triggering change detection, and only write the sections which
+        // differ.
+        let sections = &mut text.bypass_change_detection().sections;
+        let mut changed = sections.len() != self.sections.len();
+        sections.truncate(self.sections.len());
+        for (old, new) in sections.iter_mut().zip(self.sections.iter()) {
+            if !same_section(old, new) {
+                old.clone_from(new);
+                changed = true;
+            }
+        }
+        let len = sections.len();
+        sections.extend_from_slice(&self.sections[len..]);
+        if changed {
+            text.set_changed();
+        }
+    }
+
+    fn raze(&self, world: &mut World, state: &mut Self::State) {
+        let mut entt = world.entity_mut(*state);
+        entt.remove_parent();
+        entt.despawn();
+    }
+}
+
+impl PartialEq for RichText {
+    fn eq(&self, other: &Self) -> bool {
+        self.sections.len() == other.sections.len()
+            && self
+                .sections
+                .iter()
+                .zip(other.sections.iter())
+                .all(|(a, b)| same_section(a, b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::Command;
+
+    use super::*;
+    use crate::{ComputedStyle, UpdateComputedStyle};
+
+    fn style(color: Color) -> TextStyle {
+        TextStyle { color, ..default() }
+    }
+
+    #[test]
+    fn test_rich_text() {
+        let mut world = World::new();
+        let root = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, root);
+
+        let view = RichText::new()
+            .section("Hello, ", style(Color::WHITE))
+            .section("World", style(Color::RED));
+        let mut state = view.build(&mut bc);
+        let entity = state;
+        let text = bc.entity(state).get::<Text>().unwrap();
+        assert_eq!(text.sections.len(), 2);
+        assert_eq!(text.sections[1].style.color, Color::RED);
+        let changed = bc.entity(state).get_ref::<Text>().unwrap().last_changed();
+        bc.world.increment_change_tick();
+
+        // Identical sections: the text should not be modified.
+        view.clone().update(&mut bc, &mut state);
+        let last_changed = bc.entity(state).get_ref::<Text>().unwrap().last_changed();
+        assert_eq!(last_changed, changed);
+
+        // Restyling one section updates it in place.
+        let view = RichText::from(vec![
+            ("Hello, ".to_string(), style(Color::WHITE)),
+            ("World".to_string(), style(Color::BLUE)),
+            ("!".to_string(), style(Color::WHITE)),
+        ]);
+        view.update(&mut bc, &mut state);
+        assert_eq!(state, entity);
+        let text = bc.entity(state).get_ref::<Text>().unwrap();
+        assert_ne!(text.last_changed(), changed);
+        assert_eq!(
+            text.sections
+                .iter()
+                .map(|s| s.value.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Hello, ", "World", "!"]
+        );
+        assert_eq!(text.sections[1].style.color, Color::BLUE);
+
+        // Removing sections truncates the text.
+        let view = RichText::new().section("Bye", style(Color::WHITE));
+        view.update(&mut bc, &mut state);
+        let text = bc.entity(state).get::<Text>().unwrap();
+        assert_eq!(text.sections.len(), 1);
+        assert_eq!(text.sections[0].value, "Bye");
+
+        // Inherited text styles don't override the section styles.
+        let mut computed = ComputedStyle::new();
+        computed.color = Some(Color::GREEN);
+        UpdateComputedStyle {
+            entity: state,
+            computed,
+        }
+        .apply(&mut world);
+        let text = world.get::<Text>(state).unwrap();
+        assert_eq!(text.sections[0].style.color, Color::WHITE);
+    }
+}
@@ -0,0 +1,112 @@
+use bevy::ecs::world::World;
+
+use crate::{BuildContext, View};
+
+use crate::node_span::NodeSpan;
+
+/// View which renders a list of views of the same type, as a fragment. Items are matched
+/// by index: when the list is rebuilt, items at overlapping indices are updated in place,
+/// new items are built, and surplus items are razed.
+impl<V: View + Clone> View for Vec<V> {
+    type State = Vec<(V, V::State)>;
+
+    fn nodes(&self, bc: &BuildContext, state: &Self::State) -> NodeSpan {
+        let child_spans: Vec<NodeSpan> = state
+            .iter()
+            .map(|(view, st)| view.nodes(bc, st))
+            .collect();
+        NodeSpan::Fragment(child_spans.into_boxed_slice())
+    }
+
+    fn build(&self, bc: &mut BuildContext) -> Self::State {
+        self.iter()
+            .map(|view| (view.clone(), view.build(bc)))
+            .collect()
+    }
+
+    fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
+        // Update overlapping items.
+        for (view, (prev_view, st)) in self.iter().zip(state.iter_mut()) {
+            view.update(bc, st);
+            *prev_view = view.clone();
+        }
+
+        // Raze surplus items.
+        while state.len() > self.len() {
+            let (view, mut st) = state.pop().unwrap();
+            view.raze(bc.world, &mut st);
+        }
+
+        // Append new items.
+        for view in self.iter().skip(state.len()) {
+            state.push((view.clone(), view.build(bc)));
+        }
+    }
+
+    fn assemble(&self, bc: &mut BuildContext, state: &mut Self::State) -> NodeSpan {
+        let child_spans: Vec<NodeSpan> = state
+            .iter_mut()
+            .map(|(view, st)| view.assemble(bc, st))
+            .collect();
+        NodeSpan::Fragment(child_spans.into_boxed_slice())
+    }
+
+    fn raze(&self, world: &mut World, state: &mut Self::State) {
+        for (view, st) in state.iter_mut() {
+            view.raze(world, st);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+
+    fn text(world: &World, span: &NodeSpan) -> Vec<String> {
+        let mut entities = Vec::new();
+        span.flatten(&mut entities);
+        entities
+            .iter()
+            .map(|e| world.get::<Text>(*e).unwrap().sections[0].value.clone())
+            .collect()
+    }
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_vec_view() {
+        let mut world = World::new();
+        let root = world.spawn_empty().id();
+        let mut bc = BuildContext::new(&mut world, root);
+
+        let view = strings(&["a", "b", "c"]);
+        let mut state = view.build(&mut bc);
+        let span = view.assemble(&mut bc, &mut state);
+        assert_eq!(text(bc.world, &span), strings(&["a", "b", "c"]));
+        let mut first = Vec::new();
+        span.flatten(&mut first);
+
+        // Shrink: overlapping items keep their entities, surplus is despawned.
+        let view = strings(&["x", "y"]);
+        view.update(&mut bc, &mut state);
+        let span = view.assemble(&mut bc, &mut state);
+        assert_eq!(text(bc.world, &span), strings(&["x", "y"]));
+        let mut second = Vec::new();
+        span.flatten(&mut second);
+        assert_eq!(second[..], first[..2]);
+        assert!(bc.world.get_entity(first[2]).is_none());
+
+        // Grow
+        let view = strings(&["x", "y", "z", "w"]);
+        view.update(&mut bc, &mut state);
+        let span = view.assemble(&mut bc, &mut state);
+        assert_eq!(text(bc.world, &span), strings(&["x", "y", "z", "w"]));
+
+        view.raze(bc.world, &mut state);
+        assert_eq!(bc.world.query::<&Text>().iter(bc.world).count(), 0);
+    }
+}
@@ -15,15 +15,8 @@ pub struct ViewWith<V: View, F: Fn(EntityWorldMut) + Send> {
 
 impl<V: View, F: Fn(EntityWorldMut) + Send> ViewWith<V, F> {
     fn with_entity(callback: &F, nodes: &NodeSpan, world: &mut World) {
-        match nodes {
-            NodeSpan::Empty => (),
-            NodeSpan::Node(entity) => callback(world.entity_mut(*entity)),
-            NodeSpan::Fragment(ref nodes) => {
-                for node in nodes.iter() {
-                    // Recurse
-                    Self::with_entity(callback, node, world);
-                }
-            }
+        for entity in nodes {
+            callback(world.entity_mut(entity));
         }
     }
 }
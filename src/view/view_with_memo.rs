@@ -18,15 +18,8 @@ pub struct ViewWithMemo<V: View, D: Clone + PartialEq + Send, F: Fn(EntityWorldM
 
 impl<V: View, D: Clone + PartialEq + Send, F: Fn(EntityWorldMut) + Send> ViewWithMemo<V, D, F> {
     fn with_entity(callback: &F, nodes: &NodeSpan, world: &mut World) {
-        match nodes {
-            NodeSpan::Empty => (),
-            NodeSpan::Node(entity) => callback(world.entity_mut(*entity)),
-            NodeSpan::Fragment(ref nodes) => {
-                for node in nodes.iter() {
-                    // Recurse
-                    Self::with_entity(callback, node, world);
-                }
-            }
+        for entity in nodes {
+            callback(world.entity_mut(entity));
         }
     }
 }
@@ -50,7 +43,7 @@ impl<V: View, D: Clone + PartialEq + Send, F: Fn(EntityWorldMut) + Send> View
     fn update(&self, bc: &mut BuildContext, state: &mut Self::State) {
         self.inner.update(bc, &mut state.0);
         let nodes = self.inner.nodes(bc, &state.0);
-        if state.1 != self.deps || state.2 != nodes {
+        if state.1 != self.deps || !state.2.same_entities(&nodes) {
             state.1 = self.deps.clone();
             state.2 = nodes;
             Self::with_entity(&self.callback, &self.nodes(bc, state), bc.world);